@@ -0,0 +1,60 @@
+use crate::{lingo::act_name, mechanics::Player};
+
+/// Render a small shields-style SVG badge summarizing the player, for READMEs and profiles
+pub fn render(player: &Player) -> String {
+    const HEIGHT: usize = 20;
+    const LABEL_COLOR: &str = "#555";
+    const VALUE_COLOR: &str = "#8d5524";
+
+    let label = format!("Level {} {}", player.level, player.class.name);
+    let value = match &player.task {
+        Some(task) => format!(
+            "{} · {}",
+            act_name(player.quest_book.act()),
+            task.description
+        ),
+        None => act_name(player.quest_book.act()),
+    };
+
+    let (left, right) = (text_width(&label), text_width(&value));
+    let width = left + right;
+    let (label, value, name) = (escape(&label), escape(&value), escape(&player.name));
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{HEIGHT}" role="img" aria-label="{name}: {label}, {value}">
+  <title>{name}: {label}, {value}</title>
+  <clipPath id="r"><rect width="{width}" height="{HEIGHT}" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{left}" height="{HEIGHT}" fill="{LABEL_COLOR}"/>
+    <rect x="{left}" width="{right}" height="{HEIGHT}" fill="{VALUE_COLOR}"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{value_x}" y="14">{value}</text>
+  </g>
+</svg>
+"##,
+        label_x = left / 2,
+        value_x = left + right / 2,
+    )
+}
+
+// a rough estimate of 11px verdana, plus some padding
+fn text_width(text: &str) -> usize {
+    text.chars().count() * 7 + 10
+}
+
+fn escape(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut out, c| {
+            match c {
+                '&' => out.push_str("&amp;"),
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '"' => out.push_str("&quot;"),
+                '\'' => out.push_str("&apos;"),
+                c => out.push(c),
+            }
+            out
+        })
+}
@@ -0,0 +1,150 @@
+//! Estimates of how a hero's adventure will play out, worked out from the pacing formulas instead
+//! of by simulating it.
+//!
+//! Experience and plot progress only come from fighting, and a hero spends roughly
+//! [`COMBAT_SHARE`] of their time doing so. The rest goes to travelling, trading and the like.
+
+use std::time::Duration;
+
+use crate::{
+    mechanics::{plot_time, Legacy, Player, Simulation},
+    rules::RuleSet,
+};
+
+/// The share of game time spent fighting, measured from long simulated runs
+pub const COMBAT_SHARE: f32 = 0.78;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Milestone<T> {
+    pub value: T,
+    /// Game time from now until it's reached
+    pub after: Duration,
+}
+
+/// The levels and acts a hero should reach within some horizon, soonest first
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PacingCurve {
+    pub levels: Vec<Milestone<usize>>,
+    pub acts: Vec<Milestone<i32>>,
+    /// When the hero should ascend, see [`RuleSet::final_act`]
+    pub ascensions: Vec<Duration>,
+}
+
+impl PacingCurve {
+    pub fn next_level(&self) -> Option<Duration> {
+        self.levels.first().map(|milestone| milestone.after)
+    }
+
+    pub fn next_act(&self) -> Option<Duration> {
+        self.acts.first().map(|milestone| milestone.after)
+    }
+}
+
+/// Estimate the levels and acts `player` reaches over the next `horizon` of game time, with the
+/// level times from the player's tuning.
+///
+/// Monsters fleeing under the `rules` are left out, since outleveled monsters are rare
+pub fn project(player: &Player, rules: &RuleSet, horizon: Duration) -> PacingCurve {
+    let tuning = &player.tuning;
+    let budget = horizon.as_secs_f32() * COMBAT_SHARE;
+    let mut exp_percent = player.legacy.exp_percent() as f32 / 100.0;
+
+    let (mut level, mut act) = (player.level, player.quest_book.act());
+    // in seconds of fighting
+    let mut to_level = player.exp_bar.remaining().max(0.0) / exp_percent;
+    let mut to_act = player.quest_book.plot.remaining().max(0.0);
+    let mut spent = 0.0;
+
+    let mut curve = PacingCurve::default();
+    loop {
+        let step = to_level.min(to_act);
+        // levels that take no time at all would be reached forever without time passing
+        if step <= 0.0 || spent + step > budget {
+            break curve;
+        }
+        spent += step;
+        to_level -= step;
+        to_act -= step;
+        let after = Duration::from_secs_f32(spent / COMBAT_SHARE);
+
+        if to_act <= 0.0 {
+            if rules.final_act.is_some_and(|last| act >= last) {
+                curve.ascensions.push(after);
+                level = 1;
+                exp_percent += Legacy::EXP_PERCENT as f32 / 100.0;
+                to_level = tuning.level_up_time(level).as_secs_f32() / exp_percent;
+            }
+            act += 1;
+            curve.acts.push(Milestone { value: act, after });
+            to_act = plot_time(act).as_secs_f32();
+        } else {
+            level += 1;
+            curve.levels.push(Milestone {
+                value: level,
+                after,
+            });
+            to_level = tuning.level_up_time(level).as_secs_f32() / exp_percent;
+        }
+    }
+}
+
+/// Estimate when the hero reaches their next level and act, looking far enough ahead to always
+/// reach both
+pub fn upcoming(simulation: &Simulation) -> PacingCurve {
+    let player = &simulation.player;
+    let horizon = player.tuning.level_up_time(player.level) + plot_time(player.quest_book.act());
+    project(
+        player,
+        simulation.rules(),
+        horizon.mul_f32(1.0 / COMBAT_SHARE),
+    )
+}
+
+#[test]
+fn projection_matches_simulation() {
+    use crate::{mechanics::TickPolicy, testing};
+
+    const HOURS: u64 = 24;
+
+    let mut simulation = testing::simulation();
+    simulation.set_tick_policy(TickPolicy::Turbo);
+    // get through the prologue, which doesn't follow the curve
+    while simulation.player.quest_book.act() < 1 {
+        simulation.tick();
+    }
+
+    let start = simulation.player.elapsed;
+    let curve = project(
+        &simulation.player,
+        simulation.rules(),
+        Duration::from_secs(HOURS * 60 * 60),
+    );
+    while simulation.player.elapsed - start < (HOURS * 60 * 60) as f32 {
+        simulation.tick();
+    }
+
+    let projected = curve.levels.last().unwrap().value;
+    let simulated = simulation.player.level;
+    assert!(
+        projected.abs_diff(simulated) <= 1,
+        "projected level {projected}, simulated {simulated}"
+    );
+    assert!(curve.next_level() < curve.next_act());
+
+    let upcoming = upcoming(&simulation);
+    assert!(upcoming.next_level().is_some() && upcoming.next_act().is_some());
+}
+
+#[test]
+fn instant_levels() {
+    use crate::{config::Tuning, testing};
+
+    let mut simulation = testing::simulation();
+    simulation.set_tuning(Tuning {
+        level_up_minutes: 0,
+        ..Tuning::DEFAULT
+    });
+
+    let curve = upcoming(&simulation);
+    assert!(curve.levels.len() <= 1);
+}
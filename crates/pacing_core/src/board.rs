@@ -0,0 +1,210 @@
+//! A board of optional bonus objectives, posted fresh at the start of every act.
+//!
+//! Objectives are measured from what the hero has already done, like their [`Bestiary`] and
+//! [`Statistics`], so nothing extra needs tracking. A finished objective pays out a little gold
+//! straight away.
+//!
+//! [`Bestiary`]: crate::mechanics::Bestiary
+//! [`Statistics`]: crate::mechanics::Statistics
+
+use crate::{
+    config::{self, Rated as _},
+    lingo::indefinite,
+    mechanics::Player,
+    Rand, SliceExt,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Objective {
+    /// Kill `count` of one kind of monster
+    Slay {
+        monster: String,
+        count: usize,
+    },
+    /// Kill `count` monsters of any kind
+    Slaughter {
+        count: usize,
+    },
+    /// Have `gold` on hand at once
+    Amass {
+        gold: usize,
+    },
+    Gather {
+        count: usize,
+    },
+    Reach {
+        level: usize,
+    },
+}
+
+impl Objective {
+    /// The measure this objective is tracked by, before any baseline is taken off
+    fn measure(&self, player: &Player) -> usize {
+        match self {
+            Self::Slay { monster, .. } => player.bestiary.kills(monster),
+            Self::Slaughter { .. } => player.bestiary.total_kills(),
+            Self::Amass { .. } => player.inventory.gold().max(0) as usize,
+            Self::Gather { .. } => player.statistics.materials_gathered,
+            Self::Reach { .. } => player.level,
+        }
+    }
+
+    pub const fn goal(&self) -> usize {
+        match *self {
+            Self::Slay { count, .. } | Self::Slaughter { count } | Self::Gather { count } => count,
+            Self::Amass { gold } => gold,
+            Self::Reach { level } => level,
+        }
+    }
+
+    /// Counting objectives only count what happens after they're posted
+    const fn counts_from_posting(&self) -> bool {
+        !matches!(self, Self::Amass { .. } | Self::Reach { .. })
+    }
+}
+
+impl std::fmt::Display for Objective {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Slay { monster, count } => write!(
+                f,
+                "Kill {} this act",
+                indefinite(&monster.to_lowercase(), *count)
+            ),
+            Self::Slaughter { count } => write!(f, "Kill {count} monsters this act"),
+            Self::Amass { gold } => write!(f, "Amass {gold} gold"),
+            Self::Gather { count } => write!(f, "Gather {}", indefinite("material", *count)),
+            Self::Reach { level } => write!(f, "Reach level {level}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Bounty {
+    pub objective: Objective,
+    /// What the objective's measure was when it was posted
+    baseline: usize,
+    pub reward: isize,
+    pub claimed: bool,
+}
+
+impl Bounty {
+    fn new(objective: Objective, player: &Player) -> Self {
+        let baseline = if objective.counts_from_posting() {
+            objective.measure(player)
+        } else {
+            0
+        };
+        Self {
+            objective,
+            baseline,
+            reward: player.equipment_price() / 2,
+            claimed: false,
+        }
+    }
+
+    /// How far along the objective is, up to its goal
+    pub fn progress(&self, player: &Player) -> usize {
+        (self.objective.measure(player).saturating_sub(self.baseline)).min(self.objective.goal())
+    }
+
+    pub fn is_done(&self, player: &Player) -> bool {
+        self.progress(player) >= self.objective.goal()
+    }
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct QuestBoard {
+    /// The act the bounties were posted for
+    act: i32,
+    bounties: Vec<Bounty>,
+}
+
+impl QuestBoard {
+    const POSTED: usize = 3;
+
+    /// Is the board from an earlier act?
+    pub fn is_stale(&self, player: &Player) -> bool {
+        self.bounties.is_empty() || self.act != player.quest_book.act()
+    }
+
+    /// Take down the old bounties and post new ones for the current act
+    pub fn post(&mut self, player: &Player, rng: &Rand) {
+        let level = player.level;
+        let content = config::content();
+        let monsters = content
+            .monsters
+            .iter()
+            .filter(|monster| monster.level.abs_diff(level) <= 3)
+            .filter(|monster| player.rating.allows(monster.rating()))
+            .collect::<Vec<_>>();
+
+        let mut objectives = vec![
+            Objective::Slaughter {
+                count: 30 + 10 * rng.below(4),
+            },
+            // rounded to make for a nicer number
+            Objective::Amass {
+                gold: (player.equipment_price() as usize * 3 / 100 + 1) * 100,
+            },
+            Objective::Gather {
+                count: 3 + rng.below(3),
+            },
+            Objective::Reach { level: level + 2 },
+        ];
+        if !monsters.is_empty() {
+            objectives.push(Objective::Slay {
+                monster: monsters.choice(rng).name.to_string(),
+                count: 10 + 5 * rng.below(3),
+            })
+        }
+
+        self.bounties.clear();
+        while self.bounties.len() < Self::POSTED && !objectives.is_empty() {
+            let objective = objectives.swap_remove(rng.below(objectives.len()));
+            self.bounties.push(Bounty::new(objective, player));
+        }
+        self.act = player.quest_book.act();
+    }
+
+    /// Mark everything that's been done as claimed, returning what was just claimed
+    pub fn claim(&mut self, player: &Player) -> Vec<Bounty> {
+        self.bounties
+            .iter_mut()
+            .filter(|bounty| !bounty.claimed && bounty.is_done(player))
+            .map(|bounty| {
+                bounty.claimed = true;
+                bounty.clone()
+            })
+            .collect()
+    }
+
+    pub fn bounties(&self) -> impl ExactSizeIterator<Item = &Bounty> {
+        self.bounties.iter()
+    }
+}
+
+#[test]
+fn bounties_pay_out() {
+    use crate::{mechanics::TickPolicy, testing};
+
+    let mut simulation = testing::simulation();
+    simulation.set_tick_policy(TickPolicy::Turbo);
+    simulation.tick();
+    simulation.tick();
+
+    let player = &simulation.player;
+    assert_eq!(player.board.bounties().len(), QuestBoard::POSTED);
+    assert!(!player.board.is_stale(player));
+
+    let mut board = player.board.clone();
+    let mut player = player.clone();
+    player.level += 10;
+    player.inventory.add_gold(1_000_000);
+    let claimed = board.claim(&player);
+    assert!(claimed.iter().all(|bounty| matches!(
+        bounty.objective,
+        Objective::Amass { .. } | Objective::Reach { .. }
+    )));
+    assert!(board.claim(&player).is_empty());
+}
@@ -0,0 +1,71 @@
+use crate::{
+    highlights::Scoring,
+    integrity::TAMPERED,
+    lingo::act_name,
+    mechanics::Player,
+    portrait::{Portrait, Rgb},
+};
+
+/// Render a character card with the player's portrait and stats, using ANSI truecolor escapes
+pub fn ansi(player: &Player) -> String {
+    const RESET: &str = "\x1b[0m";
+    const BOLD: &str = "\x1b[1m";
+    const DIM: &str = "\x1b[2m";
+
+    // each line of text draws two rows of the portrait with a half block
+    let portrait = Portrait::of(player)
+        .rows()
+        .collect::<Vec<_>>()
+        .chunks(2)
+        .map(|pair| {
+            let (top, bottom) = (pair[0], pair.get(1).unwrap_or(&pair[0]));
+            top.iter()
+                .zip(bottom.iter())
+                .fold(String::new(), |mut line, (&top, &bottom)| {
+                    let (Rgb(r, g, b), Rgb(br, bg, bb)) = (top, bottom);
+                    line += &format!("\x1b[38;2;{r};{g};{b}m\x1b[48;2;{br};{bg};{bb}m▀");
+                    line
+                })
+                + RESET
+        })
+        .collect::<Vec<_>>();
+
+    let mut info = vec![
+        format!("{BOLD}{}{RESET}", player.name),
+        format!(
+            "Level {} {} {}",
+            player.level, player.race.name, player.class.name
+        ),
+        format!("{DIM}{}{RESET}", act_name(player.quest_book.act())),
+    ];
+    if player.tampered {
+        info.push(format!("{DIM}{TAMPERED}{RESET}"));
+    }
+    let stats = player.stats.iter().collect::<Vec<_>>();
+    info.extend(stats.chunks(2).map(|pair| {
+        pair.iter()
+            .map(|(stat, value)| format!("{:<6} {value:>3}", stat.as_str()))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }));
+
+    let blank = " ".repeat(Portrait::WIDTH);
+    let mut card = (0..portrait.len().max(info.len()))
+        .map(|i| {
+            format!(
+                "{}  {}\n",
+                portrait.get(i).unwrap_or(&blank),
+                info.get(i).map(|s| &**s).unwrap_or_default()
+            )
+        })
+        .collect::<String>();
+
+    let highlights = Scoring::default().top(&player.journal, 3);
+    if !highlights.is_empty() {
+        card += &format!("{BOLD}Highlights{RESET}\n");
+        for highlight in highlights {
+            card += &format!("- {}\n", highlight.entry.event);
+        }
+    }
+    card
+}
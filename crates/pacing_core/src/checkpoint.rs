@@ -0,0 +1,339 @@
+use std::{collections::VecDeque, time::Duration};
+
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+use crate::{
+    config,
+    format::Time,
+    journal::Event,
+    lingo::{act_name, indefinite},
+    mechanics::{Player, Simulation, Stats},
+};
+
+/// A summary of a player at some point in time, for comparing against later
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Checkpoint {
+    pub elapsed: f32,
+    pub level: usize,
+    pub gold: isize,
+    pub act: i32,
+    pub quests: usize,
+    pub stats: Stats,
+    /// Names and quantities
+    pub items: Vec<(String, usize)>,
+    pub spells: Vec<String>,
+    pub equipment: Vec<(config::Equipment, String)>,
+    pub task: Option<String>,
+}
+
+impl Checkpoint {
+    pub fn of(player: &Player) -> Self {
+        Self {
+            elapsed: player.elapsed,
+            level: player.level,
+            gold: player.inventory.gold(),
+            act: player.quest_book.act(),
+            quests: player.quest_book.total(),
+            stats: player.stats.clone(),
+            items: player
+                .inventory
+                .items()
+                .map(|(name, quantity)| (name.clone(), *quantity))
+                .collect(),
+            spells: player
+                .spell_book
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            equipment: player
+                .equipment
+                .iter()
+                .map(|(slot, name)| (slot, name.to_string()))
+                .collect(),
+            task: player
+                .task
+                .as_ref()
+                .map(|task| task.description.to_string()),
+        }
+    }
+
+    pub fn diff(&self, older: &Self) -> StateDiff {
+        fn added<'a, T: PartialEq + Clone + 'a>(
+            new: &'a [T],
+            old: &'a [T],
+        ) -> impl Iterator<Item = T> + 'a {
+            new.iter().filter(|item| !old.contains(item)).cloned()
+        }
+
+        StateDiff {
+            elapsed: self.elapsed - older.elapsed,
+            level: (older.level, self.level),
+            gold: (older.gold, self.gold),
+            act: (older.act, self.act),
+            quests: self.quests.saturating_sub(older.quests),
+            new_items: self
+                .items
+                .iter()
+                .filter(|(name, _)| !older.items.iter().any(|(old, _)| old == name))
+                .map(|(name, _)| name.clone())
+                .collect(),
+            new_spells: added(&self.spells, &older.spells).collect(),
+            new_equipment: added(&self.equipment, &older.equipment).collect(),
+        }
+    }
+}
+
+impl Simulation {
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint::of(&self.player)
+    }
+
+    pub fn diff(&self, older: &Checkpoint) -> StateDiff {
+        self.checkpoint().diff(older)
+    }
+}
+
+/// A [`Checkpoint`] every [`Self::INTERVAL`] of game time, going back [`Self::SPAN`], for
+/// scrubbing back through recent play. The checkpoints are copies, so looking at them can't affect
+/// the game
+#[derive(Clone, Debug, Default)]
+pub struct Timeline {
+    checkpoints: VecDeque<Checkpoint>,
+}
+
+impl Timeline {
+    pub const INTERVAL: f32 = 30.0;
+    pub const SPAN: f32 = 60.0 * 60.0;
+
+    /// Take a checkpoint if it's been long enough since the last one
+    pub fn observe(&mut self, player: &Player) {
+        if self
+            .checkpoints
+            .back()
+            .is_none_or(|last| player.elapsed - last.elapsed >= Self::INTERVAL)
+        {
+            self.checkpoints.push_back(Checkpoint::of(player));
+        }
+
+        while self
+            .checkpoints
+            .front()
+            .is_some_and(|first| player.elapsed - first.elapsed > Self::SPAN)
+        {
+            self.checkpoints.pop_front();
+        }
+    }
+
+    /// Oldest first
+    pub fn checkpoints(&self) -> impl ExactSizeIterator<Item = &Checkpoint> + DoubleEndedIterator {
+        self.checkpoints.iter()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Checkpoint> {
+        self.checkpoints.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+}
+
+/// A play session, started when a player is loaded and ended when the app quits
+#[derive(Clone, Debug)]
+pub struct Session {
+    started: Instant,
+    checkpoint: Checkpoint,
+}
+
+impl Session {
+    pub fn start(player: &Player) -> Self {
+        Self {
+            started: Instant::now(),
+            checkpoint: Checkpoint::of(player),
+        }
+    }
+
+    /// Summarize the session and write it to the player's journal
+    pub fn end(&self, player: &mut Player) -> SessionSummary {
+        let summary = SessionSummary {
+            played: self.started.elapsed(),
+            diff: Checkpoint::of(player).diff(&self.checkpoint),
+        };
+        player.journal.record(
+            player.elapsed,
+            Event::SessionEnded {
+                played: summary.played.as_secs(),
+                levels: summary.diff.levels(),
+                gold: summary.diff.gold(),
+            },
+        );
+        summary
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SessionSummary {
+    /// Real time spent playing, which differs from the game time in the diff when time is scaled
+    pub played: Duration,
+    pub diff: StateDiff,
+}
+
+impl std::fmt::Display for SessionSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Played for {}", Time::compact(self.played.as_secs_f32()))?;
+        self.diff.fmt(f)
+    }
+}
+
+/// What changed between two [`Checkpoint`]s
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct StateDiff {
+    pub elapsed: f32,
+    pub level: (usize, usize),
+    pub gold: (isize, isize),
+    pub act: (i32, i32),
+    pub quests: usize,
+    pub new_items: Vec<String>,
+    pub new_spells: Vec<String>,
+    pub new_equipment: Vec<(config::Equipment, String)>,
+}
+
+impl StateDiff {
+    pub const fn levels(&self) -> usize {
+        self.level.1.saturating_sub(self.level.0)
+    }
+
+    pub const fn gold(&self) -> isize {
+        self.gold.1 - self.gold.0
+    }
+
+    pub const fn acts(&self) -> i32 {
+        self.act.1 - self.act.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels() == 0
+            && self.gold() == 0
+            && self.acts() == 0
+            && self.quests == 0
+            && self.new_items.is_empty()
+            && self.new_spells.is_empty()
+            && self.new_equipment.is_empty()
+    }
+}
+
+impl std::fmt::Display for StateDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Over {}:", Time::compact(self.elapsed))?;
+        if self.is_empty() {
+            return writeln!(f, "- nothing of note happened");
+        }
+
+        if self.levels() > 0 {
+            writeln!(
+                f,
+                "- gained {} (now level {})",
+                indefinite("level", self.levels()),
+                self.level.1
+            )?;
+        }
+
+        if self.gold() != 0 {
+            writeln!(f, "- gold {:+} (now {})", self.gold(), self.gold.1)?;
+        }
+
+        if self.acts() > 0 {
+            writeln!(
+                f,
+                "- completed {} (now in {})",
+                indefinite("act", self.acts() as _),
+                act_name(self.act.1)
+            )?;
+        }
+
+        if self.quests > 0 {
+            writeln!(f, "- completed {}", indefinite("quest", self.quests))?;
+        }
+
+        for (label, list) in [
+            ("new items", &self.new_items),
+            ("new spells", &self.new_spells),
+        ] {
+            if !list.is_empty() {
+                writeln!(f, "- {label}: {}", list.join(", "))?;
+            }
+        }
+
+        for (slot, name) in &self.new_equipment {
+            writeln!(f, "- new {slot}: {name}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn diff() {
+    use crate::{mechanics::Stats, Rand};
+
+    let rng = Rand::seed(42);
+    let mut player = Player::new(
+        "Tester",
+        config::RACES[0].clone(),
+        config::CLASSES[0].clone(),
+        Stats::new(config::ALL_STATS.into_iter().map(|stat| (stat, 10))),
+    );
+    let older = Checkpoint::of(&player);
+    assert!(Checkpoint::of(&player).diff(&older).is_empty());
+
+    player.elapsed += 60.0 * 60.0;
+    player.level_up(&rng);
+    player.inventory.add_gold(150);
+    player.inventory.add_item("ant antenna", 2);
+    player
+        .quest_book
+        .add_quest("Fetch me a sock", None, player.elapsed);
+    player.quest_book.next_act();
+
+    let diff = Checkpoint::of(&player).diff(&older);
+    assert_eq!(diff.levels(), 1);
+    assert_eq!(diff.gold(), 150);
+    assert_eq!(diff.acts(), 1);
+    assert_eq!(diff.quests, 1);
+    assert_eq!(diff.new_items, ["ant antenna"]);
+    assert!(!diff.new_spells.is_empty());
+
+    let text = diff.to_string();
+    assert!(text.starts_with("Over 1h:"), "{text}");
+    assert!(text.contains("gained a level (now level 2)"), "{text}");
+    assert!(text.contains("gold +150"), "{text}");
+}
+
+#[test]
+fn timeline() {
+    let mut player = Player::new(
+        "Tester",
+        config::RACES[0].clone(),
+        config::CLASSES[0].clone(),
+        Stats::new(config::ALL_STATS.into_iter().map(|stat| (stat, 10))),
+    );
+    let mut timeline = Timeline::default();
+    for _ in 0..2 * 60 * 6 {
+        player.elapsed += 10.0;
+        player.inventory.add_gold(1);
+        timeline.observe(&player);
+    }
+
+    let full = (Timeline::SPAN / Timeline::INTERVAL) as usize;
+    assert!((full..=full + 1).contains(&timeline.len()));
+    let first = timeline.get(0).unwrap();
+    assert!(player.elapsed - first.elapsed <= Timeline::SPAN);
+    assert!(first.gold < player.inventory.gold());
+}
@@ -0,0 +1,432 @@
+//! Everything a hero can earn or come down with, whether they have yet or not: achievements,
+//! titles and effects.
+//!
+//! These are read-only lists for front-ends and exporters to show in full, with each entry's
+//! [`Unlock`] saying what it takes to get it. A [content pack](config::ContentPack) can add
+//! achievements and titles to the built-in ones, and the hazards behind most effects come from it.
+//!
+//! Earning an achievement or a title from a pack is recorded in the journal as
+//! [`Event::Unlocked`](crate::journal::Event::Unlocked). Slayer titles and effects have events of their own.
+
+use std::{borrow::Cow, collections::BTreeSet};
+
+use crate::{
+    config::{self, Family, Stat},
+    format::Time,
+    mechanics::{Bestiary, Player, Simulation, Statistics},
+};
+
+/// One achievement, title or effect
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct Entry {
+    /// Stays the same between versions, for remembering which entries were seen
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub unlock: Unlock,
+}
+
+impl Entry {
+    pub fn is_unlocked(&self, player: &Player) -> bool {
+        self.unlock.is_met(player)
+    }
+}
+
+/// What it takes to get an [`Entry`]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Unlock {
+    Level(usize),
+    /// Starting an act, the prologue is act 0
+    Act(i32),
+    /// One of the [`Statistics`] reaching at least this much
+    Tally(Tally, usize),
+    /// Slaying at least this many of a family
+    Slayer(Family, usize),
+    /// Avoiding or suffering a hazard, see [`Statistics::hazards`]
+    Hazard {
+        name: Cow<'static, str>,
+        passed: bool,
+    },
+    /// Being left for dead by a hazard
+    LastStand,
+}
+
+impl Unlock {
+    pub fn is_met(&self, player: &Player) -> bool {
+        match self {
+            Self::Level(level) => player.level >= *level,
+            Self::Act(act) => player.quest_book.act() >= *act,
+            Self::Tally(tally, at_least) => tally.of(&player.statistics) >= *at_least,
+            Self::Slayer(family, kills) => player.bestiary.family_kills(*family) >= *kills,
+            Self::Hazard { name, passed } => {
+                player.statistics.hazards.get(&**name).map_or(0, |tally| {
+                    if *passed {
+                        tally.avoided
+                    } else {
+                        tally.suffered
+                    }
+                }) > 0
+            }
+            Self::LastStand => player.statistics.last_stands > 0,
+        }
+    }
+}
+
+impl std::fmt::Display for Unlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Level(level) => write!(f, "Reach level {level}"),
+            Self::Act(0) => f.write_str("Start the prologue"),
+            Self::Act(act) => write!(f, "Start act {act}"),
+            Self::Tally(tally, at_least) => write!(f, "{}: {at_least}", tally.as_str()),
+            Self::Slayer(family, kills) => write!(f, "Slay {kills} {family}"),
+            Self::Hazard { name, passed } => {
+                let outcome = if *passed { "Avoid" } else { "Suffer" };
+                write!(f, "{outcome} a {name}")
+            }
+            Self::LastStand => f.write_str("Be left for dead"),
+        }
+    }
+}
+
+/// The [`Statistics`] an achievement can count
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Tally {
+    Kills,
+    QuestsCompleted,
+    GoldEarned,
+    ItemsSold,
+    MaterialsGathered,
+    HazardsAvoided,
+    TasksFinished,
+}
+
+impl Tally {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Kills => "Kills",
+            Self::QuestsCompleted => "Quests completed",
+            Self::GoldEarned => "Gold earned",
+            Self::ItemsSold => "Items sold",
+            Self::MaterialsGathered => "Materials gathered",
+            Self::HazardsAvoided => "Hazards avoided",
+            Self::TasksFinished => "Tasks finished",
+        }
+    }
+
+    pub fn of(self, statistics: &Statistics) -> usize {
+        match self {
+            Self::Kills => statistics.kills,
+            Self::QuestsCompleted => statistics.quests_completed,
+            Self::GoldEarned => statistics.gold_earned.max(0) as usize,
+            Self::ItemsSold => statistics.items_sold,
+            Self::MaterialsGathered => statistics.materials_gathered,
+            Self::HazardsAvoided => statistics.hazards_avoided,
+            Self::TasksFinished => statistics.tasks_finished,
+        }
+    }
+}
+
+/// An achievement or a title, as it's given in a content pack
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Achievement {
+    pub id: Cow<'static, str>,
+    pub name: Cow<'static, str>,
+    pub description: Cow<'static, str>,
+    pub unlock: Unlock,
+}
+
+impl Achievement {
+    pub const fn new(
+        id: &'static str,
+        name: &'static str,
+        description: &'static str,
+        unlock: Unlock,
+    ) -> Self {
+        Self {
+            id: Cow::Borrowed(id),
+            name: Cow::Borrowed(name),
+            description: Cow::Borrowed(description),
+            unlock,
+        }
+    }
+}
+
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement::new(
+        "first-blood",
+        "First Blood",
+        "Win a fight",
+        Unlock::Tally(Tally::Kills, 1),
+    ),
+    Achievement::new(
+        "centurion",
+        "Centurion",
+        "Win a hundred fights",
+        Unlock::Tally(Tally::Kills, 100),
+    ),
+    Achievement::new(
+        "errand-runner",
+        "Errand Runner",
+        "Complete ten quests",
+        Unlock::Tally(Tally::QuestsCompleted, 10),
+    ),
+    Achievement::new(
+        "pillar-of-the-community",
+        "Pillar of the Community",
+        "Complete a hundred quests",
+        Unlock::Tally(Tally::QuestsCompleted, 100),
+    ),
+    Achievement::new(
+        "pocket-money",
+        "Pocket Money",
+        "Earn a thousand gold",
+        Unlock::Tally(Tally::GoldEarned, 1_000),
+    ),
+    Achievement::new(
+        "tycoon",
+        "Tycoon",
+        "Earn a million gold",
+        Unlock::Tally(Tally::GoldEarned, 1_000_000),
+    ),
+    Achievement::new(
+        "haggler",
+        "Haggler",
+        "Sell a hundred items",
+        Unlock::Tally(Tally::ItemsSold, 100),
+    ),
+    Achievement::new(
+        "forager",
+        "Forager",
+        "Gather fifty materials",
+        Unlock::Tally(Tally::MaterialsGathered, 50),
+    ),
+    Achievement::new(
+        "sure-footed",
+        "Sure-Footed",
+        "Avoid twenty-five hazards",
+        Unlock::Tally(Tally::HazardsAvoided, 25),
+    ),
+    Achievement::new(
+        "back-from-the-brink",
+        "Back from the Brink",
+        "Survive being left for dead",
+        Unlock::LastStand,
+    ),
+    Achievement::new(
+        "out-of-the-prologue",
+        "Out of the Prologue",
+        "Finish the prologue",
+        Unlock::Act(1),
+    ),
+    Achievement::new("epic", "Epic", "Make it to the fifth act", Unlock::Act(5)),
+    Achievement::new("seasoned", "Seasoned", "Reach level 10", Unlock::Level(10)),
+    Achievement::new(
+        "legendary",
+        "Legendary",
+        "Reach level 50",
+        Unlock::Level(50),
+    ),
+];
+
+impl From<&Achievement> for Entry {
+    fn from(achievement: &Achievement) -> Self {
+        Self {
+            id: achievement.id.to_string(),
+            name: achievement.name.to_string(),
+            description: achievement.description.to_string(),
+            unlock: achievement.unlock.clone(),
+        }
+    }
+}
+
+/// `builtin` followed by `extra`, where an extra entry with a built-in id replaces it
+fn extend(builtin: &[Achievement], extra: &[Achievement]) -> Vec<Entry> {
+    builtin
+        .iter()
+        .filter(|achievement| extra.iter().all(|other| other.id != achievement.id))
+        .chain(extra)
+        .map(Entry::from)
+        .collect()
+}
+
+/// Every built-in achievement, and any the current content pack adds
+pub fn achievements() -> Vec<Entry> {
+    extend(ACHIEVEMENTS, &config::content().achievements)
+}
+
+/// Every slayer title, see [`Bestiary::title`], and any titles the current content pack adds
+pub fn titles() -> Vec<Entry> {
+    let mut titles = slayer_titles();
+    titles.extend(extend(&[], &config::content().codex_titles));
+    titles
+}
+
+fn slayer_titles() -> Vec<Entry> {
+    Family::ALL
+        .into_iter()
+        .flat_map(|family| {
+            Bestiary::SLAYER_KILLS
+                .into_iter()
+                .zip(1..)
+                .filter_map(move |(kills, rank)| {
+                    Some(Entry {
+                        id: format!("slayer-{}-{rank}", slug(family.as_str())),
+                        name: family.slayer_title(rank)?,
+                        description: format!("Slay {kills} {family}"),
+                        unlock: Unlock::Slayer(family, kills),
+                    })
+                })
+        })
+        .collect()
+}
+
+/// Every effect that can weaken or bolster the hero, from the hazards in the current content pack
+/// and a last stand
+pub fn effects() -> Vec<Entry> {
+    let hazard = Time::compact(Simulation::HAZARD_EFFECT);
+    let mut effects = config::content()
+        .hazards
+        .iter()
+        .flat_map(|config::Hazard { name, stat, .. }| {
+            [(true, "+1", "avoided"), (false, "-1", "suffered")].map(|(passed, amount, outcome)| {
+                Entry {
+                    id: format!("hazard-{}-{outcome}", slug(name)),
+                    name: format!("{} {outcome}", capitalize(name)),
+                    description: format!("{amount} {stat} for {hazard}"),
+                    unlock: Unlock::Hazard {
+                        name: name.clone(),
+                        passed,
+                    },
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let weakened = [Stat::Strength, Stat::Condition, Stat::Dexterity].map(|stat| stat.as_str());
+    effects.push(Entry {
+        id: String::from("last-stand"),
+        name: String::from("Left for dead"),
+        description: format!(
+            "-{} {} for {}",
+            Simulation::LAST_STAND_WEAKNESS,
+            weakened.join(", "),
+            Time::compact(Simulation::LAST_STAND_RECOVERY)
+        ),
+        unlock: Unlock::LastStand,
+    });
+    effects
+}
+
+/// The achievements and content pack titles `player` has earned since this was last called, which
+/// are remembered in `unlocked`. The first call for an older save only remembers them, so what was
+/// earned before isn't announced all at once
+pub(crate) fn newly_unlocked(
+    player: &Player,
+    unlocked: &mut Option<BTreeSet<String>>,
+) -> Vec<Entry> {
+    let announce = unlocked.is_some();
+    let unlocked = unlocked.get_or_insert_with(BTreeSet::new);
+    let content = config::content();
+    let entries = extend(ACHIEVEMENTS, &content.achievements)
+        .into_iter()
+        .chain(extend(&[], &content.codex_titles));
+
+    let mut new = vec![];
+    for entry in entries {
+        if !unlocked.contains(&entry.id) && entry.is_unlocked(player) {
+            unlocked.insert(entry.id.clone());
+            new.push(entry);
+        }
+    }
+    if !announce {
+        new.clear()
+    }
+    new
+}
+
+/// `name` in lowercase, with dashes between the words
+fn slug(name: &str) -> String {
+    name.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+#[test]
+fn codex() {
+    use crate::mechanics::StatsBuilder;
+
+    let mut ids = achievements()
+        .into_iter()
+        .chain(titles())
+        .chain(effects())
+        .map(|entry| entry.id)
+        .collect::<Vec<_>>();
+    let len = ids.len();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), len);
+
+    assert_eq!(titles()[0].name, "Slayer of Undead");
+    assert_eq!(effects()[0].name, "Pit trap avoided");
+    assert_eq!(effects()[0].description, "+1 DEX for 10m");
+
+    let mut player = Player::new(
+        "Tester",
+        config::RACES[0].clone(),
+        config::CLASSES[0].clone(),
+        StatsBuilder::default().roll(&crate::Rand::seed(42)),
+    );
+    let first_blood = &achievements()[0];
+    assert!(!first_blood.is_unlocked(&player));
+    player.statistics.kills += 1;
+    assert!(first_blood.is_unlocked(&player));
+    assert_eq!(first_blood.unlock.to_string(), "Kills: 1");
+
+    assert!(!effects()[0].is_unlocked(&player));
+    player
+        .statistics
+        .hazards
+        .entry(String::from("pit trap"))
+        .or_default()
+        .avoided += 1;
+    assert!(effects()[0].is_unlocked(&player));
+
+    // an older save only remembers what was already earned, a new hero hears about it
+    let mut unlocked = None;
+    assert!(newly_unlocked(&player, &mut unlocked).is_empty());
+    assert!(unlocked.as_ref().unwrap().contains("first-blood"));
+    let mut unlocked = Some(BTreeSet::new());
+    assert_eq!(newly_unlocked(&player, &mut unlocked)[0].id, "first-blood");
+    assert!(newly_unlocked(&player, &mut unlocked).is_empty());
+
+    let pack = config::ContentPack {
+        achievements: vec![Achievement::new(
+            "first-blood",
+            "Blooded",
+            "Win a fight",
+            Unlock::Tally(Tally::Kills, 1),
+        )],
+        codex_titles: vec![Achievement::new(
+            "the-bold",
+            "the Bold",
+            "Reach level 2",
+            Unlock::Level(2),
+        )],
+        ..Default::default()
+    }
+    .shared();
+    config::with_content(&pack, || {
+        assert_eq!(achievements().len(), ACHIEVEMENTS.len());
+        assert_eq!(achievements().last().unwrap().name, "Blooded");
+        assert_eq!(titles().len(), slayer_titles().len() + 1);
+    });
+}
@@ -0,0 +1,104 @@
+//! Fights are played out blow by blow when a monster is picked, so how long they take depends on
+//! the hero's stats and equipment rather than just their level.
+//!
+//! Monsters have [`HEALTH_PER_LEVEL`] health for each level, and the hero swings at them every
+//! [`SWING`] until they go down. Experience still comes from the time spent fighting, so stronger
+//! heroes win fights faster but don't level any faster for it.
+
+use std::time::Duration;
+
+use crate::{config, mechanics::Player, Rand};
+
+pub const HEALTH_PER_LEVEL: usize = 20;
+pub const SWING: Duration = Duration::from_secs(1);
+/// Fights against monsters far above the hero are cut short, they get away eventually
+pub const MAX_BLOWS: usize = 30;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Blow {
+    Hit(usize),
+    Crit(usize),
+    Miss,
+    /// The monster ran away, see [`RuleSet::flee_chance`](crate::rules::RuleSet::flee_chance)
+    Fled,
+}
+
+/// What a hero brings to a fight
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Attack {
+    pub damage: usize,
+    /// Out of 100
+    pub crit_chance: usize,
+}
+
+impl Attack {
+    pub const MAX_CRIT_CHANCE: usize = 30;
+    pub const MISS_CHANCE: usize = 10;
+
+    pub fn of(player: &Player) -> Self {
+        let weapon = player.equipment.quality(config::Equipment::Weapon).max(0) as usize;
+        Self {
+            damage: 3 * player.level + player.stats[config::Stat::Strength] / 2 + weapon,
+            crit_chance: player.stats[config::Stat::Dexterity].min(Self::MAX_CRIT_CHANCE),
+        }
+    }
+
+    /// Swing at a monster of `level` until it goes down
+    pub fn resolve(&self, level: usize, rng: &Rand) -> Vec<Blow> {
+        let mut health = HEALTH_PER_LEVEL * level.max(1);
+        let mut blows = vec![];
+
+        while health > 0 && blows.len() < MAX_BLOWS {
+            // within a quarter either way
+            let damage = self.damage * 3 / 4 + rng.below(self.damage / 2 + 1);
+            let blow = if rng.odds(Self::MISS_CHANCE, 100) {
+                Blow::Miss
+            } else if rng.odds(self.crit_chance, 100) {
+                Blow::Crit(damage * 2)
+            } else {
+                Blow::Hit(damage)
+            };
+
+            health = health.saturating_sub(blow.damage());
+            blows.push(blow);
+        }
+        blows
+    }
+}
+
+impl Blow {
+    pub const fn damage(self) -> usize {
+        match self {
+            Self::Hit(damage) | Self::Crit(damage) => damage,
+            Self::Miss | Self::Fled => 0,
+        }
+    }
+}
+
+/// How long the `blows` take to land
+pub fn duration(blows: &[Blow]) -> Duration {
+    SWING * blows.len().max(1) as u32
+}
+
+#[test]
+fn stronger_heroes_fight_faster() {
+    let rng = Rand::seed(42);
+    let weak = Attack {
+        damage: 5,
+        crit_chance: 0,
+    };
+    let strong = Attack {
+        damage: 50,
+        crit_chance: 20,
+    };
+
+    let fights = |attack: Attack| {
+        (0..100)
+            .map(|_| duration(&attack.resolve(10, &rng)))
+            .sum::<Duration>()
+    };
+    assert!(fights(strong) < fights(weak));
+
+    let blows = weak.resolve(1000, &rng);
+    assert_eq!(blows.len(), MAX_BLOWS);
+}
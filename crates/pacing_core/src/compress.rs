@@ -0,0 +1,51 @@
+//! Optional compression for saves. Loading sniffs the data, so plain and compressed saves can
+//! both be read regardless of how the current save was written.
+
+use std::{borrow::Cow, io};
+
+/// The first bytes of a gzip stream
+pub const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+pub fn is_compressed(data: &[u8]) -> bool {
+    data.starts_with(&GZIP_MAGIC)
+}
+
+/// Gzip `data`
+#[cfg(feature = "compress")]
+pub fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+    use io::Write as _;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+/// Decompress `data` if it was compressed, otherwise return it as-is
+pub fn decompress(data: &[u8]) -> io::Result<Cow<'_, [u8]>> {
+    if !is_compressed(data) {
+        return Ok(Cow::Borrowed(data));
+    }
+
+    #[cfg(feature = "compress")]
+    {
+        use io::Read as _;
+        let mut out = Vec::with_capacity(data.len() * 4);
+        flate2::read::GzDecoder::new(data).read_to_end(&mut out)?;
+        Ok(Cow::Owned(out))
+    }
+
+    #[cfg(not(feature = "compress"))]
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "the save is compressed, but compression support isn't enabled",
+    ))
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn round_trip() {
+    let data = b"(name: \"Gorbash\", level: 12)".repeat(10);
+    let compressed = compress(&data).unwrap();
+    assert!(is_compressed(&compressed));
+    assert_eq!(&*decompress(&compressed).unwrap(), &data[..]);
+    assert_eq!(&*decompress(&data).unwrap(), &data[..]);
+}
@@ -1,10 +1,15 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
 macro_rules! define_enum {
-    ($ident:ident { $($field:ident => $repr:expr),* $(,)? }) => {
+    ($(#[$meta:meta])* $ident:ident { $($(#[$attr:meta])* $field:ident => $repr:expr),* $(,)? }) => {
         #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash, serde::Deserialize, serde::Serialize)]
+        $(#[$meta])*
         pub enum $ident {
-            $( $field ),*
+            $( $(#[$attr])* $field ),*
         }
 
         impl $ident {
@@ -51,6 +56,199 @@ define_enum! {
     }
 }
 
+define_enum! {
+    /// Where a piece of [`Equipment`] is worn
+    BodyPart {
+        Head   => "Head",
+        Torso  => "Torso",
+        Arms   => "Arms",
+        Legs   => "Legs",
+        Feet   => "Feet",
+        Weapon => "Weapon",
+        Shield => "Shield",
+    }
+}
+
+impl Equipment {
+    pub const ALL: [Self; 10] = [
+        Self::Weapon,
+        Self::Shield,
+        Self::Helm,
+        Self::Hauberk,
+        Self::Brassairts,
+        Self::Vambraces,
+        Self::Gauntlets,
+        Self::Guisses,
+        Self::Greaves,
+        Self::Sollerets,
+    ];
+
+    pub const fn body_part(self) -> BodyPart {
+        match self {
+            Self::Weapon => BodyPart::Weapon,
+            Self::Shield => BodyPart::Shield,
+            Self::Helm => BodyPart::Head,
+            Self::Hauberk => BodyPart::Torso,
+            Self::Brassairts | Self::Vambraces | Self::Gauntlets => BodyPart::Arms,
+            Self::Guisses | Self::Greaves => BodyPart::Legs,
+            Self::Sollerets => BodyPart::Feet,
+        }
+    }
+
+    /// Where the slot goes on a paper doll, as a `(column, row)` in a [`Self::DOLL_SIZE`] grid.
+    ///
+    /// ```text
+    ///             Helm
+    /// Brassairts  Hauberk    Vambraces
+    /// Weapon      Gauntlets  Shield
+    /// Guisses                Greaves
+    ///             Sollerets
+    /// ```
+    pub const fn doll_position(self) -> (usize, usize) {
+        match self {
+            Self::Helm => (1, 0),
+            Self::Brassairts => (0, 1),
+            Self::Hauberk => (1, 1),
+            Self::Vambraces => (2, 1),
+            Self::Weapon => (0, 2),
+            Self::Gauntlets => (1, 2),
+            Self::Shield => (2, 2),
+            Self::Guisses => (0, 3),
+            Self::Greaves => (2, 3),
+            Self::Sollerets => (1, 4),
+        }
+    }
+
+    /// The `(columns, rows)` of the paper doll
+    pub const DOLL_SIZE: (usize, usize) = (3, 5);
+}
+
+define_enum! {
+    NameStyle {
+        Phonemes  => "Phonemes",
+        Syllables => "Syllables",
+        Markov    => "Markov",
+    }
+}
+
+define_enum! {
+    #[derive(Default)]
+    GlyphStyle {
+        #[default]
+        Off   => "Off",
+        Ascii => "ASCII",
+        Emoji => "Emoji",
+    }
+}
+
+define_enum! {
+    /// The panels and bars a new player gets a hint for
+    Hint {
+        CharacterSheet => "Character Sheet",
+        SpellBook      => "Spell Book",
+        Equipment      => "Equipment",
+        Inventory      => "Inventory",
+        Plot           => "Plot Development",
+        Quests         => "Quests",
+        Task           => "Task",
+    }
+}
+
+define_enum! {
+    /// What the market trades in, each at its own [`Market`](crate::mechanics::Market) rate
+    Goods {
+        Trophies  => "trophies",
+        Relics    => "relics",
+        Materials => "materials",
+        Equipment => "equipment",
+    }
+}
+
+impl Goods {
+    pub const ALL: [Self; 4] = [
+        Self::Trophies,
+        Self::Relics,
+        Self::Materials,
+        Self::Equipment,
+    ];
+
+    /// What kind of goods an item in the inventory is sold as
+    pub fn of(item: &str) -> Self {
+        if item.contains(" of ") {
+            return Self::Relics;
+        }
+        let gathered = content()
+            .gatherings
+            .iter()
+            .any(|gathering| gathering.yields.iter().any(|material| material == item));
+        if gathered {
+            Self::Materials
+        } else {
+            Self::Trophies
+        }
+    }
+}
+
+define_enum! {
+    #[derive(Default)]
+    Language {
+        #[default]
+        English => "English",
+        German  => "Deutsch",
+        French  => "Français",
+    }
+}
+
+impl Language {
+    pub const ALL: [Self; 3] = [Self::English, Self::German, Self::French];
+}
+
+define_enum! {
+    #[derive(Default)]
+    Rating {
+        Family  => "Family friendly",
+        #[default]
+        Classic => "Classic",
+    }
+}
+
+impl Rating {
+    pub const ALL: [Self; 2] = [Self::Family, Self::Classic];
+
+    /// Whether content rated `rating` can be generated under this rating
+    pub fn allows(self, rating: Self) -> bool {
+        rating <= self
+    }
+}
+
+/// Content that is only generated with a certain [`Rating`]
+pub trait Rated {
+    fn rating(&self) -> Rating;
+}
+
+impl Rated for &str {
+    fn rating(&self) -> Rating {
+        if CLASSIC_ONLY.contains(self) {
+            Rating::Classic
+        } else {
+            Rating::Family
+        }
+    }
+}
+
+/// Entries from the plain lists below that are only generated with the [`Rating::Classic`] rating
+pub const CLASSIC_ONLY: &[&str] = &[
+    "Summon a Bitch",
+    "Tumor (Malignant)",
+    "Corset",
+    "Pleasure",
+    "Prurience",
+    "Domination",
+    "Submission",
+    "Incarceration",
+    "teratoma",
+];
+
 pub const PRIME_STATS: [Stat; 6] = [
     Stat::Strength,
     Stat::Condition,
@@ -121,6 +319,7 @@ pub const SPELLS: &[&str] = &[
     "Infinite Confusion",
 ];
 
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct Modifier {
     pub name: Cow<'static, str>,
     pub quality: i32,
@@ -191,7 +390,7 @@ pub const DEFENSE_QUIRK: &[Modifier] = &[
     Modifier::new("Corroded", -3),
 ];
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct EquipmentPreset {
     pub name: Cow<'static, str>,
     pub quality: i32,
@@ -540,12 +739,12 @@ pub const CLASSES: &[Class] = &[
 
 pub const MONSTERS: &[Monster] = &[
     Monster::new("Anhkheg", 6, Some("chitin")),
-    Monster::new("Ant", 0, Some("antenna")),
-    Monster::new("Ape", 4, Some("ass")),
-    Monster::new("Baluchitherium", 14, Some("ear")),
+    Monster::new("Ant", 0, Some("antenna")).of(Family::Beasts),
+    Monster::new("Ape", 4, Some("ass")).of(Family::Beasts),
+    Monster::new("Baluchitherium", 14, Some("ear")).of(Family::Beasts),
     Monster::new("Beholder", 10, Some("eyestalk")),
     Monster::new("Black Pudding", 10, Some("saliva")),
-    Monster::new("Blink Dog", 4, Some("eyelid")),
+    Monster::new("Blink Dog", 4, Some("eyelid")).of(Family::Beasts),
     Monster::new("Cub Scout", 1, Some("neckerchief")),
     Monster::new("Girl Scout", 2, Some("cookie")),
     Monster::new("Boy Scout", 3, Some("merit badge")),
@@ -553,94 +752,94 @@ pub const MONSTERS: &[Monster] = &[
     Monster::new("Bugbear", 3, Some("skin")),
     Monster::new("Bugboar", 3, Some("tusk")),
     Monster::new("Boogie", 3, Some("slime")),
-    Monster::new("Camel", 2, Some("hump")),
+    Monster::new("Camel", 2, Some("hump")).of(Family::Beasts),
     Monster::new("Carrion Crawler", 3, Some("egg")),
     Monster::new("Catoblepas", 6, Some("neck")),
     Monster::new("Centaur", 4, Some("rib")),
-    Monster::new("Centipede", 0, Some("leg")),
+    Monster::new("Centipede", 0, Some("leg")).of(Family::Beasts),
     Monster::new("Cockatrice", 5, Some("wattle")),
     Monster::new("Couatl", 9, Some("wing")),
-    Monster::new("Crayfish", 0, Some("antenna")),
-    Monster::new("Demogorgon", 53, Some("tentacle")),
-    Monster::new("Jubilex", 17, Some("gel")),
-    Monster::new("Manes", 1, Some("tooth")),
-    Monster::new("Orcus", 27, Some("wand")),
-    Monster::new("Succubus", 6, Some("bra")),
-    Monster::new("Vrock", 8, Some("neck")),
-    Monster::new("Hezrou", 9, Some("leg")),
-    Monster::new("Glabrezu", 10, Some("collar")),
-    Monster::new("Nalfeshnee", 11, Some("tusk")),
-    Monster::new("Marilith", 7, Some("arm")),
-    Monster::new("Balor", 8, Some("whip")),
-    Monster::new("Yeenoghu", 25, Some("flail")),
-    Monster::new("Asmodeus", 52, Some("leathers")),
-    Monster::new("Baalzebul", 43, Some("pants")),
-    Monster::new("Barbed Devil", 8, Some("flame")),
-    Monster::new("Bone Devil", 9, Some("hook")),
-    Monster::new("Dispater", 30, Some("matches")),
-    Monster::new("Erinyes", 6, Some("thong")),
-    Monster::new("Geryon", 30, Some("cornucopia")),
-    Monster::new("Malebranche", 5, Some("fork")),
-    Monster::new("Ice Devil", 11, Some("snow")),
-    Monster::new("Lemure", 3, Some("blob")),
-    Monster::new("Pit Fiend", 13, Some("seed")),
-    Monster::new("Ankylosaurus", 9, Some("tail")),
-    Monster::new("Brontosaurus", 30, Some("brain")),
-    Monster::new("Diplodocus", 24, Some("fin")),
-    Monster::new("Elasmosaurus", 15, Some("neck")),
-    Monster::new("Gorgosaurus", 13, Some("arm")),
-    Monster::new("Iguanadon", 6, Some("thumb")),
-    Monster::new("Megalosaurus", 12, Some("jaw")),
-    Monster::new("Monoclonius", 8, Some("horn")),
-    Monster::new("Pentasaurus", 12, Some("head")),
-    Monster::new("Stegosaurus", 18, Some("plate")),
-    Monster::new("Triceratops", 16, Some("horn")),
-    Monster::new("Tyrannosaurus Rex", 18, Some("forearm")),
+    Monster::new("Crayfish", 0, Some("antenna")).of(Family::Beasts),
+    Monster::new("Demogorgon", 53, Some("tentacle")).of(Family::Fiends),
+    Monster::new("Jubilex", 17, Some("gel")).of(Family::Fiends),
+    Monster::new("Manes", 1, Some("tooth")).of(Family::Fiends),
+    Monster::new("Orcus", 27, Some("wand")).of(Family::Fiends),
+    Monster::classic("Succubus", 6, Some("bra")).of(Family::Fiends),
+    Monster::new("Vrock", 8, Some("neck")).of(Family::Fiends),
+    Monster::new("Hezrou", 9, Some("leg")).of(Family::Fiends),
+    Monster::new("Glabrezu", 10, Some("collar")).of(Family::Fiends),
+    Monster::new("Nalfeshnee", 11, Some("tusk")).of(Family::Fiends),
+    Monster::new("Marilith", 7, Some("arm")).of(Family::Fiends),
+    Monster::new("Balor", 8, Some("whip")).of(Family::Fiends),
+    Monster::new("Yeenoghu", 25, Some("flail")).of(Family::Fiends),
+    Monster::new("Asmodeus", 52, Some("leathers")).of(Family::Fiends),
+    Monster::new("Baalzebul", 43, Some("pants")).of(Family::Fiends),
+    Monster::new("Barbed Devil", 8, Some("flame")).of(Family::Fiends),
+    Monster::new("Bone Devil", 9, Some("hook")).of(Family::Fiends),
+    Monster::new("Dispater", 30, Some("matches")).of(Family::Fiends),
+    Monster::classic("Erinyes", 6, Some("thong")).of(Family::Fiends),
+    Monster::new("Geryon", 30, Some("cornucopia")).of(Family::Fiends),
+    Monster::new("Malebranche", 5, Some("fork")).of(Family::Fiends),
+    Monster::new("Ice Devil", 11, Some("snow")).of(Family::Fiends),
+    Monster::new("Lemure", 3, Some("blob")).of(Family::Fiends),
+    Monster::new("Pit Fiend", 13, Some("seed")).of(Family::Fiends),
+    Monster::new("Ankylosaurus", 9, Some("tail")).of(Family::Beasts),
+    Monster::new("Brontosaurus", 30, Some("brain")).of(Family::Beasts),
+    Monster::new("Diplodocus", 24, Some("fin")).of(Family::Beasts),
+    Monster::new("Elasmosaurus", 15, Some("neck")).of(Family::Beasts),
+    Monster::new("Gorgosaurus", 13, Some("arm")).of(Family::Beasts),
+    Monster::new("Iguanadon", 6, Some("thumb")).of(Family::Beasts),
+    Monster::new("Megalosaurus", 12, Some("jaw")).of(Family::Beasts),
+    Monster::new("Monoclonius", 8, Some("horn")).of(Family::Beasts),
+    Monster::new("Pentasaurus", 12, Some("head")).of(Family::Beasts),
+    Monster::new("Stegosaurus", 18, Some("plate")).of(Family::Beasts),
+    Monster::new("Triceratops", 16, Some("horn")).of(Family::Beasts),
+    Monster::new("Tyrannosaurus Rex", 18, Some("forearm")).of(Family::Beasts),
     Monster::new("Djinn", 7, Some("lamp")),
     Monster::new("Doppelganger", 4, Some("face")),
-    Monster::new("Black Dragon", 7, None),
-    Monster::new("Plaid Dragon", 7, Some("sporrin")),
-    Monster::new("Blue Dragon", 9, None),
-    Monster::new("Beige Dragon", 9, None),
-    Monster::new("Brass Dragon", 7, Some("pole")),
-    Monster::new("Tin Dragon", 8, None),
-    Monster::new("Bronze Dragon", 9, Some("medal")),
-    Monster::new("Chromatic Dragon", 16, Some("scale")),
-    Monster::new("Copper Dragon", 8, Some("loafer")),
-    Monster::new("Gold Dragon", 8, Some("filling")),
-    Monster::new("Green Dragon", 8, None),
-    Monster::new("Platinum Dragon", 21, None),
-    Monster::new("Red Dragon", 10, Some("cocktail")),
-    Monster::new("Silver Dragon", 10, None),
-    Monster::new("White Dragon", 6, Some("tooth")),
-    Monster::new("Dragon Turtle", 13, Some("shell")),
+    Monster::new("Black Dragon", 7, None).of(Family::Dragons),
+    Monster::new("Plaid Dragon", 7, Some("sporrin")).of(Family::Dragons),
+    Monster::new("Blue Dragon", 9, None).of(Family::Dragons),
+    Monster::new("Beige Dragon", 9, None).of(Family::Dragons),
+    Monster::new("Brass Dragon", 7, Some("pole")).of(Family::Dragons),
+    Monster::new("Tin Dragon", 8, None).of(Family::Dragons),
+    Monster::new("Bronze Dragon", 9, Some("medal")).of(Family::Dragons),
+    Monster::new("Chromatic Dragon", 16, Some("scale")).of(Family::Dragons),
+    Monster::new("Copper Dragon", 8, Some("loafer")).of(Family::Dragons),
+    Monster::new("Gold Dragon", 8, Some("filling")).of(Family::Dragons),
+    Monster::new("Green Dragon", 8, None).of(Family::Dragons),
+    Monster::new("Platinum Dragon", 21, None).of(Family::Dragons),
+    Monster::new("Red Dragon", 10, Some("cocktail")).of(Family::Dragons),
+    Monster::new("Silver Dragon", 10, None).of(Family::Dragons),
+    Monster::new("White Dragon", 6, Some("tooth")).of(Family::Dragons),
+    Monster::new("Dragon Turtle", 13, Some("shell")).of(Family::Dragons),
     Monster::new("Dryad", 2, Some("acorn")),
     Monster::new("Dwarf", 1, Some("drawers")),
-    Monster::new("Eel", 2, Some("sashimi")),
+    Monster::new("Eel", 2, Some("sashimi")).of(Family::Beasts),
     Monster::new("Efreet", 10, Some("cinder")),
-    Monster::new("Sand Elemental", 8, Some("glass")),
-    Monster::new("Bacon Elemental", 10, Some("bit")),
-    Monster::new("Porn Elemental", 12, Some("lube")),
-    Monster::new("Cheese Elemental", 14, Some("curd")),
-    Monster::new("Hair Elemental", 16, Some("follicle")),
+    Monster::new("Sand Elemental", 8, Some("glass")).of(Family::Constructs),
+    Monster::new("Bacon Elemental", 10, Some("bit")).of(Family::Constructs),
+    Monster::classic("Porn Elemental", 12, Some("lube")).of(Family::Constructs),
+    Monster::new("Cheese Elemental", 14, Some("curd")).of(Family::Constructs),
+    Monster::new("Hair Elemental", 16, Some("follicle")).of(Family::Constructs),
     Monster::new("Swamp Elf", 1, Some("lilypad")),
     Monster::new("Brown Elf", 1, Some("tusk")),
     Monster::new("Sea Elf", 1, Some("jerkin")),
     Monster::new("Ettin", 10, Some("fur")),
-    Monster::new("Frog", 0, Some("leg")),
+    Monster::new("Frog", 0, Some("leg")).of(Family::Beasts),
     Monster::new("Violet Fungi", 3, Some("spore")),
-    Monster::new("Gargoyle", 4, Some("gravel")),
+    Monster::new("Gargoyle", 4, Some("gravel")).of(Family::Constructs),
     Monster::new("Gelatinous Cube", 4, Some("jam")),
-    Monster::new("Ghast", 4, Some("vomit")),
-    Monster::new("Ghost", 10, None),
-    Monster::new("Ghoul", 2, Some("muscle")),
+    Monster::new("Ghast", 4, Some("vomit")).of(Family::Undead),
+    Monster::new("Ghost", 10, None).of(Family::Undead),
+    Monster::new("Ghoul", 2, Some("muscle")).of(Family::Undead),
     Monster::new("Humidity Giant", 12, Some("drops")),
     Monster::new("Beef Giant", 11, Some("steak")),
     Monster::new("Quartz Giant", 10, Some("crystal")),
     Monster::new("Porcelain Giant", 9, Some("fixture")),
     Monster::new("Rice Giant", 8, Some("grain")),
     Monster::new("Cloud Giant", 12, Some("condensation")),
-    Monster::new("Fire Giant", 11, Some("cigarettes")),
+    Monster::classic("Fire Giant", 11, Some("cigarettes")),
     Monster::new("Frost Giant", 10, Some("snowman")),
     Monster::new("Hill Giant", 8, Some("corpse")),
     Monster::new("Stone Giant", 9, Some("hatchling")),
@@ -651,35 +850,35 @@ pub const MONSTERS: &[Monster] = &[
     Monster::new("Goblin", 1, Some("ear")),
     Monster::new("Grid Bug", 1, Some("carapace")),
     Monster::new("Jellyrock", 9, Some("seedling")),
-    Monster::new("Beer Golem", 15, Some("foam")),
-    Monster::new("Oxygen Golem", 17, Some("platelet")),
-    Monster::new("Cardboard Golem", 14, Some("recycling")),
-    Monster::new("Rubber Golem", 16, Some("ball")),
-    Monster::new("Leather Golem", 15, Some("fob")),
-    Monster::new("Gorgon", 8, Some("testicle")),
+    Monster::new("Beer Golem", 15, Some("foam")).of(Family::Constructs),
+    Monster::new("Oxygen Golem", 17, Some("platelet")).of(Family::Constructs),
+    Monster::new("Cardboard Golem", 14, Some("recycling")).of(Family::Constructs),
+    Monster::new("Rubber Golem", 16, Some("ball")).of(Family::Constructs),
+    Monster::new("Leather Golem", 15, Some("fob")).of(Family::Constructs),
+    Monster::classic("Gorgon", 8, Some("testicle")),
     Monster::new("Gray Ooze", 3, Some("gravy")),
     Monster::new("Green Slime", 2, Some("sample")),
-    Monster::new("Griffon", 7, Some("nest")),
-    Monster::new("Banshee", 7, Some("larynx")),
+    Monster::new("Griffon", 7, Some("nest")).of(Family::Beasts),
+    Monster::new("Banshee", 7, Some("larynx")).of(Family::Undead),
     Monster::new("Harpy", 3, Some("mascara")),
-    Monster::new("Hell Hound", 5, Some("tongue")),
+    Monster::new("Hell Hound", 5, Some("tongue")).of(Family::Fiends),
     Monster::new("Hippocampus", 4, Some("mane")),
-    Monster::new("Hippogriff", 3, Some("egg")),
+    Monster::new("Hippogriff", 3, Some("egg")).of(Family::Beasts),
     Monster::new("Hobgoblin", 1, Some("patella")),
-    Monster::new("Homunculus", 2, Some("fluid")),
-    Monster::new("Hydra", 8, Some("gyrum")),
-    Monster::new("Imp", 2, Some("tail")),
+    Monster::new("Homunculus", 2, Some("fluid")).of(Family::Constructs),
+    Monster::new("Hydra", 8, Some("gyrum")).of(Family::Dragons),
+    Monster::new("Imp", 2, Some("tail")).of(Family::Fiends),
     Monster::new("Invisible Stalker", 8, None),
-    Monster::new("Iron Peasant", 3, Some("chaff")),
+    Monster::new("Iron Peasant", 3, Some("chaff")).of(Family::Constructs),
     Monster::new("Jumpskin", 3, Some("shin")),
-    Monster::new("Kobold", 1, Some("penis")),
+    Monster::classic("Kobold", 1, Some("penis")),
     Monster::new("Leprechaun", 1, Some("wallet")),
     Monster::new("Leucrotta", 6, Some("hoof")),
-    Monster::new("Lich", 11, Some("crown")),
+    Monster::new("Lich", 11, Some("crown")).of(Family::Undead),
     Monster::new("Lizard Man", 2, Some("tail")),
     Monster::new("Lurker", 10, Some("sac")),
-    Monster::new("Manticore", 6, Some("spike")),
-    Monster::new("Mastodon", 12, Some("tusk")),
+    Monster::new("Manticore", 6, Some("spike")).of(Family::Beasts),
+    Monster::new("Mastodon", 12, Some("tusk")).of(Family::Beasts),
     Monster::new("Medusa", 6, Some("eye")),
     Monster::new("Multicell", 2, Some("dendrite")),
     Monster::new("Pirate", 1, Some("booty")),
@@ -693,47 +892,47 @@ pub const MONSTERS: &[Monster] = &[
     Monster::new("Minotaur", 6, Some("map")),
     Monster::new("Yellow Mold", 1, Some("spore")),
     Monster::new("Morkoth", 7, Some("teeth")),
-    Monster::new("Mummy", 6, Some("gauze")),
+    Monster::new("Mummy", 6, Some("gauze")).of(Family::Undead),
     Monster::new("Naga", 9, Some("rattle")),
     Monster::new("Nebbish", 1, Some("belly")),
     Monster::new("Neo-Otyugh", 11, Some("organ ")),
     Monster::new("Nixie", 1, Some("webbing")),
     Monster::new("Nymph", 3, Some("hanky")),
     Monster::new("Ochre Jelly", 6, Some("nucleus")),
-    Monster::new("Octopus", 2, Some("beak")),
+    Monster::new("Octopus", 2, Some("beak")).of(Family::Beasts),
     Monster::new("Ogre", 4, Some("talon")),
     Monster::new("Ogre Mage", 5, Some("apparel")),
     Monster::new("Orc", 1, Some("snout")),
     Monster::new("Otyugh", 7, Some("organ")),
-    Monster::new("Owlbear", 5, Some("feather")),
+    Monster::new("Owlbear", 5, Some("feather")).of(Family::Beasts),
     Monster::new("Pegasus", 4, Some("aileron")),
     Monster::new("Peryton", 4, Some("antler")),
     Monster::new("Piercer", 3, Some("tip")),
     Monster::new("Pixie", 1, Some("dust")),
     Monster::new("Man-o-war", 3, Some("tentacle")),
     Monster::new("Purple Worm", 15, Some("dung")),
-    Monster::new("Quasit", 3, Some("tail")),
-    Monster::new("Rakshasa", 7, Some("pajamas")),
-    Monster::new("Rat", 0, Some("tail")),
+    Monster::new("Quasit", 3, Some("tail")).of(Family::Fiends),
+    Monster::new("Rakshasa", 7, Some("pajamas")).of(Family::Fiends),
+    Monster::new("Rat", 0, Some("tail")).of(Family::Beasts),
     Monster::new("Remorhaz", 11, Some("protrusion")),
-    Monster::new("Roc", 18, Some("wing")),
+    Monster::new("Roc", 18, Some("wing")).of(Family::Beasts),
     Monster::new("Roper", 11, Some("twine")),
     Monster::new("Rot Grub", 1, Some("eggsac")),
     Monster::new("Rust Monster", 5, Some("shavings")),
     Monster::new("Satyr", 5, Some("hoof")),
     Monster::new("Sea Hag", 3, Some("wart")),
     Monster::new("Silkie", 3, Some("fur")),
-    Monster::new("Shadow", 3, Some("silhouette")),
+    Monster::new("Shadow", 3, Some("silhouette")).of(Family::Undead),
     Monster::new("Shambling Mound", 10, Some("mulch")),
     Monster::new("Shedu", 9, Some("hoof")),
     Monster::new("Shrieker", 3, Some("stalk")),
-    Monster::new("Skeleton", 1, Some("clavicle")),
-    Monster::new("Spectre", 7, Some("vestige")),
+    Monster::new("Skeleton", 1, Some("clavicle")).of(Family::Undead),
+    Monster::new("Spectre", 7, Some("vestige")).of(Family::Undead),
     Monster::new("Sphinx", 10, Some("paw")),
-    Monster::new("Spider", 0, Some("web")),
+    Monster::new("Spider", 0, Some("web")).of(Family::Beasts),
     Monster::new("Sprite", 1, Some("can")),
-    Monster::new("Stirge", 1, Some("proboscis")),
-    Monster::new("Stun Bear", 5, Some("tooth")),
+    Monster::new("Stirge", 1, Some("proboscis")).of(Family::Beasts),
+    Monster::new("Stun Bear", 5, Some("tooth")).of(Family::Beasts),
     Monster::new("Stun Worm", 2, Some("trode")),
     Monster::new("Su-monster", 5, Some("tail")),
     Monster::new("Sylph", 3, Some("thigh")),
@@ -745,30 +944,30 @@ pub const MONSTERS: &[Monster] = &[
     Monster::new("Troll", 6, Some("hide")),
     Monster::new("Umber Hulk", 8, Some("claw")),
     Monster::new("Unicorn", 4, Some("blood")),
-    Monster::new("Vampire", 8, Some("pancreas")),
-    Monster::new("Wight", 4, Some("lung")),
+    Monster::new("Vampire", 8, Some("pancreas")).of(Family::Undead),
+    Monster::new("Wight", 4, Some("lung")).of(Family::Undead),
     Monster::new("Will-o'-the-Wisp", 9, Some("wisp")),
-    Monster::new("Wraith", 5, Some("finger")),
-    Monster::new("Wyvern", 7, Some("wing")),
+    Monster::new("Wraith", 5, Some("finger")).of(Family::Undead),
+    Monster::new("Wyvern", 7, Some("wing")).of(Family::Dragons),
     Monster::new("Xorn", 7, Some("jaw")),
     Monster::new("Yeti", 4, Some("fur")),
-    Monster::new("Zombie", 2, Some("forehead")),
-    Monster::new("Wasp", 0, Some("stinger")),
-    Monster::new("Rat", 1, Some("tail")),
-    Monster::new("Bunny", 0, Some("ear")),
-    Monster::new("Moth", 0, Some("dust")),
-    Monster::new("Beagle", 0, Some("collar")),
-    Monster::new("Midge", 0, Some("corpse")),
-    Monster::new("Ostrich", 1, Some("beak")),
-    Monster::new("Billy Goat", 1, Some("beard")),
-    Monster::new("Bat", 1, Some("wing")),
-    Monster::new("Koala", 2, Some("heart")),
-    Monster::new("Wolf", 2, Some("paw")),
-    Monster::new("Whippet", 2, Some("collar")),
+    Monster::new("Zombie", 2, Some("forehead")).of(Family::Undead),
+    Monster::new("Wasp", 0, Some("stinger")).of(Family::Beasts),
+    Monster::new("Rat", 1, Some("tail")).of(Family::Beasts),
+    Monster::new("Bunny", 0, Some("ear")).of(Family::Beasts),
+    Monster::new("Moth", 0, Some("dust")).of(Family::Beasts),
+    Monster::new("Beagle", 0, Some("collar")).of(Family::Beasts),
+    Monster::new("Midge", 0, Some("corpse")).of(Family::Beasts),
+    Monster::new("Ostrich", 1, Some("beak")).of(Family::Beasts),
+    Monster::new("Billy Goat", 1, Some("beard")).of(Family::Beasts),
+    Monster::new("Bat", 1, Some("wing")).of(Family::Beasts),
+    Monster::new("Koala", 2, Some("heart")).of(Family::Beasts),
+    Monster::new("Wolf", 2, Some("paw")).of(Family::Beasts),
+    Monster::new("Whippet", 2, Some("collar")).of(Family::Beasts),
     Monster::new("Uruk", 2, Some("boot")),
     Monster::new("Poroid", 4, Some("node")),
     Monster::new("Moakum", 8, Some("frenum")),
-    Monster::new("Fly", 0, None),
+    Monster::new("Fly", 0, None).of(Family::Beasts),
     Monster::new("Hogbird", 3, Some("curl")),
     Monster::new("Wolog", 4, Some("lemma")),
 ];
@@ -778,10 +977,28 @@ pub struct Monster {
     pub name: Cow<'static, str>,
     pub level: usize,
     pub item: Option<Cow<'static, str>>,
+    #[serde(default)]
+    pub rating: Rating,
+    #[serde(default)]
+    pub family: Option<Family>,
 }
 
 impl Monster {
     pub const fn new(name: &'static str, level: usize, item: Option<&'static str>) -> Self {
+        Self::with_rating(name, level, item, Rating::Family)
+    }
+
+    /// A monster that only shows up with the [`Rating::Classic`] rating
+    pub const fn classic(name: &'static str, level: usize, item: Option<&'static str>) -> Self {
+        Self::with_rating(name, level, item, Rating::Classic)
+    }
+
+    const fn with_rating(
+        name: &'static str,
+        level: usize,
+        item: Option<&'static str>,
+        rating: Rating,
+    ) -> Self {
         Self {
             name: Cow::Borrowed(name),
             level,
@@ -789,10 +1006,769 @@ impl Monster {
                 Some(item) => Some(Cow::Borrowed(item)),
                 None => None,
             },
+            rating,
+            family: None,
+        }
+    }
+
+    /// Tag the monster as part of a `family`
+    pub const fn of(mut self, family: Family) -> Self {
+        self.family = Some(family);
+        self
+    }
+}
+
+define_enum! {
+    /// Kinds of monster a hero can become a slayer of, see
+    /// [`Bestiary::slayer_rank`](crate::mechanics::Bestiary::slayer_rank)
+    Family {
+        Undead     => "Undead",
+        Beasts     => "Beasts",
+        Constructs => "Constructs",
+        Fiends     => "Fiends",
+        Dragons    => "Dragons",
+    }
+}
+
+impl Family {
+    pub const ALL: [Self; 5] = [
+        Self::Undead,
+        Self::Beasts,
+        Self::Constructs,
+        Self::Fiends,
+        Self::Dragons,
+    ];
+
+    /// The title for slaying enough of the family to reach `rank`, which starts at 1
+    pub fn slayer_title(self, rank: usize) -> Option<String> {
+        const TITLES: [&str; 3] = ["Slayer", "Scourge", "Bane"];
+        let title = TITLES.get(rank.checked_sub(1)?)?;
+        Some(format!("{title} of {self}"))
+    }
+}
+
+define_enum! {
+    /// The lay of a [`Region`](crate::world::Region), or the theme of an act, which the quests
+    /// given after visiting it or during the act lean toward
+    Climate {
+        Frozen   => "Frozen",
+        Scorched => "Scorched",
+        Sodden   => "Sodden",
+        Haunted  => "Haunted",
+        Verdant  => "Verdant",
+        Ruined   => "Ruined",
+    }
+}
+
+impl Climate {
+    pub const ALL: [Self; 6] = [
+        Self::Frozen,
+        Self::Scorched,
+        Self::Sodden,
+        Self::Haunted,
+        Self::Verdant,
+        Self::Ruined,
+    ];
+}
+
+impl Rated for Cow<'_, str> {
+    fn rating(&self) -> Rating {
+        (&**self).rating()
+    }
+}
+
+impl Rated for Monster {
+    fn rating(&self) -> Rating {
+        self.rating
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Product {
+    Modifier(Equipment, Modifier),
+    Consumable(Cow<'static, str>),
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Recipe {
+    pub name: Cow<'static, str>,
+    pub materials: Cow<'static, [Cow<'static, str>]>,
+    pub quantity: usize,
+    pub product: Product,
+}
+
+impl Recipe {
+    pub const fn new(
+        name: &'static str,
+        materials: &'static [Cow<'static, str>],
+        quantity: usize,
+        product: Product,
+    ) -> Self {
+        Self {
+            name: Cow::Borrowed(name),
+            materials: Cow::Borrowed(materials),
+            quantity,
+            product,
+        }
+    }
+
+    pub fn accepts(&self, item: &str) -> bool {
+        self.materials.iter().any(|material| {
+            item.strip_suffix(&**material)
+                .is_some_and(|rest| rest.ends_with(' '))
+        })
+    }
+}
+
+macro_rules! materials {
+    ($($material:expr),* $(,)?) => {
+        &[$(Cow::Borrowed($material)),*]
+    };
+}
+
+pub const RECIPES: &[Recipe] = &[
+    Recipe::new(
+        "set of bone studs",
+        materials!["tusk", "rib", "eyestalk", "curl", "neck"],
+        4,
+        Product::Modifier(Equipment::Weapon, Modifier::new("Bone-Studded", 1)),
+    ),
+    Recipe::new(
+        "hide wrapping",
+        materials!["skin", "hump", "ass", "chitin"],
+        4,
+        Product::Modifier(Equipment::Hauberk, Modifier::new("Hide-Wrapped", 1)),
+    ),
+    Recipe::new(
+        "jar of bug paste",
+        materials!["antenna", "leg", "egg", "lemma"],
+        3,
+        Product::Consumable(Cow::Borrowed("jar of bug paste")),
+    ),
+    Recipe::new(
+        "pot of slime salve",
+        materials!["slime", "saliva", "frenum"],
+        3,
+        Product::Consumable(Cow::Borrowed("pot of slime salve")),
+    ),
+    Recipe::new(
+        "scouting kit",
+        materials!["neckerchief", "cookie", "merit badge", "eyelid", "ear"],
+        3,
+        Product::Consumable(Cow::Borrowed("scouting kit")),
+    ),
+    Recipe::new(
+        "herbal poultice",
+        materials!["leaf", "root", "sprig"],
+        3,
+        Product::Consumable(Cow::Borrowed("herbal poultice")),
+    ),
+];
+
+/// A quiet way to pass the time between acts, turning up materials for [`RECIPES`]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Gathering {
+    pub name: Cow<'static, str>,
+    pub yields: Cow<'static, [Cow<'static, str>]>,
+    /// What the hero is up to while gathering, one line per task
+    pub pastimes: Cow<'static, [Cow<'static, str>]>,
+}
+
+impl Gathering {
+    pub const fn new(
+        name: &'static str,
+        yields: &'static [Cow<'static, str>],
+        pastimes: &'static [Cow<'static, str>],
+    ) -> Self {
+        Self {
+            name: Cow::Borrowed(name),
+            yields: Cow::Borrowed(yields),
+            pastimes: Cow::Borrowed(pastimes),
+        }
+    }
+}
+
+pub const GATHERINGS: &[Gathering] = &[
+    Gathering::new(
+        "Fishing",
+        materials!["trout skin", "eel skin", "carp rib", "minnow egg"],
+        materials![
+            "Casting a line into a quiet stream",
+            "Watching a bobber drift lazily downstream",
+            "Dozing on a sunny riverbank",
+            "Trading fish stories with a heron",
+        ],
+    ),
+    Gathering::new(
+        "Herb picking",
+        materials!["sage leaf", "mandrake root", "mint sprig", "thyme sprig"],
+        materials![
+            "Wandering along an overgrown hedgerow",
+            "Humming while sorting herbs into bundles",
+            "Stopping to smell the wildflowers",
+            "Kneeling in the dewy grass of a meadow",
+        ],
+    ),
+    Gathering::new(
+        "Beachcombing",
+        materials!["crab leg", "gull egg", "kelp leaf"],
+        materials![
+            "Strolling along the shore at low tide",
+            "Skipping stones across the shallows",
+            "Listening to the waves roll in",
+        ],
+    ),
+];
+
+/// Something a monster can drop, picked by weight against the rest of its [`LootTable`]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Drop {
+    pub item: Cow<'static, str>,
+    pub weight: usize,
+    /// Only ever dropped once for each hero
+    #[serde(default)]
+    pub unique: bool,
+}
+
+/// What a kind of monster drops. Monsters without one drop a part of themselves, like "ant
+/// antenna"
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct LootTable {
+    pub monster: Cow<'static, str>,
+    pub drops: Cow<'static, [Drop]>,
+}
+
+impl LootTable {
+    pub const fn new(monster: &'static str, drops: &'static [Drop]) -> Self {
+        Self {
+            monster: Cow::Borrowed(monster),
+            drops: Cow::Borrowed(drops),
+        }
+    }
+
+    /// Pick a drop by weight, skipping uniques the hero has already `looted`
+    pub fn roll(&self, looted: impl Fn(&str) -> bool, rng: &crate::Rand) -> Option<&Drop> {
+        let drops = || {
+            self.drops
+                .iter()
+                .filter(|drop| !(drop.unique && looted(&drop.item)))
+        };
+        let total = drops().map(|drop| drop.weight).sum::<usize>();
+        if total == 0 {
+            return None;
+        }
+
+        let mut pick = rng.below(total);
+        drops().find(|drop| match pick.checked_sub(drop.weight) {
+            Some(rest) => {
+                pick = rest;
+                false
+            }
+            None => true,
+        })
+    }
+}
+
+macro_rules! drops {
+    (@unique) => { false };
+    (@unique unique) => { true };
+    ($(($item:expr, $weight:expr $(, $unique:ident)?)),* $(,)?) => {
+        &[$(Drop {
+            item: Cow::Borrowed($item),
+            weight: $weight,
+            unique: drops!(@unique $($unique)?),
+        }),*]
+    };
+}
+
+pub const LOOT_TABLES: &[LootTable] = &[
+    LootTable::new(
+        "Cub Scout",
+        drops![
+            ("cub scout neckerchief", 70),
+            ("half-eaten s'more", 25),
+            ("Wolf Badge of Valor", 5, unique),
+        ],
+    ),
+    LootTable::new(
+        "Bugbear",
+        drops![
+            ("bugbear skin", 60),
+            ("bugbear claw", 35),
+            ("Honeypot of the Hive Queen", 5, unique),
+        ],
+    ),
+    LootTable::new(
+        "Cockatrice",
+        drops![
+            ("cockatrice wattle", 60),
+            ("petrified feather", 35),
+            ("Unhatched Cockatrice Egg", 5, unique),
+        ],
+    ),
+    LootTable::new(
+        "Beholder",
+        drops![
+            ("beholder eyestalk", 65),
+            ("beholder lens", 30),
+            ("Central Eye of Xanathar", 5, unique),
+        ],
+    ),
+    LootTable::new(
+        "Orcus",
+        drops![
+            ("orcus wand", 70),
+            ("shard of the abyss", 27),
+            ("Wand of Orcus", 3, unique),
+        ],
+    ),
+    LootTable::new(
+        "Demogorgon",
+        drops![
+            ("demogorgon tentacle", 70),
+            ("forked tongue", 27),
+            ("Twin Crowns of Demogorgon", 3, unique),
+        ],
+    ),
+];
+
+/// What a class starts out with. A negative quality is shown in front of the name, like "-3 Burlap"
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct StartingKit {
+    pub class: Cow<'static, str>,
+    pub equipment: Cow<'static, [(Equipment, EquipmentPreset)]>,
+}
+
+impl StartingKit {
+    pub const fn new(
+        class: &'static str,
+        equipment: &'static [(Equipment, EquipmentPreset)],
+    ) -> Self {
+        Self {
+            class: Cow::Borrowed(class),
+            equipment: Cow::Borrowed(equipment),
+        }
+    }
+}
+
+// const fns can't be promoted to a static slice, but struct literals can
+macro_rules! kit {
+    ($(($slot:ident, $name:expr, $quality:expr)),* $(,)?) => {
+        &[$((
+            Equipment::$slot,
+            EquipmentPreset {
+                name: Cow::Borrowed($name),
+                quality: $quality,
+            },
+        )),*]
+    };
+}
+
+/// For classes without a kit
+pub const DEFAULT_KIT: &[(Equipment, EquipmentPreset)] =
+    kit![(Weapon, "Sharp Rock", 0), (Hauberk, "Burlap", -3)];
+
+pub const STARTING_KITS: &[StartingKit] = &[
+    StartingKit::new(
+        "Ur-Paladin",
+        kit![
+            (Weapon, "Rusty Mace", -1),
+            (Shield, "Pie Plate", -2),
+            (Hauberk, "Burlap", -3),
+        ],
+    ),
+    StartingKit::new(
+        "Voodoo Princess",
+        kit![(Weapon, "Pin Cushion", -2), (Hauberk, "Lace", -2)],
+    ),
+    StartingKit::new(
+        "Robot Monk",
+        kit![(Weapon, "Socket Wrench", -1), (Hauberk, "Tin Plating", -3)],
+    ),
+    StartingKit::new(
+        "Mu-Fu Monk",
+        kit![(Weapon, "Bare Fists", 0), (Hauberk, "Burlap", -3)],
+    ),
+    StartingKit::new(
+        "Mage Illusioner",
+        kit![(Weapon, "Cracked Wand", -2), (Hauberk, "Macrame", -3)],
+    ),
+    StartingKit::new(
+        "Shiv Knight",
+        kit![
+            (Weapon, "Shiv", -1),
+            (Shield, "Garbage Can Lid", -2),
+            (Hauberk, "Burlap", -3),
+        ],
+    ),
+    StartingKit::new(
+        "Inner Mason",
+        kit![(Weapon, "Trowel", -1), (Hauberk, "Canvas", -3)],
+    ),
+    StartingKit::new(
+        "Fighter/Organist",
+        kit![(Weapon, "Organ Pipe", -1), (Hauberk, "Flannel", -3)],
+    ),
+    StartingKit::new(
+        "Puma Burgular",
+        kit![(Weapon, "Lockpick", -2), (Hauberk, "Chamois", -3)],
+    ),
+    StartingKit::new(
+        "Runeloremaster",
+        kit![(Weapon, "Chisel", -2), (Hauberk, "Burlap", -3)],
+    ),
+    StartingKit::new(
+        "Hunter Strangler",
+        kit![(Weapon, "Frayed Garrote", -1), (Hauberk, "Canvas", -3),],
+    ),
+    StartingKit::new(
+        "Battle Felon",
+        kit![
+            (Weapon, "Broken Bottle", -1),
+            (Hauberk, "Prison Stripes", -3),
+        ],
+    ),
+    StartingKit::new(
+        "Tickle-Mimic",
+        kit![(Weapon, "Feather", -2), (Hauberk, "Lace", -3)],
+    ),
+    StartingKit::new(
+        "Slow Poisoner",
+        kit![(Weapon, "Leaky Vial", -2), (Hauberk, "Burlap", -3)],
+    ),
+    StartingKit::new(
+        "Lowling",
+        kit![(Weapon, "Sharp Rock", 0), (Hauberk, "Burlap", -4)],
+    ),
+    StartingKit::new(
+        "Birdrider",
+        kit![
+            (Weapon, "Riding Crop", -2),
+            (Helm, "Feathered Cap", -2),
+            (Hauberk, "Burlap", -3),
+        ],
+    ),
+    StartingKit::new(
+        "Bastard Lunatic",
+        kit![(Weapon, "Stick", -1), (Hauberk, "Straitjacket", -3)],
+    ),
+    StartingKit::new(
+        "Vermineer",
+        kit![(Weapon, "Rat Trap", -1), (Hauberk, "Burlap", -3)],
+    ),
+];
+
+/// A few things a race starts out carrying
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Provisions {
+    pub race: Cow<'static, str>,
+    pub items: Cow<'static, [Cow<'static, str>]>,
+}
+
+impl Provisions {
+    pub const fn new(race: &'static str, items: &'static [Cow<'static, str>]) -> Self {
+        Self {
+            race: Cow::Borrowed(race),
+            items: Cow::Borrowed(items),
+        }
+    }
+}
+
+/// What the quests given in a [`Climate`] lean toward, see [`ContentPack::climates`]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct ClimateTags {
+    pub climate: Climate,
+    /// The families of monster that quests there ask after
+    pub families: Cow<'static, [Family]>,
+    /// Boring items the locals want fetched and delivered
+    pub items: Cow<'static, [Cow<'static, str>]>,
+}
+
+impl ClimateTags {
+    pub const fn new(
+        climate: Climate,
+        families: &'static [Family],
+        items: &'static [Cow<'static, str>],
+    ) -> Self {
+        Self {
+            climate,
+            families: Cow::Borrowed(families),
+            items: Cow::Borrowed(items),
+        }
+    }
+}
+
+pub const CLIMATES: &[ClimateTags] = &[
+    ClimateTags::new(
+        Climate::Frozen,
+        &[Family::Beasts, Family::Undead],
+        materials![
+            "lump of glacier ice",
+            "icicle",
+            "frostbitten mitten",
+            "snowshoe",
+            "jar of frozen breath"
+        ],
+    ),
+    ClimateTags::new(
+        Climate::Scorched,
+        &[Family::Dragons, Family::Fiends],
+        materials![
+            "cinder",
+            "lump of pumice",
+            "singed eyebrow",
+            "jar of ash",
+            "cooling rock"
+        ],
+    ),
+    ClimateTags::new(
+        Climate::Sodden,
+        &[Family::Beasts],
+        materials![
+            "soggy boot",
+            "bucket of bog water",
+            "leech",
+            "reed",
+            "waterlogged scroll"
+        ],
+    ),
+    ClimateTags::new(
+        Climate::Haunted,
+        &[Family::Undead, Family::Fiends],
+        materials![
+            "grave candle",
+            "cracked mirror",
+            "lock of ghost hair",
+            "funeral bell",
+            "cold draft"
+        ],
+    ),
+    ClimateTags::new(
+        Climate::Verdant,
+        &[Family::Beasts, Family::Dragons],
+        materials![
+            "acorn",
+            "bundle of moss",
+            "mushroom",
+            "birds nest",
+            "sprig of thyme"
+        ],
+    ),
+    ClimateTags::new(
+        Climate::Ruined,
+        &[Family::Constructs, Family::Undead],
+        materials![
+            "rusty cog",
+            "crumbled brick",
+            "broken column",
+            "toppled statue nose",
+            "old coin"
+        ],
+    ),
+];
+
+pub const PROVISIONS: &[Provisions] = &[
+    Provisions::new("Half Orc", materials!["tusk polish", "grog"]),
+    Provisions::new("Half Man", materials!["half a sandwich"]),
+    Provisions::new("Half Halfling", materials!["quarter loaf"]),
+    Provisions::new("Double Hobbit", materials!["second breakfast", "pipeweed"]),
+    Provisions::new("Hob-Hobbit", materials!["hob nail"]),
+    Provisions::new("Low Elf", materials!["stale lembas"]),
+    Provisions::new("Dung Elf", materials!["dung cake"]),
+    Provisions::new("Talking Pony", materials!["sugar cube", "horseshoe"]),
+    Provisions::new("Gyrognome", materials!["spare gear"]),
+    Provisions::new("Lesser Dwarf", materials!["lesser ale"]),
+    Provisions::new("Crested Dwarf", materials!["crest wax"]),
+    Provisions::new("Eel Man", materials!["jar of brine"]),
+    Provisions::new("Panda Man", materials!["bamboo shoot"]),
+    Provisions::new("Trans-Kobold", materials!["shiny pebble"]),
+    Provisions::new("Enchanted Motorcycle", materials!["can of oil"]),
+    Provisions::new("Will o' the Wisp", materials!["stub of candle"]),
+    Provisions::new("Battle-Finch", materials!["birdseed"]),
+    Provisions::new("Double Wookiee", materials!["comb"]),
+    Provisions::new("Skraeling", materials!["dried fish"]),
+    Provisions::new("Demicanadian", materials!["maple syrup"]),
+    Provisions::new("Land Squid", materials!["ink pot"]),
+];
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Hazard {
+    pub name: Cow<'static, str>,
+    pub stat: Stat,
+    pub difficulty: usize,
+    pub success: Cow<'static, str>,
+    pub failure: Cow<'static, str>,
+}
+
+impl Hazard {
+    pub const fn new(
+        name: &'static str,
+        stat: Stat,
+        difficulty: usize,
+        success: &'static str,
+        failure: &'static str,
+    ) -> Self {
+        Self {
+            name: Cow::Borrowed(name),
+            stat,
+            difficulty,
+            success: Cow::Borrowed(success),
+            failure: Cow::Borrowed(failure),
         }
     }
 }
 
+pub const HAZARDS: &[Hazard] = &[
+    Hazard::new(
+        "pit trap",
+        Stat::Dexterity,
+        10,
+        "You nimbly sidestep a concealed pit trap",
+        "You tumble headlong into a pit trap",
+    ),
+    Hazard::new(
+        "poison needle",
+        Stat::Condition,
+        12,
+        "A poison needle pricks you, but you shrug it off",
+        "A poison needle leaves you queasy and sweating",
+    ),
+    Hazard::new(
+        "riddling sphinx",
+        Stat::Intelligence,
+        12,
+        "You answer the sphinx's riddle with a smug grin",
+        "The sphinx's riddle leaves you weeping in confusion",
+    ),
+    Hazard::new(
+        "fallen tree",
+        Stat::Strength,
+        10,
+        "You heave a fallen tree out of the road",
+        "You strain your back on a fallen tree",
+    ),
+    Hazard::new(
+        "suspicious mushroom",
+        Stat::Wisdom,
+        8,
+        "You wisely decline a suspicious mushroom",
+        "You eat a suspicious mushroom, and regret it",
+    ),
+    Hazard::new(
+        "toll troll",
+        Stat::Charisma,
+        10,
+        "You charm your way past a toll troll",
+        "A toll troll takes offense at your face",
+    ),
+];
+
+pub const DUNGEONS: &[&str] = &[
+    "Crypt",
+    "Warrens",
+    "Catacombs",
+    "Sunken Halls",
+    "Labyrinth",
+    "Oubliette",
+    "Barrow",
+    "Undercroft",
+];
+
+pub const TERRAINS: &[&str] = &[
+    "Marches", "Wilds", "Reach", "Vale", "Fens", "Peaks", "Barrens", "Downs", "Weald", "Moors",
+];
+
+/// Quest captions with a `{theme}` slot for one of the player's themes, see [`crate::lingo::weave`]
+/// and [`ContentPack::themed_quests`]
+pub const THEMED_QUESTS: &[&str] = &[
+    "Recover the stolen {theme}",
+    "Investigate rumors of {theme}",
+    "Guard the shrine of {theme}",
+    "Bring word of {theme} to the capital",
+    "Find out who's hoarding {theme}",
+];
+
+/// Quest captions with a `{theme}` slot for the region the hero was last in, see
+/// [`ContentPack::regional_quests`]
+pub const REGIONAL_QUESTS: &[&str] = &[
+    "Chart the far edges of {theme}",
+    "Bring back word from {theme}",
+    "Find the lost patrol in {theme}",
+    "Light the beacons across {theme}",
+];
+
+/// Cinematic scenes with a `{theme}` slot, like [`THEMED_QUESTS`]
+pub const THEMED_SCENES: &[&str] = &[
+    "You dream of {theme}, and wake with new resolve",
+    "A bard by the fire sings of {theme}",
+    "The elders speak of {theme} in hushed tones",
+    "A stranger presses you for news of {theme}",
+];
+
+pub const ACT_TITLE_ADJECTIVES: &[&str] = &[
+    "Frozen",
+    "Burning",
+    "Sunken",
+    "Forgotten",
+    "Shattered",
+    "Howling",
+    "Gilded",
+    "Withered",
+    "Endless",
+    "Crimson",
+    "Silent",
+    "Drowned",
+];
+
+pub const ACT_TITLE_PLACES: &[&str] = &[
+    "Marches", "Wastes", "Reaches", "Spires", "Fens", "Vaults", "Hills", "Steppes", "Isles",
+    "Depths", "Woods", "Halls",
+];
+
+pub const NAME_SYLLABLES: &[&str] = &[
+    "al", "an", "ar", "bel", "bor", "dan", "dor", "el", "en", "fal", "gar", "gor", "hal", "is",
+    "kor", "lin", "mar", "mor", "nim", "or", "quil", "ran", "sar", "thal", "tor", "ul", "vor",
+    "wen", "yar", "zed",
+];
+
+pub const NAME_CORPUS: &[&str] = &[
+    "Aldric",
+    "Bramble",
+    "Corwin",
+    "Dunstan",
+    "Elowen",
+    "Fenwick",
+    "Gildas",
+    "Hestia",
+    "Isolde",
+    "Jorund",
+    "Kestrel",
+    "Lorcan",
+    "Marigold",
+    "Niamh",
+    "Osric",
+    "Perrin",
+    "Quenby",
+    "Rowena",
+    "Sigrid",
+    "Tamsin",
+    "Ulric",
+    "Veda",
+    "Wystan",
+    "Yseult",
+    "Zephyrine",
+    "Grimbold",
+    "Thistle",
+    "Barnaby",
+    "Cuthbert",
+    "Edwina",
+    "Hamish",
+    "Ottoline",
+    "Pelagius",
+    "Rosalind",
+    "Wilhelmina",
+];
+
 pub const TITLES: &[&str] = &[
     "Mr.", "Mrs.", "Sir", "Sgt.", "Ms.", "Captain", "Chief", "Admiral", "Saint",
 ];
@@ -810,3 +1786,383 @@ pub const IMPRESSIVE_TITLES: &[&str] = &[
     "Boss",
     "Archbishop",
 ];
+
+/// Generated names containing any of these (ignoring case and spaces) are thrown away
+pub const NAME_DENY_LIST: &[&str] = &[
+    "chink", "cock", "cunt", "dick", "fag", "fuck", "jap", "kike", "nig", "rape", "shit", "slut",
+    "spic", "twat", "whore",
+];
+
+/// The formulas the pacing of the game comes from, carried by a
+/// [`Simulation`](crate::mechanics::Simulation). The defaults are the original's
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Tuning {
+    /// Each level takes this many minutes of experience per level, so level 3 takes three times
+    /// as long as level 1
+    pub level_up_minutes: usize,
+    /// The `[a, b, c]` in the price of new equipment, `a * level² + b * level + c`
+    pub equipment_price: [usize; 3],
+    /// How many items the hero can carry without any strength
+    pub base_capacity: usize,
+    /// How many more items each point of strength lets the hero carry
+    pub capacity_per_strength: usize,
+    /// How long quests are, as a range of progress. Shorter ones are more likely
+    pub quest_length: std::ops::Range<usize>,
+    /// Gold per level for finishing the longest quests, shorter ones give proportionally less
+    pub quest_gold: usize,
+    /// The fastest the game can go, in seconds of game time per real second
+    pub max_time_scale: f32,
+    /// How fast new characters start out going
+    pub default_time_scale: f32,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Tuning {
+    pub const DEFAULT: Self = Self {
+        level_up_minutes: 20,
+        equipment_price: [5, 10, 20],
+        base_capacity: 10,
+        capacity_per_strength: 1,
+        quest_length: 50..1050,
+        quest_gold: 50,
+        max_time_scale: 100.0,
+        default_time_scale: 1.0,
+    };
+
+    /// `scale` kept between real time and [`Self::max_time_scale`]
+    pub fn clamp_time_scale(&self, scale: f32) -> f32 {
+        scale.clamp(1.0, self.max_time_scale.max(1.0))
+    }
+
+    pub const fn level_up_time(&self, level: usize) -> Duration {
+        Duration::from_secs((self.level_up_minutes * level * 60) as _)
+    }
+
+    /// What a new piece of equipment costs at `level`
+    pub const fn equipment_price(&self, level: usize) -> isize {
+        let [a, b, c] = self.equipment_price;
+        (a * level.pow(2) + b * level + c) as _
+    }
+
+    /// How many items a hero with `strength` can carry
+    pub const fn capacity(&self, strength: usize) -> usize {
+        self.base_capacity + self.capacity_per_strength * strength
+    }
+
+    pub fn quest_length(&self, rng: &crate::Rand) -> f32 {
+        let spread = self
+            .quest_length
+            .end
+            .saturating_sub(self.quest_length.start);
+        (self.quest_length.start + rng.below_low(spread.max(1))) as f32
+    }
+
+    /// Where a quest of `length` falls in [`Self::quest_length`], from 0 for the shortest to 1
+    /// for the longest
+    pub fn quest_difficulty(&self, length: f32) -> f32 {
+        let spread = self
+            .quest_length
+            .end
+            .saturating_sub(self.quest_length.start)
+            .max(1);
+        ((length - self.quest_length.start as f32) / spread as f32).clamp(0.0, 1.0)
+    }
+
+    /// Check the formulas can be played with, a loaded tuning could say anything
+    pub fn validate(&self) -> Result<(), TuningError> {
+        if self.level_up_minutes == 0 {
+            return Err(TuningError::InstantLevels);
+        }
+        if self.quest_length.is_empty() {
+            return Err(TuningError::QuestLength(self.quest_length.clone()));
+        }
+        if !(1.0..=self.max_time_scale).contains(&self.default_time_scale) {
+            return Err(TuningError::TimeScale {
+                default: self.default_time_scale,
+                max: self.max_time_scale,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Why a [`Tuning`] can't be played with
+#[derive(Debug, Clone, PartialEq)]
+pub enum TuningError {
+    /// Levels taking no time at all
+    InstantLevels,
+    /// No quest lengths to pick from
+    QuestLength(std::ops::Range<usize>),
+    /// New characters would start out faster than the game can go, or slower than real time
+    TimeScale { default: f32, max: f32 },
+}
+
+impl std::fmt::Display for TuningError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InstantLevels => f.write_str("level_up_minutes must be at least 1"),
+            Self::QuestLength(range) => write!(
+                f,
+                "quest_length {}..{} is empty, the end must be after the start",
+                range.start, range.end
+            ),
+            Self::TimeScale { default, max } => write!(
+                f,
+                "default_time_scale {default} must be between 1 and max_time_scale {max}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TuningError {}
+
+/// The [`ContentPack::id`] of the built-in content, bumped whenever it changes how runs play out
+pub const BUILTIN_CONTENT: &str = "builtin-1";
+
+/// Everything the simulation makes content from, so it can be modded without recompiling.
+///
+/// The default is the built-in content above. A deserialized pack can leave out any list to
+/// keep the built-in one
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ContentPack {
+    /// Names the pack and its version, like `frozen-north-2`. Ironman runs are locked to it, so it
+    /// should change whenever the pack changes how a run plays out
+    pub id: Cow<'static, str>,
+    pub races: Vec<Race>,
+    pub classes: Vec<Class>,
+    pub starting_kits: Vec<StartingKit>,
+    pub provisions: Vec<Provisions>,
+    pub monsters: Vec<Monster>,
+    pub spells: Vec<Cow<'static, str>>,
+
+    pub weapons: Vec<EquipmentPreset>,
+    pub shields: Vec<EquipmentPreset>,
+    pub armors: Vec<EquipmentPreset>,
+    pub offense_attributes: Vec<Modifier>,
+    pub defense_attributes: Vec<Modifier>,
+    pub offense_quirks: Vec<Modifier>,
+    pub defense_quirks: Vec<Modifier>,
+
+    pub specials: Vec<Cow<'static, str>>,
+    pub item_attributes: Vec<Cow<'static, str>>,
+    pub item_prepositions: Vec<Cow<'static, str>>,
+    pub boring_items: Vec<Cow<'static, str>>,
+
+    pub recipes: Vec<Recipe>,
+    pub gatherings: Vec<Gathering>,
+    pub loot_tables: Vec<LootTable>,
+    pub hazards: Vec<Hazard>,
+    pub dungeons: Vec<Cow<'static, str>>,
+    pub terrains: Vec<Cow<'static, str>>,
+    pub climates: Vec<ClimateTags>,
+    /// Quest captions with a `{theme}` slot for the region the hero was last in
+    pub regional_quests: Vec<Cow<'static, str>>,
+    /// Quest captions with a `{theme}` slot for one of the player's themes. Like the rest of the
+    /// pack, these should be in the language picked with [`crate::lingo::set_language`]
+    pub themed_quests: Vec<Cow<'static, str>>,
+    /// Cinematic scenes with a `{theme}` slot for one of the player's themes
+    pub themed_scenes: Vec<Cow<'static, str>>,
+
+    pub titles: Vec<Cow<'static, str>>,
+    pub impressive_titles: Vec<Cow<'static, str>>,
+    pub act_title_adjectives: Vec<Cow<'static, str>>,
+    pub act_title_places: Vec<Cow<'static, str>>,
+
+    /// Achievements on top of the built-in ones, one with a built-in id replaces it
+    pub achievements: Vec<crate::codex::Achievement>,
+    /// Titles for the [codex](crate::codex) on top of the slayer titles
+    pub codex_titles: Vec<crate::codex::Achievement>,
+}
+
+impl Default for ContentPack {
+    fn default() -> Self {
+        fn words(list: &'static [&'static str]) -> Vec<Cow<'static, str>> {
+            list.iter().copied().map(Cow::Borrowed).collect()
+        }
+
+        Self {
+            id: Cow::Borrowed(BUILTIN_CONTENT),
+            races: RACES.to_vec(),
+            classes: CLASSES.to_vec(),
+            starting_kits: STARTING_KITS.to_vec(),
+            provisions: PROVISIONS.to_vec(),
+            monsters: MONSTERS.to_vec(),
+            spells: words(SPELLS),
+
+            weapons: WEAPONS.to_vec(),
+            shields: SHIELDS.to_vec(),
+            armors: ARMORS.to_vec(),
+            offense_attributes: OFFENSE_ATTRIBUTE.to_vec(),
+            defense_attributes: DEFENSE_ATTRIBUTE.to_vec(),
+            offense_quirks: OFFENSE_QUIRK.to_vec(),
+            defense_quirks: DEFENSE_QUIRK.to_vec(),
+
+            specials: words(SPECIALS),
+            item_attributes: words(ITEM_ATTRIBUTES),
+            item_prepositions: words(ITEM_PREPOSITION),
+            boring_items: words(BORING_ITEMS),
+
+            recipes: RECIPES.to_vec(),
+            gatherings: GATHERINGS.to_vec(),
+            loot_tables: LOOT_TABLES.to_vec(),
+            hazards: HAZARDS.to_vec(),
+            dungeons: words(DUNGEONS),
+            terrains: words(TERRAINS),
+            climates: CLIMATES.to_vec(),
+            regional_quests: words(REGIONAL_QUESTS),
+            themed_quests: words(THEMED_QUESTS),
+            themed_scenes: words(THEMED_SCENES),
+
+            titles: words(TITLES),
+            impressive_titles: words(IMPRESSIVE_TITLES),
+            act_title_adjectives: words(ACT_TITLE_ADJECTIVES),
+            act_title_places: words(ACT_TITLE_PLACES),
+
+            achievements: Vec::new(),
+            codex_titles: Vec::new(),
+        }
+    }
+}
+
+impl ContentPack {
+    /// Replace any empty lists with the built-in ones, everything is chosen from at some point
+    fn fill_empty(mut self) -> Self {
+        fn fill<T>(list: &mut Vec<T>, builtin: Vec<T>) {
+            if list.is_empty() {
+                *list = builtin
+            }
+        }
+
+        let builtin = Self::default();
+        fill(&mut self.races, builtin.races);
+        fill(&mut self.classes, builtin.classes);
+        fill(&mut self.starting_kits, builtin.starting_kits);
+        fill(&mut self.provisions, builtin.provisions);
+        fill(&mut self.monsters, builtin.monsters);
+        fill(&mut self.spells, builtin.spells);
+        fill(&mut self.weapons, builtin.weapons);
+        fill(&mut self.shields, builtin.shields);
+        fill(&mut self.armors, builtin.armors);
+        fill(&mut self.offense_attributes, builtin.offense_attributes);
+        fill(&mut self.defense_attributes, builtin.defense_attributes);
+        fill(&mut self.offense_quirks, builtin.offense_quirks);
+        fill(&mut self.defense_quirks, builtin.defense_quirks);
+        fill(&mut self.specials, builtin.specials);
+        fill(&mut self.item_attributes, builtin.item_attributes);
+        fill(&mut self.item_prepositions, builtin.item_prepositions);
+        fill(&mut self.boring_items, builtin.boring_items);
+        fill(&mut self.hazards, builtin.hazards);
+        fill(&mut self.dungeons, builtin.dungeons);
+        fill(&mut self.terrains, builtin.terrains);
+        fill(&mut self.climates, builtin.climates);
+        fill(&mut self.regional_quests, builtin.regional_quests);
+        fill(&mut self.themed_quests, builtin.themed_quests);
+        fill(&mut self.themed_scenes, builtin.themed_scenes);
+        fill(&mut self.titles, builtin.titles);
+        fill(&mut self.impressive_titles, builtin.impressive_titles);
+        fill(&mut self.act_title_adjectives, builtin.act_title_adjectives);
+        fill(&mut self.act_title_places, builtin.act_title_places);
+        // no recipes just means nothing can be crafted, no gatherings means nothing is gathered,
+        // and no loot tables means every monster drops a part of itself. Achievements and codex
+        // titles are added to the built-in ones, see [`crate::codex`]
+        self
+    }
+}
+
+impl ContentPack {
+    /// What quests in `climate` lean toward, if this pack says
+    pub fn climate(&self, climate: Climate) -> Option<&ClimateTags> {
+        self.climates.iter().find(|tags| tags.climate == climate)
+    }
+
+    /// Fill in any lists that were left out, ready for [`set_content`] or a
+    /// [`Simulation`](crate::mechanics::Simulation) of its own
+    pub fn shared(self) -> Arc<Self> {
+        Arc::new(self.fill_empty())
+    }
+}
+
+static CONTENT: RwLock<Option<Arc<ContentPack>>> = RwLock::new(None);
+
+thread_local! {
+    /// Set while a simulation with its own content is playing on this thread
+    static SCOPED: std::cell::RefCell<Option<Arc<ContentPack>>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Replace the content used everywhere. This only affects content generated from now on, and not
+/// simulations that were given their own
+pub fn set_content(pack: ContentPack) {
+    *CONTENT.write().unwrap() = Some(pack.shared());
+}
+
+/// Use `pack` in place of the global content for anything `f` does on this thread
+pub fn with_content<T>(pack: &Arc<ContentPack>, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<Arc<ContentPack>>);
+    impl std::ops::Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED.with(|scoped| *scoped.borrow_mut() = self.0.take())
+        }
+    }
+
+    let _restore = Restore(SCOPED.with(|scoped| scoped.replace(Some(Arc::clone(pack)))));
+    f()
+}
+
+/// The current content. That's the pack given to [`with_content`] while it runs, otherwise the
+/// built-in content unless [`set_content`] was called
+pub fn content() -> Arc<ContentPack> {
+    if let Some(pack) = SCOPED.with(|scoped| scoped.borrow().clone()) {
+        return pack;
+    }
+    if let Some(pack) = &*CONTENT.read().unwrap() {
+        return Arc::clone(pack);
+    }
+    Arc::clone(
+        CONTENT
+            .write()
+            .unwrap()
+            .get_or_insert_with(|| Arc::new(ContentPack::default())),
+    )
+}
+
+#[test]
+fn tuning_validation() {
+    assert_eq!(Tuning::DEFAULT.validate(), Ok(()));
+
+    let invalid = [
+        Tuning {
+            level_up_minutes: 0,
+            ..Tuning::DEFAULT
+        },
+        Tuning {
+            quest_length: 50..50,
+            ..Tuning::DEFAULT
+        },
+        Tuning {
+            quest_length: std::ops::Range {
+                start: 1050,
+                end: 50,
+            },
+            ..Tuning::DEFAULT
+        },
+        Tuning {
+            max_time_scale: 10.0,
+            default_time_scale: 20.0,
+            ..Tuning::DEFAULT
+        },
+        Tuning {
+            default_time_scale: f32::NAN,
+            ..Tuning::DEFAULT
+        },
+    ];
+    for tuning in invalid {
+        assert!(tuning.validate().is_err(), "{tuning:?}");
+    }
+}
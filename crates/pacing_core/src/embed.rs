@@ -0,0 +1,201 @@
+//! A small JSON interface for driving the simulation from outside of Rust.
+//!
+//! The state is an opaque document that is passed back into [`step`] unchanged, apart from its
+//! `snapshot` field, which is a stable read-only view of the character. Events are a JSON array
+//! of objects tagged by their `type`.
+
+use crate::{
+    config,
+    lingo::generate_name,
+    mechanics::{Player, Simulation, StatsBuilder},
+    Rand, SliceExt,
+};
+
+/// Bumped whenever the shape of the state, [`Snapshot`] or [`Event`] changes incompatibly
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum Error {
+    Json(serde_json::Error),
+    Version { found: u32 },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "invalid json: {err}"),
+            Self::Version { found } => write!(
+                f,
+                "unsupported schema version: {found}, expected {SCHEMA_VERSION}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct State {
+    version: u32,
+    player: Player,
+}
+
+/// What a frontend needs to draw the character, without depending on the internal types
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Snapshot {
+    pub name: String,
+    pub race: String,
+    pub class: String,
+    pub level: usize,
+    pub act: i32,
+    pub elapsed: f32,
+    pub gold: isize,
+    pub task: Option<String>,
+    /// Progress through the current task, from 0.0 to 1.0
+    pub task_progress: f32,
+    pub exp_progress: f32,
+    /// The game time the task and experience bars last filled up at
+    pub task_completed_at: Option<f32>,
+    pub exp_completed_at: Option<f32>,
+    pub quest: Option<String>,
+}
+
+impl Snapshot {
+    pub fn of(player: &Player) -> Self {
+        fn progress(bar: &crate::mechanics::Bar) -> f32 {
+            if bar.max <= 0.0 {
+                return 0.0;
+            }
+            (bar.pos / bar.max).clamp(0.0, 1.0)
+        }
+
+        Self {
+            name: player.name.clone(),
+            race: player.race.name.to_string(),
+            class: player.class.name.to_string(),
+            level: player.level,
+            act: player.quest_book.act(),
+            elapsed: player.elapsed,
+            gold: player.inventory.gold(),
+            task: player
+                .task
+                .as_ref()
+                .map(|task| task.description.to_string()),
+            task_progress: progress(&player.task_bar),
+            exp_progress: progress(&player.exp_bar),
+            task_completed_at: player.task_bar.completed_at,
+            exp_completed_at: player.exp_bar.completed_at,
+            quest: player
+                .quest_book
+                .current_quest()
+                .map(|quest| quest.caption.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    TaskCompleted {
+        description: String,
+    },
+    LevelUp {
+        level: usize,
+    },
+    ActStarted {
+        act: i32,
+    },
+    /// Anything from the character's journal, already formatted
+    Journal {
+        elapsed: f32,
+        text: String,
+    },
+}
+
+/// Roll a new random character, returning its state
+pub fn create(seed: u64) -> Result<String, Error> {
+    save(&roll(&Rand::seed(seed)))
+}
+
+/// Roll a new random character
+pub fn roll(rng: &Rand) -> Player {
+    let content = config::content();
+    Player::new(
+        generate_name(None, rng),
+        content.races.choice(rng).clone(),
+        content.classes.choice(rng).clone(),
+        StatsBuilder::default().roll(rng),
+    )
+}
+
+/// Serialize a character into a state document
+pub fn save(player: &Player) -> Result<String, Error> {
+    #[derive(serde::Serialize)]
+    struct StateRef<'a> {
+        version: u32,
+        snapshot: Snapshot,
+        player: &'a Player,
+    }
+
+    Ok(serde_json::to_string(&StateRef {
+        version: SCHEMA_VERSION,
+        snapshot: Snapshot::of(player),
+        player,
+    })?)
+}
+
+/// Deserialize a character from a state document
+pub fn load(state: &str) -> Result<Player, Error> {
+    let state: State = serde_json::from_str(state)?;
+    if state.version != SCHEMA_VERSION {
+        return Err(Error::Version {
+            found: state.version,
+        });
+    }
+    Ok(state.player)
+}
+
+/// Advance the character in `state` by `dt` seconds of game time, returning the new state and
+/// the events that happened along the way.
+///
+/// The seed is mixed with the game clock, so the same seed can be used for every step.
+pub fn step(state: &str, dt: f32, seed: u64) -> Result<(String, String), Error> {
+    let player = load(state)?;
+    let seed = seed ^ player.elapsed.to_bits() as u64;
+    let mut simulation = Simulation::with_seed(player, seed);
+
+    let events = serde_json::to_string(&advance(&mut simulation, dt))?;
+    Ok((save(&simulation.player)?, events))
+}
+
+/// Advance a long-lived simulation by `dt` seconds of game time, returning the events that
+/// happened along the way
+pub fn advance(simulation: &mut Simulation, dt: f32) -> Vec<Event> {
+    let level = simulation.player.level;
+    let act = simulation.player.quest_book.act();
+    let recorded = simulation.player.journal.recorded();
+    let completed = simulation.completed();
+
+    simulation.advance_by(dt);
+
+    let player = &simulation.player;
+    let mut events = simulation
+        .history_since(completed)
+        .map(|description| Event::TaskCompleted {
+            description: description.to_string(),
+        })
+        .collect::<Vec<_>>();
+    events.extend((level + 1..=player.level).map(|level| Event::LevelUp { level }));
+    events.extend((act + 1..=player.quest_book.act()).map(|act| Event::ActStarted { act }));
+    events.extend(player.journal.since(recorded).map(|entry| Event::Journal {
+        elapsed: entry.elapsed,
+        text: entry.event.to_string(),
+    }));
+    events
+}
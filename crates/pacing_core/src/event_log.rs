@@ -0,0 +1,254 @@
+//! A fine grained log of what the simulation did, for showing a scrolling adventure journal.
+//!
+//! The milestones are already in the player's [`Journal`], and the finished tasks in the
+//! simulation's [history](crate::mechanics::Simulation::history). The [`EventLog`] lives on the
+//! [`Simulation`](crate::mechanics::Simulation) and only keeps what neither of them does, and
+//! [`EventLog::with_journal`] weaves it back together with the journal.
+
+use std::collections::VecDeque;
+
+use crate::{
+    checkpoint::StateDiff,
+    combat::Blow,
+    config,
+    journal::{Event, Journal},
+    lingo::indefinite,
+};
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum LogEvent {
+    LevelUp {
+        level: usize,
+    },
+    ItemGained {
+        name: String,
+    },
+    EquipmentUpgraded {
+        slot: config::Equipment,
+        name: String,
+    },
+    /// A single blow of a fight, recorded as the fight's task finishes
+    Blow {
+        foe: String,
+        blow: Blow,
+    },
+}
+
+impl LogEvent {
+    /// The events describing what changed in `diff`, in the order they should be shown
+    pub fn from_diff(diff: &StateDiff) -> impl Iterator<Item = Self> + '_ {
+        let levels = (diff.level.0 + 1..=diff.level.1).map(|level| Self::LevelUp { level });
+        let items = diff
+            .new_items
+            .iter()
+            .map(|name| Self::ItemGained { name: name.clone() });
+        let equipment = diff
+            .new_equipment
+            .iter()
+            .map(|(slot, name)| Self::EquipmentUpgraded {
+                slot: *slot,
+                name: name.clone(),
+            });
+
+        levels.chain(items).chain(equipment)
+    }
+}
+
+impl std::fmt::Display for LogEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LevelUp { level } => write!(f, "Reached level {level}"),
+            Self::ItemGained { name } => write!(f, "Gained {}", indefinite(name, 1)),
+            Self::EquipmentUpgraded { slot, name } => write!(f, "Equipped {name} ({slot})"),
+            Self::Blow { foe, blow } => match blow {
+                Blow::Hit(damage) => write!(f, "Struck {foe} for {damage}"),
+                Blow::Crit(damage) => write!(f, "Critically struck {foe} for {damage}!"),
+                Blow::Miss => write!(f, "Missed {foe}"),
+                Blow::Fled => write!(f, "Chased off {foe}"),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct LogEntry {
+    pub elapsed: f32,
+    pub event: LogEvent,
+}
+
+/// One line of the adventure journal, from the [`Journal`] or the [`EventLog`]
+#[derive(Copy, Clone, Debug)]
+pub enum LogLine<'a> {
+    Journal(&'a Event),
+    Detail(&'a LogEvent),
+}
+
+impl std::fmt::Display for LogLine<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Journal(event) => event.fmt(f),
+            Self::Detail(event) => event.fmt(f),
+        }
+    }
+}
+
+/// Events oldest first. By default it's a ring buffer that drops the oldest events once it's full
+#[derive(Clone, Debug)]
+pub struct EventLog {
+    entries: VecDeque<LogEntry>,
+    capacity: Option<usize>,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::bounded(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl EventLog {
+    pub const DEFAULT_CAPACITY: usize = 500;
+
+    /// Keep at most `capacity` events
+    pub fn bounded(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity: Some(capacity),
+        }
+    }
+
+    /// Keep every event, for when something else is consuming and clearing the log
+    pub const fn unbounded() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: None,
+        }
+    }
+
+    pub const fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: Option<usize>) {
+        self.capacity = capacity;
+        if let Some(capacity) = capacity {
+            let drop = self.entries.len().saturating_sub(capacity);
+            self.entries.drain(..drop);
+        }
+    }
+
+    pub fn record(&mut self, elapsed: f32, event: LogEvent) {
+        if let Some(capacity) = self.capacity {
+            if capacity == 0 {
+                return;
+            }
+            while self.entries.len() >= capacity {
+                self.entries.pop_front();
+            }
+        }
+        self.entries.push_back(LogEntry { elapsed, event });
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &LogEntry> + ExactSizeIterator {
+        self.entries.iter()
+    }
+
+    /// The newest `count` events, oldest first
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &LogEntry> {
+        self.entries
+            .iter()
+            .skip(self.entries.len().saturating_sub(count))
+    }
+
+    /// The log woven together with the entries of `journal` from the time it covers, oldest first.
+    /// When the log is empty, like after loading, that's the whole journal
+    pub fn with_journal<'a>(&'a self, journal: &'a Journal) -> Vec<(f32, LogLine<'a>)> {
+        let since = self
+            .entries
+            .front()
+            .map_or(f32::NEG_INFINITY, |entry| entry.elapsed);
+        let mut milestones = journal
+            .iter()
+            .filter(|entry| entry.elapsed >= since)
+            .peekable();
+        let mut details = self.entries.iter().peekable();
+
+        let mut lines = Vec::with_capacity(self.entries.len());
+        loop {
+            // what a task earned comes before the milestones it led to
+            let milestone = match (milestones.peek(), details.peek()) {
+                (None, None) => break,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (Some(milestone), Some(detail)) => milestone.elapsed < detail.elapsed,
+            };
+            lines.push(
+                match milestone {
+                    true => milestones
+                        .next()
+                        .map(|entry| (entry.elapsed, LogLine::Journal(&entry.event))),
+                    false => details
+                        .next()
+                        .map(|entry| (entry.elapsed, LogLine::Detail(&entry.event))),
+                }
+                .expect("peeked"),
+            );
+        }
+        lines
+    }
+
+    /// Take every event out of the log
+    pub fn drain(&mut self) -> impl Iterator<Item = LogEntry> + '_ {
+        self.entries.drain(..)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[test]
+fn ring_buffer() {
+    let mut log = EventLog::bounded(3);
+    for level in 1..=5 {
+        log.record(level as f32, LogEvent::LevelUp { level });
+    }
+
+    let levels = log
+        .iter()
+        .map(|entry| match entry.event {
+            LogEvent::LevelUp { level } => level,
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(levels, [3, 4, 5]);
+    assert_eq!(log.recent(1).next().unwrap().elapsed, 5.0);
+
+    let mut journal = Journal::default();
+    journal.record(1.0, Event::TreasureMapFound);
+    journal.record(4.0, Event::TreasureMapFound);
+    let lines = log
+        .with_journal(&journal)
+        .into_iter()
+        .map(|(elapsed, _)| elapsed)
+        .collect::<Vec<_>>();
+    assert_eq!(lines, [3.0, 4.0, 4.0, 5.0]);
+
+    log.set_capacity(Some(1));
+    assert_eq!(log.len(), 1);
+
+    let mut log = EventLog::unbounded();
+    for level in 1..=5 {
+        log.record(level as f32, LogEvent::LevelUp { level });
+    }
+    assert_eq!(log.drain().count(), 5);
+    assert!(log.is_empty());
+}
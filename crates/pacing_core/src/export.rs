@@ -0,0 +1,70 @@
+//! Export a player's history for analysis with other tools
+
+use std::io::Write;
+
+use crate::{format::Time, journal::Event, mechanics::Player};
+
+/// Write the player's journal as JSON Lines, one object per entry, oldest first
+pub fn jsonl(player: &Player, mut out: impl Write) -> std::io::Result<()> {
+    #[derive(serde::Serialize)]
+    struct Line<'a> {
+        elapsed: f32,
+        clock: String,
+        kind: &'static str,
+        text: String,
+        event: &'a Event,
+    }
+
+    for entry in player.journal.iter() {
+        let line = Line {
+            elapsed: entry.elapsed,
+            clock: Time::clock(entry.elapsed),
+            kind: entry.event.kind(),
+            text: entry.event.to_string(),
+            event: &entry.event,
+        };
+        serde_json::to_writer(&mut out, &line)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()
+}
+
+/// Write the scores of every arena gauntlet still in the player's journal as JSON Lines, for
+/// putting on a leaderboard
+pub fn arena_scores(player: &Player, mut out: impl Write) -> std::io::Result<()> {
+    #[derive(serde::Serialize)]
+    struct Line<'a> {
+        hero: &'a str,
+        class: &'a str,
+        roll_mode: String,
+        elapsed: f32,
+        arena: &'a str,
+        rounds: usize,
+        score: usize,
+        trophy: bool,
+    }
+
+    for entry in player.journal.arena() {
+        if let Event::ArenaFinished {
+            name,
+            rounds,
+            score,
+            trophy,
+        } = &entry.event
+        {
+            let line = Line {
+                hero: &player.name,
+                class: &player.class.name,
+                roll_mode: player.roll_mode.to_string(),
+                elapsed: entry.elapsed,
+                arena: name,
+                rounds: *rounds,
+                score: *score,
+                trophy: trophy.is_some(),
+            };
+            serde_json::to_writer(&mut out, &line)?;
+            out.write_all(b"\n")?;
+        }
+    }
+    out.flush()
+}
@@ -1,3 +1,5 @@
+use crate::{highlights::Scoring, lingo::act_name, mechanics::Player};
+
 pub struct Roman;
 impl Roman {
     pub fn from_i32(mut number: i32) -> String {
@@ -52,12 +54,197 @@ impl Roman {
                 _ => 0,
             })
             .fold((0_i32, 0_i32), |(a, max), n| {
-                (a + (n >= max).then_some(n).unwrap_or(-n), max.max(n))
+                (a + if n >= max { n } else { -n }, max.max(n))
             })
             .0
     }
 }
 
+pub struct Time;
+impl Time {
+    pub fn clock(secs: f32) -> String {
+        let secs = secs as u64;
+        format!(
+            "{:02}:{:02}:{:02}",
+            secs / (60 * 60),
+            (secs / 60) % 60,
+            secs % 60
+        )
+    }
+
+    pub fn compact(secs: f32) -> String {
+        let secs = secs as u64;
+        let (days, hours, minutes, secs) = (
+            secs / (60 * 60 * 24),
+            (secs / (60 * 60)) % 24,
+            (secs / 60) % 60,
+            secs % 60,
+        );
+
+        let out = [(days, 'd'), (hours, 'h'), (minutes, 'm'), (secs, 's')]
+            .into_iter()
+            .skip_while(|(n, _)| *n == 0)
+            .take(2)
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, unit)| format!("{n}{unit}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if out.is_empty() {
+            String::from("0s")
+        } else {
+            out
+        }
+    }
+}
+
+/// How a [`CharacterSheet`] is marked up
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SheetStyle {
+    #[default]
+    PlainText,
+    Markdown,
+    /// For forums, like the bragging posts of old
+    BbCode,
+}
+
+impl SheetStyle {
+    pub const ALL: [Self; 3] = [Self::PlainText, Self::Markdown, Self::BbCode];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::PlainText => "Plain text",
+            Self::Markdown => "Markdown",
+            Self::BbCode => "BBCode",
+        }
+    }
+
+    fn heading(&self, f: &mut std::fmt::Formatter<'_>, text: &str) -> std::fmt::Result {
+        match self {
+            Self::PlainText => writeln!(f, "\n{text}\n{}", "-".repeat(text.chars().count())),
+            Self::Markdown => writeln!(f, "\n### {text}\n"),
+            Self::BbCode => writeln!(f, "\n[b]{text}[/b]"),
+        }
+    }
+
+    fn item(&self, f: &mut std::fmt::Formatter<'_>, label: &str, value: &str) -> std::fmt::Result {
+        match self {
+            Self::PlainText => writeln!(f, "  {label}: {value}"),
+            Self::Markdown => writeln!(f, "- **{label}**: {value}"),
+            Self::BbCode => writeln!(f, "[*]{label}: {value}"),
+        }
+    }
+
+    fn list(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        heading: &str,
+        items: impl IntoIterator<Item = (String, String)>,
+    ) -> std::fmt::Result {
+        self.heading(f, heading)?;
+        if let Self::BbCode = self {
+            writeln!(f, "[list]")?;
+        }
+        for (label, value) in items {
+            self.item(f, &label, &value)?;
+        }
+        if let Self::BbCode = self {
+            writeln!(f, "[/list]")?;
+        }
+        Ok(())
+    }
+}
+
+/// A player's stats, equipment, spells, highlights and current quest, for sharing
+pub struct CharacterSheet<'a> {
+    player: &'a Player,
+    style: SheetStyle,
+}
+
+impl<'a> CharacterSheet<'a> {
+    /// How many of the player's most notable moments are shown
+    const HIGHLIGHTS: usize = 3;
+
+    pub const fn new(player: &'a Player, style: SheetStyle) -> Self {
+        Self { player, style }
+    }
+}
+
+impl std::fmt::Display for CharacterSheet<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (player, style) = (self.player, self.style);
+        let title = format!(
+            "{}, level {} {} {}",
+            player.name, player.level, player.race.name, player.class.name
+        );
+        match style {
+            SheetStyle::PlainText => writeln!(f, "{title}\n{}", "=".repeat(title.chars().count()))?,
+            SheetStyle::Markdown => writeln!(f, "## {title}")?,
+            SheetStyle::BbCode => writeln!(f, "[size=150][b]{title}[/b][/size]")?,
+        }
+        writeln!(
+            f,
+            "{}, {} played",
+            act_name(player.quest_book.act()),
+            Time::compact(player.elapsed)
+        )?;
+        if let Some(ironman) = &player.ironman {
+            writeln!(f, "Ironman: {ironman}")?;
+        }
+
+        style.list(
+            f,
+            "Stats",
+            player
+                .stats
+                .iter()
+                .map(|(stat, value)| (stat.to_string(), value.to_string())),
+        )?;
+
+        style.list(
+            f,
+            "Equipment",
+            std::iter::once((String::from("Best"), player.equipment.best().to_string())).chain(
+                player.equipment.iter().filter_map(|(slot, _)| {
+                    Some((slot.to_string(), player.equipment.display(slot)?))
+                }),
+            ),
+        )?;
+
+        if player.spell_book.iter().len() > 0 {
+            style.list(
+                f,
+                "Spell Book",
+                player
+                    .spell_book
+                    .iter()
+                    .map(|(name, level)| (name.to_string(), Roman::from_i32(level))),
+            )?;
+        }
+
+        let highlights = Scoring::default().top(&player.journal, Self::HIGHLIGHTS);
+        if !highlights.is_empty() {
+            style.list(
+                f,
+                "Highlights",
+                highlights.into_iter().map(|highlight| {
+                    (
+                        Time::compact(highlight.entry.elapsed),
+                        highlight.entry.event.to_string(),
+                    )
+                }),
+            )?;
+        }
+
+        if let Some(quest) = player.quest_book.current_quest() {
+            style.heading(f, "Current Quest")?;
+            writeln!(f, "{quest}")?;
+        }
+
+        Ok(())
+    }
+}
+
 #[test]
 fn roman() {
     for (num, cmp) in [
@@ -71,3 +258,46 @@ fn roman() {
         assert_eq!(Roman::to_roman(num), cmp, "{cmp}");
     }
 }
+
+#[test]
+fn time() {
+    for (secs, clock, compact) in [
+        (0.0, "00:00:00", "0s"),
+        (59.9, "00:00:59", "59s"),
+        (3_725.0, "01:02:05", "1h 2m"),
+        (90_061.0, "25:01:01", "1d 1h"),
+        (7_205.0, "02:00:05", "2h"),
+    ] {
+        assert_eq!(Time::clock(secs), clock, "{secs}");
+        assert_eq!(Time::compact(secs), compact, "{secs}");
+    }
+}
+
+#[test]
+fn character_sheet() {
+    use crate::{config, journal::Event, mechanics::Stats, testing};
+
+    let mut player = testing::hero_with(Stats::new(
+        config::ALL_STATS.into_iter().map(|stat| (stat, 10)),
+    ));
+
+    let sheet = CharacterSheet::new(&player, SheetStyle::Markdown).to_string();
+    assert!(sheet.starts_with("## Gorbash, level 1"), "{sheet}");
+    assert!(sheet.contains("- **STR**: 10"), "{sheet}");
+
+    let sheet = CharacterSheet::new(&player, SheetStyle::BbCode).to_string();
+    assert!(sheet.contains("[list]\n[*]STR: 10"), "{sheet}");
+    assert!(!sheet.contains("Highlights"), "{sheet}");
+
+    player.journal.record(
+        90.0,
+        Event::TreasureFound {
+            item: String::from("Sword of Fire"),
+        },
+    );
+    let sheet = CharacterSheet::new(&player, SheetStyle::PlainText).to_string();
+    assert!(
+        sheet.contains("Highlights\n----------\n  1m 30s: "),
+        "{sheet}"
+    );
+}
@@ -0,0 +1,211 @@
+//! The most memorable moments in a player's [`Journal`], for a highlight reel.
+//!
+//! Every entry gets a notability score from a [`Scoring`]: rare loot, beating a high level rival
+//! and long streaks of luck score higher. The weights are public so they can be tuned.
+
+use crate::{
+    journal::{Entry, Event, Journal},
+    mechanics::Player,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Scoring {
+    /// Per word of an item's name, since rarer items pick up more adjectives
+    pub loot: u32,
+    /// Extra for an "... of ..." item
+    pub special_loot: u32,
+    /// Per level of the rival involved
+    pub rival: u32,
+    /// Per hazard passed or showdown won in a row, from the second one on
+    pub streak: u32,
+    /// Per room of a cleared dungeon
+    pub dungeon_room: u32,
+    pub act: u32,
+}
+
+impl Default for Scoring {
+    fn default() -> Self {
+        Self {
+            loot: 2,
+            special_loot: 5,
+            rival: 2,
+            streak: 4,
+            dungeon_room: 2,
+            act: 20,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Highlight<'a> {
+    pub entry: &'a Entry,
+    pub score: u32,
+}
+
+impl Scoring {
+    pub fn loot(&self, item: &str) -> u32 {
+        let words = item.split_whitespace().count() as u32;
+        let special = if item.contains(" of ") {
+            self.special_loot
+        } else {
+            0
+        };
+        self.loot * words + special
+    }
+
+    /// Score every entry, oldest first. Streaks and rival levels carry over from earlier entries,
+    /// so these have to be scored in order
+    pub fn score<'a>(&self, entries: impl IntoIterator<Item = &'a Entry>) -> Vec<Highlight<'a>> {
+        let (mut hazards, mut showdowns, mut nemesis) = (0_u32, 0_u32, 1_u32);
+
+        entries
+            .into_iter()
+            .map(|entry| {
+                let score = match &entry.event {
+                    Event::RivalAppeared { .. } => self.rival,
+                    Event::RivalOvertook { level, .. } | Event::RivalFellBehind { level, .. } => {
+                        nemesis = *level as u32;
+                        self.rival * nemesis / 2
+                    }
+                    Event::RivalShowdown { won: true, .. } => {
+                        showdowns += 1;
+                        self.rival * nemesis + self.streak * (showdowns - 1)
+                    }
+                    Event::RivalShowdown { won: false, .. } => {
+                        showdowns = 0;
+                        self.rival
+                    }
+                    Event::Hazard { passed: true, .. } => {
+                        hazards += 1;
+                        self.streak * hazards.saturating_sub(1)
+                    }
+                    Event::Hazard { passed: false, .. } => {
+                        hazards = 0;
+                        0
+                    }
+                    Event::Crafted { item } | Event::TreasureFound { item } => self.loot(item),
+                    Event::DungeonCleared { rooms, loot, .. } => {
+                        self.dungeon_room * *rooms as u32
+                            + loot.iter().map(|item| self.loot(item)).max().unwrap_or(0)
+                    }
+                    Event::QuestCompleted { reward, bonus, .. } => reward
+                        .iter()
+                        .chain(bonus)
+                        .map(|reward| self.loot(reward))
+                        .max()
+                        .unwrap_or(0),
+                    Event::ActStarted { .. } => self.act,
+                    Event::ArenaFinished { rounds, trophy, .. } => {
+                        self.streak * *rounds as u32
+                            + trophy.as_deref().map_or(0, |trophy| self.loot(trophy))
+                    }
+                    Event::Ascended { ascension, .. } => self.act * 5 * *ascension as u32,
+                    Event::BountyClaimed { .. } => self.streak,
+                    Event::LastStand { .. }
+                    | Event::SlayerTitle { .. }
+                    | Event::Unlocked { .. } => self.act,
+                    Event::NemesisEscaped { .. } => self.rival,
+                    Event::NemesisSlain {
+                        encounters, loot, ..
+                    } => {
+                        self.rival * *encounters as u32
+                            + loot.iter().map(|item| self.loot(item)).max().unwrap_or(0)
+                    }
+                    Event::TreasureMapFound
+                    | Event::DungeonEntered { .. }
+                    | Event::ArenaEntered { .. }
+                    | Event::Recovered { .. }
+                    | Event::External { .. }
+                    | Event::Travelled { .. }
+                    | Event::SessionEnded { .. }
+                    | Event::LostTrackOfTime { .. } => 0,
+                };
+                Highlight { entry, score }
+            })
+            .collect()
+    }
+
+    /// The `count` most notable entries, best first
+    pub fn top<'a>(&self, journal: &'a Journal, count: usize) -> Vec<Highlight<'a>> {
+        best(self.score(journal.iter()), count)
+    }
+
+    /// The `count` most notable entries of each act in the journal, oldest act first
+    pub fn by_act<'a>(&self, player: &'a Player, count: usize) -> Vec<(i32, Vec<Highlight<'a>>)> {
+        let journal = &player.journal;
+
+        // old entries may have been dropped, so count back from the newest act
+        let started = journal
+            .iter()
+            .filter(|entry| matches!(entry.event, Event::ActStarted { .. }))
+            .count() as i32;
+        let mut act = (player.quest_book.act() - started).max(0);
+
+        let mut acts = vec![(act, vec![])];
+        for highlight in self.score(journal.iter()) {
+            if let Event::ActStarted { act: next, .. } = highlight.entry.event {
+                act = next;
+                acts.push((act, vec![]));
+            }
+            acts.last_mut().unwrap().1.push(highlight);
+        }
+
+        acts.into_iter()
+            .map(|(act, highlights)| (act, best(highlights, count)))
+            .filter(|(_, highlights)| !highlights.is_empty())
+            .collect()
+    }
+}
+
+fn best(mut highlights: Vec<Highlight<'_>>, count: usize) -> Vec<Highlight<'_>> {
+    highlights.retain(|highlight| highlight.score > 0);
+    // stable, so ties go to the older entry
+    highlights.sort_by_key(|highlight| std::cmp::Reverse(highlight.score));
+    highlights.truncate(count);
+    highlights
+}
+
+#[test]
+fn streaks_and_rarity() {
+    let entry = |event| Entry {
+        elapsed: 0.0,
+        event,
+    };
+    let hazard = |passed| {
+        entry(Event::Hazard {
+            name: String::from("Rockslide"),
+            passed,
+        })
+    };
+
+    let entries = [
+        hazard(true),
+        hazard(true),
+        hazard(true),
+        hazard(false),
+        hazard(true),
+        entry(Event::TreasureFound {
+            item: String::from("sword"),
+        }),
+        entry(Event::TreasureFound {
+            item: String::from("gleaming sword of the dawn"),
+        }),
+    ];
+
+    let scoring = Scoring::default();
+    let scores = scoring
+        .score(&entries)
+        .iter()
+        .map(|highlight| highlight.score)
+        .collect::<Vec<_>>();
+
+    let streak = scoring.streak;
+    assert_eq!(scores[..5], [0, streak, 2 * streak, 0, 0]);
+    assert!(scores[6] > scores[5]);
+
+    let best = best(scoring.score(&entries), 1);
+    assert!(matches!(
+        &best[0].entry.event,
+        Event::TreasureFound { item } if item.contains("dawn")
+    ));
+}
@@ -0,0 +1,181 @@
+//! A long term record of a player, which outlives the bounded [`Journal`](crate::journal::Journal).
+//!
+//! [`Recorder`] copies what happened into a [`HistoryStore`]: every journal entry, the quests that
+//! were finished and a periodic [`Sample`] of the player's progress. [`MemoryHistory`] keeps it all
+//! in memory, and with the `sqlite` feature [`SqliteHistory`] keeps it in a database file so
+//! years of play can be queried without loading it all.
+
+use std::{convert::Infallible, ops::Range};
+
+use crate::{
+    journal::{Entry, Event},
+    mechanics::Player,
+};
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "sqlite")]
+pub use sqlite::{Error as SqliteError, SqliteHistory};
+
+/// A point on the player's progress, for charting
+#[derive(Copy, Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Sample {
+    pub elapsed: f32,
+    pub level: usize,
+    pub gold: isize,
+    /// Progress through the current level, from 0.0 to 1.0
+    pub exp: f32,
+}
+
+impl Sample {
+    pub fn of(player: &Player) -> Self {
+        Self {
+            elapsed: player.elapsed,
+            level: player.level,
+            gold: player.inventory.gold(),
+            exp: player.exp_bar.pos / player.exp_bar.max,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ArchivedQuest {
+    pub elapsed: f32,
+    pub caption: String,
+    pub giver: Option<String>,
+    pub reward: Option<String>,
+}
+
+/// Somewhere to keep a player's history. Queries take a range of elapsed game time and return
+/// results oldest first
+pub trait HistoryStore {
+    type Error;
+
+    fn record(&mut self, entry: &Entry) -> Result<(), Self::Error>;
+    fn sample(&mut self, sample: Sample) -> Result<(), Self::Error>;
+    fn archive(&mut self, quest: ArchivedQuest) -> Result<(), Self::Error>;
+
+    fn entries(&self, range: Range<f32>) -> Result<Vec<Entry>, Self::Error>;
+    fn samples(&self, range: Range<f32>) -> Result<Vec<Sample>, Self::Error>;
+    fn quests(&self, range: Range<f32>) -> Result<Vec<ArchivedQuest>, Self::Error>;
+}
+
+#[derive(Default, Debug)]
+pub struct MemoryHistory {
+    entries: Vec<Entry>,
+    samples: Vec<Sample>,
+    quests: Vec<ArchivedQuest>,
+}
+
+impl MemoryHistory {
+    // everything is appended in order, so the range is a contiguous slice
+    fn within<T: Clone>(list: &[T], range: Range<f32>, elapsed: impl Fn(&T) -> f32) -> Vec<T> {
+        let start = list.partition_point(|item| elapsed(item) < range.start);
+        let end = list.partition_point(|item| elapsed(item) < range.end);
+        list[start..end.max(start)].to_vec()
+    }
+}
+
+impl HistoryStore for MemoryHistory {
+    type Error = Infallible;
+
+    fn record(&mut self, entry: &Entry) -> Result<(), Self::Error> {
+        self.entries.push(entry.clone());
+        Ok(())
+    }
+
+    fn sample(&mut self, sample: Sample) -> Result<(), Self::Error> {
+        self.samples.push(sample);
+        Ok(())
+    }
+
+    fn archive(&mut self, quest: ArchivedQuest) -> Result<(), Self::Error> {
+        self.quests.push(quest);
+        Ok(())
+    }
+
+    fn entries(&self, range: Range<f32>) -> Result<Vec<Entry>, Self::Error> {
+        Ok(Self::within(&self.entries, range, |entry| entry.elapsed))
+    }
+
+    fn samples(&self, range: Range<f32>) -> Result<Vec<Sample>, Self::Error> {
+        Ok(Self::within(&self.samples, range, |sample| sample.elapsed))
+    }
+
+    fn quests(&self, range: Range<f32>) -> Result<Vec<ArchivedQuest>, Self::Error> {
+        Ok(Self::within(&self.quests, range, |quest| quest.elapsed))
+    }
+}
+
+/// Copies new history from a player into a store, call [`Recorder::observe`] after advancing
+#[derive(Debug)]
+pub struct Recorder {
+    recorded: usize,
+    last_sample: Option<f32>,
+}
+
+impl Recorder {
+    /// Game time between samples
+    pub const SAMPLE_INTERVAL: f32 = 60.0;
+
+    /// Start recording from the player's current state, so old journal entries aren't copied again
+    pub fn new(player: &Player) -> Self {
+        Self {
+            recorded: player.journal.recorded(),
+            last_sample: None,
+        }
+    }
+
+    pub fn observe<S: HistoryStore>(
+        &mut self,
+        player: &Player,
+        store: &mut S,
+    ) -> Result<(), S::Error> {
+        for entry in player.journal.since(self.recorded) {
+            store.record(entry)?;
+            if let Event::QuestCompleted {
+                caption,
+                giver,
+                reward,
+                ..
+            } = &entry.event
+            {
+                store.archive(ArchivedQuest {
+                    elapsed: entry.elapsed,
+                    caption: caption.clone(),
+                    giver: giver.clone(),
+                    reward: reward.clone(),
+                })?;
+            }
+        }
+        self.recorded = player.journal.recorded();
+
+        if self
+            .last_sample
+            .is_none_or(|last| player.elapsed - last >= Self::SAMPLE_INTERVAL)
+        {
+            store.sample(Sample::of(player))?;
+            self.last_sample = Some(player.elapsed);
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn memory_history_range() {
+    let mut history = MemoryHistory::default();
+    for elapsed in [1.0, 2.0, 3.0, 4.0] {
+        let entry = Entry {
+            elapsed,
+            event: Event::TreasureMapFound,
+        };
+        history.record(&entry).unwrap();
+    }
+
+    let found = history.entries(2.0..4.0).unwrap();
+    assert_eq!(
+        found.iter().map(|entry| entry.elapsed).collect::<Vec<_>>(),
+        [2.0, 3.0]
+    );
+    assert!(history.entries(5.0..10.0).unwrap().is_empty());
+}
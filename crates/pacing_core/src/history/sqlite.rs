@@ -0,0 +1,178 @@
+use std::{ops::Range, path::Path};
+
+use rusqlite::{params, Connection};
+
+use super::{ArchivedQuest, HistoryStore, Sample};
+use crate::journal::Entry;
+
+#[derive(Debug)]
+pub enum Error {
+    Sqlite(rusqlite::Error),
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sqlite(err) => write!(f, "sqlite: {err}"),
+            Self::Json(err) => write!(f, "invalid event: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Self {
+        Self::Sqlite(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// A [`HistoryStore`] in an SQLite database. Events are stored as JSON next to their kind and
+/// text, so the database is also useful from other tools
+pub struct SqliteHistory {
+    conn: Connection,
+}
+
+impl SqliteHistory {
+    const SCHEMA: &'static str = "
+        CREATE TABLE IF NOT EXISTS entries (
+            elapsed REAL NOT NULL,
+            kind TEXT NOT NULL,
+            text TEXT NOT NULL,
+            event TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS entries_elapsed ON entries (elapsed);
+
+        CREATE TABLE IF NOT EXISTS samples (
+            elapsed REAL NOT NULL,
+            level INTEGER NOT NULL,
+            gold INTEGER NOT NULL,
+            exp REAL NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS samples_elapsed ON samples (elapsed);
+
+        CREATE TABLE IF NOT EXISTS quests (
+            elapsed REAL NOT NULL,
+            caption TEXT NOT NULL,
+            giver TEXT,
+            reward TEXT
+        );
+        CREATE INDEX IF NOT EXISTS quests_elapsed ON quests (elapsed);
+    ";
+
+    /// Open (or create) the database at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Error> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, Error> {
+        conn.execute_batch(Self::SCHEMA)?;
+        Ok(Self { conn })
+    }
+}
+
+impl HistoryStore for SqliteHistory {
+    type Error = Error;
+
+    fn record(&mut self, entry: &Entry) -> Result<(), Self::Error> {
+        self.conn.execute(
+            "INSERT INTO entries (elapsed, kind, text, event) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                entry.elapsed,
+                entry.event.kind(),
+                entry.event.to_string(),
+                serde_json::to_string(&entry.event)?
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn sample(&mut self, sample: Sample) -> Result<(), Self::Error> {
+        self.conn.execute(
+            "INSERT INTO samples (elapsed, level, gold, exp) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                sample.elapsed,
+                sample.level as i64,
+                sample.gold as i64,
+                sample.exp
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn archive(&mut self, quest: ArchivedQuest) -> Result<(), Self::Error> {
+        self.conn.execute(
+            "INSERT INTO quests (elapsed, caption, giver, reward) VALUES (?1, ?2, ?3, ?4)",
+            params![quest.elapsed, quest.caption, quest.giver, quest.reward],
+        )?;
+        Ok(())
+    }
+
+    fn entries(&self, range: Range<f32>) -> Result<Vec<Entry>, Self::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT elapsed, event FROM entries
+             WHERE elapsed >= ?1 AND elapsed < ?2 ORDER BY elapsed, rowid",
+        )?;
+        let rows = stmt
+            .query_map(params![range.start, range.end], |row| {
+                Ok((row.get::<_, f32>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        rows.into_iter()
+            .map(|(elapsed, event)| {
+                Ok(Entry {
+                    elapsed,
+                    event: serde_json::from_str(&event)?,
+                })
+            })
+            .collect()
+    }
+
+    fn samples(&self, range: Range<f32>) -> Result<Vec<Sample>, Self::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT elapsed, level, gold, exp FROM samples
+             WHERE elapsed >= ?1 AND elapsed < ?2 ORDER BY elapsed, rowid",
+        )?;
+        let samples = stmt
+            .query_map(params![range.start, range.end], |row| {
+                Ok(Sample {
+                    elapsed: row.get(0)?,
+                    level: row.get::<_, i64>(1)? as _,
+                    gold: row.get::<_, i64>(2)? as _,
+                    exp: row.get(3)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(samples)
+    }
+
+    fn quests(&self, range: Range<f32>) -> Result<Vec<ArchivedQuest>, Self::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT elapsed, caption, giver, reward FROM quests
+             WHERE elapsed >= ?1 AND elapsed < ?2 ORDER BY elapsed, rowid",
+        )?;
+        let quests = stmt
+            .query_map(params![range.start, range.end], |row| {
+                Ok(ArchivedQuest {
+                    elapsed: row.get(0)?,
+                    caption: row.get(1)?,
+                    giver: row.get(2)?,
+                    reward: row.get(3)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(quests)
+    }
+}
@@ -0,0 +1,123 @@
+//! Seals for saves, so edited characters can be told apart from ones that were played.
+//!
+//! A seal is a trailing comment line with a SHA-256 checksum of the save, or an HMAC of it when
+//! a local key is used. Formats without comments, like JSON, keep the [`signature`] beside the
+//! save instead. Saves from before seals existed are treated as untouched.
+//!
+//! Sealing needs the `seal` feature, only the verdict is available without it.
+
+#[cfg(feature = "seal")]
+use hmac::{Hmac, Mac};
+#[cfg(feature = "seal")]
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "seal")]
+const PREFIX: &str = "// seal: ";
+
+/// Shown for players whose saves failed verification
+pub const TAMPERED: &str = "Touched by dark forces";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Verification {
+    Valid,
+    /// The save has no seal
+    Unsealed,
+    /// The save was sealed with a key, but no key was given to check it with
+    Unverifiable,
+    Invalid,
+}
+
+impl Verification {
+    /// Whether the save was changed after it was sealed
+    pub const fn is_tampered(self) -> bool {
+        matches!(self, Self::Invalid)
+    }
+}
+
+/// What `data` is sealed with, e.g. `sha256 <digest>`. It's an HMAC if there's a `key`
+#[cfg(feature = "seal")]
+pub fn signature(data: &str, key: Option<&[u8]>) -> String {
+    let data = data.trim_end_matches('\n');
+    match key {
+        Some(key) => format!("hmac-sha256 {}", hex(&hmac(key, data))),
+        None => format!("sha256 {}", hex(&Sha256::digest(data.as_bytes()))),
+    }
+}
+
+/// Check `data` against a [`signature`] that was kept apart from it
+#[cfg(feature = "seal")]
+pub fn check(data: &str, signature: &str, key: Option<&[u8]>) -> Verification {
+    let data = data.trim_end_matches('\n');
+    let expected = match (signature.trim().split_once(' '), key) {
+        (Some(("sha256", digest)), _) => Some((hex(&Sha256::digest(data.as_bytes())), digest)),
+        (Some(("hmac-sha256", digest)), Some(key)) => Some((hex(&hmac(key, data)), digest)),
+        (Some(("hmac-sha256", _)), None) => return Verification::Unverifiable,
+        _ => None,
+    };
+
+    match expected {
+        Some((expected, digest)) if expected == digest => Verification::Valid,
+        _ => Verification::Invalid,
+    }
+}
+
+/// Append a seal to `data`, using an HMAC if there's a `key`
+#[cfg(feature = "seal")]
+pub fn seal(data: &str, key: Option<&[u8]>) -> String {
+    let data = data.trim_end_matches('\n');
+    format!("{data}\n{PREFIX}{}\n", signature(data, key))
+}
+
+/// Check the seal on `data`, returning the data without the seal
+#[cfg(feature = "seal")]
+pub fn verify<'a>(data: &'a str, key: Option<&[u8]>) -> (&'a str, Verification) {
+    let trimmed = data.trim_end_matches('\n');
+    match trimmed
+        .rsplit_once('\n')
+        .and_then(|(body, line)| Some((body, line.strip_prefix(PREFIX)?)))
+    {
+        Some((body, seal)) => (body, check(body, seal, key)),
+        None => (data, Verification::Unsealed),
+    }
+}
+
+#[cfg(feature = "seal")]
+fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac accepts any key length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(feature = "seal")]
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(feature = "seal")]
+#[test]
+fn seal_round_trip() {
+    let data = "(name: \"Gorbash\", level: 12)";
+    for key in [None, Some(&b"secret"[..])] {
+        let sealed = seal(data, key);
+        assert_eq!(verify(&sealed, key), (data, Verification::Valid));
+
+        let edited = sealed.replace("12", "99");
+        assert_eq!(verify(&edited, key).1, Verification::Invalid);
+    }
+
+    assert_eq!(verify(data, None), (data, Verification::Unsealed));
+    assert_eq!(
+        verify(&seal(data, Some(b"secret")), None).1,
+        Verification::Unverifiable
+    );
+
+    let signature = signature(data, Some(b"secret"));
+    assert_eq!(
+        check(data, &signature, Some(b"secret")),
+        Verification::Valid
+    );
+    assert_eq!(
+        check(data, &signature, Some(b"other")),
+        Verification::Invalid
+    );
+}
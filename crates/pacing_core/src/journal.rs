@@ -0,0 +1,376 @@
+use std::collections::VecDeque;
+
+use crate::{
+    format::{Roman, Time},
+    lingo::{act_name, indefinite},
+};
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Event {
+    RivalAppeared {
+        name: String,
+        class: String,
+    },
+    RivalOvertook {
+        name: String,
+        level: usize,
+    },
+    RivalFellBehind {
+        name: String,
+        level: usize,
+    },
+    RivalShowdown {
+        name: String,
+        won: bool,
+    },
+    Crafted {
+        item: String,
+    },
+    TreasureMapFound,
+    TreasureFound {
+        item: String,
+    },
+    DungeonEntered {
+        name: String,
+    },
+    Hazard {
+        name: String,
+        passed: bool,
+    },
+    DungeonCleared {
+        name: String,
+        rooms: usize,
+        loot: Vec<String>,
+    },
+    ActStarted {
+        act: i32,
+        title: String,
+    },
+    ArenaEntered {
+        name: String,
+    },
+    /// A gauntlet was won, with a trophy, or ran out of time after `rounds`
+    ArenaFinished {
+        name: String,
+        rounds: usize,
+        score: usize,
+        trophy: Option<String>,
+    },
+    QuestCompleted {
+        caption: String,
+        giver: Option<String>,
+        #[serde(default)]
+        reward: Option<String>,
+        /// The extra rewards for a longer quest
+        #[serde(default)]
+        bonus: Vec<String>,
+        #[serde(default)]
+        gold: usize,
+    },
+    /// The simulation got stuck and was nudged back into motion
+    Recovered {
+        ticks: usize,
+        state: String,
+    },
+    /// Finished an objective from the quest board
+    BountyClaimed {
+        objective: String,
+        reward: isize,
+    },
+    /// Pushed too far by a `cause` and left for dead, but recovered
+    LastStand {
+        cause: String,
+    },
+    /// A nemesis from a cinematic was beaten, but got away to come back in a later act
+    NemesisEscaped {
+        name: String,
+        encounters: usize,
+    },
+    /// A nemesis was beaten for good, after `encounters` fights
+    NemesisSlain {
+        name: String,
+        encounters: usize,
+        loot: Vec<String>,
+    },
+    /// Killed enough of a monster family to earn a title, see [`Bestiary`](crate::mechanics::Bestiary)
+    SlayerTitle {
+        family: String,
+        title: String,
+    },
+    /// Went out to a new region of the [`WorldMap`](crate::world::WorldMap)
+    Travelled {
+        region: String,
+    },
+    /// A task injected from outside the game, see [`ExternalEvent`](crate::mechanics::ExternalEvent)
+    External {
+        source: String,
+        description: String,
+    },
+    /// Finished the final act and started over
+    Ascended {
+        ascension: usize,
+        level: usize,
+    },
+    /// A play session ended, `played` is in real seconds
+    SessionEnded {
+        played: u64,
+        levels: usize,
+        gold: isize,
+    },
+    /// Earned an achievement or a title from the [codex](crate::codex)
+    Unlocked {
+        name: String,
+        description: String,
+    },
+    /// The computer slept for `away` real seconds in the middle of a session, which was caught up
+    /// on all at once
+    LostTrackOfTime {
+        away: u64,
+    },
+}
+
+impl Event {
+    /// A short name for the kind of event, for exporting
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::RivalAppeared { .. } => "rival_appeared",
+            Self::RivalOvertook { .. } => "rival_overtook",
+            Self::RivalFellBehind { .. } => "rival_fell_behind",
+            Self::RivalShowdown { .. } => "rival_showdown",
+            Self::Crafted { .. } => "crafted",
+            Self::TreasureMapFound => "treasure_map_found",
+            Self::TreasureFound { .. } => "treasure_found",
+            Self::DungeonEntered { .. } => "dungeon_entered",
+            Self::Hazard { .. } => "hazard",
+            Self::DungeonCleared { .. } => "dungeon_cleared",
+            Self::ActStarted { .. } => "act_started",
+            Self::ArenaEntered { .. } => "arena_entered",
+            Self::ArenaFinished { .. } => "arena_finished",
+            Self::QuestCompleted { .. } => "quest_completed",
+            Self::Recovered { .. } => "recovered",
+            Self::BountyClaimed { .. } => "bounty_claimed",
+            Self::LastStand { .. } => "last_stand",
+            Self::NemesisEscaped { .. } => "nemesis_escaped",
+            Self::NemesisSlain { .. } => "nemesis_slain",
+            Self::SlayerTitle { .. } => "slayer_title",
+            Self::Travelled { .. } => "travelled",
+            Self::External { .. } => "external",
+            Self::Ascended { .. } => "ascended",
+            Self::SessionEnded { .. } => "session_ended",
+            Self::Unlocked { .. } => "unlocked",
+            Self::LostTrackOfTime { .. } => "lost_track_of_time",
+        }
+    }
+}
+
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RivalAppeared { name, class } => {
+                write!(f, "{name} the {class} has sworn to outdo you")
+            }
+            Self::RivalOvertook { name, level } => {
+                write!(
+                    f,
+                    "Word arrives that {name} has overtaken you at level {level}"
+                )
+            }
+            Self::RivalFellBehind { name, level } => {
+                write!(f, "{name} has fallen behind you, still at level {level}")
+            }
+            Self::RivalShowdown { name, won: true } => {
+                write!(f, "You bested {name} in a showdown")
+            }
+            Self::RivalShowdown { name, won: false } => {
+                write!(f, "{name} bested you in a showdown")
+            }
+            Self::Crafted { item } => write!(f, "Crafted {}", indefinite(item, 1)),
+            Self::TreasureMapFound => f.write_str("Found a tattered treasure map"),
+            Self::TreasureFound { item } => {
+                write!(
+                    f,
+                    "Dug up {} at the end of a treasure map",
+                    indefinite(item, 1)
+                )
+            }
+            Self::DungeonEntered { name } => write!(f, "Descended into {name}"),
+            Self::Hazard { name, passed: true } => {
+                write!(f, "Overcame {}", indefinite(name, 1))
+            }
+            Self::Hazard {
+                name,
+                passed: false,
+            } => write!(f, "Fell afoul of {}", indefinite(name, 1)),
+            Self::DungeonCleared { name, rooms, loot } => {
+                write!(f, "Cleared {name}: {rooms} rooms")?;
+                if !loot.is_empty() {
+                    write!(f, ", {}", loot.join(", "))?;
+                }
+                Ok(())
+            }
+            Self::ActStarted { act, title } => write!(f, "{} — {title}", act_name(*act)),
+            Self::ArenaEntered { name } => write!(f, "Entered the gauntlet of {name}"),
+            Self::ArenaFinished {
+                name,
+                rounds,
+                score,
+                trophy: Some(trophy),
+            } => write!(
+                f,
+                "Won {}, beating {name} in {rounds} rounds for {score} points",
+                indefinite(trophy, 1)
+            ),
+            Self::ArenaFinished {
+                name,
+                rounds,
+                score,
+                trophy: None,
+            } => write!(
+                f,
+                "Ran out of time in {name} after {}, for {score} points",
+                indefinite("round", *rounds)
+            ),
+            Self::QuestCompleted {
+                caption,
+                giver,
+                bonus,
+                gold,
+                ..
+            } => {
+                write!(f, "Completed \"{caption}\"")?;
+                if let Some(giver) = giver {
+                    write!(f, " for {giver}")?;
+                }
+                let gold = (*gold > 0).then(|| format!("{gold} gold"));
+                let extras = bonus.iter().cloned().chain(gold).collect::<Vec<_>>();
+                match extras.split_last() {
+                    Some((last, [])) => write!(f, ", with a bonus of {last}"),
+                    Some((last, rest)) => {
+                        write!(f, ", with a bonus of {} and {last}", rest.join(", "))
+                    }
+                    None => Ok(()),
+                }
+            }
+            Self::Recovered { ticks, state } => {
+                write!(f, "Shook off a {ticks} tick daze ({state})")
+            }
+            Self::BountyClaimed { objective, reward } => {
+                write!(
+                    f,
+                    "Claimed a bounty for \"{objective}\", earning {reward} gold"
+                )
+            }
+            Self::LastStand { cause } => write!(
+                f,
+                "Was left for dead after {}, but lived to tell of it",
+                indefinite(cause, 1)
+            ),
+            Self::NemesisEscaped { name, .. } => {
+                write!(f, "Beat back {name}, who escaped swearing revenge")
+            }
+            Self::NemesisSlain {
+                name,
+                encounters,
+                loot,
+            } => {
+                write!(f, "Slew {name} at last, after {encounters} fights")?;
+                if !loot.is_empty() {
+                    write!(f, ", {}", loot.join(", "))?;
+                }
+                Ok(())
+            }
+            Self::SlayerTitle { title, .. } => write!(f, "Earned the title {title}"),
+            Self::Travelled { region } => write!(f, "Set out for {region}"),
+            Self::External { description, .. } => f.write_str(description),
+            Self::Ascended { ascension, level } => write!(
+                f,
+                "Ascended at level {level} to begin anew, ascension {}",
+                Roman::from_i32(*ascension as i32)
+            ),
+            Self::SessionEnded {
+                played,
+                levels,
+                gold,
+            } => write!(
+                f,
+                "Rested after {}, having gained {} and {gold:+} gold",
+                Time::compact(*played as f32),
+                indefinite("level", *levels)
+            ),
+            Self::Unlocked { name, description } => write!(f, "Earned {name}: {description}"),
+            Self::LostTrackOfTime { away } => write!(
+                f,
+                "You lose track of time… {} pass in a blur",
+                Time::compact(*away as f32)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Entry {
+    pub elapsed: f32,
+    pub event: Event,
+}
+
+#[derive(Clone, Default, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Journal {
+    entries: VecDeque<Entry>,
+    #[serde(default)]
+    recorded: usize,
+}
+
+impl Journal {
+    const MAX_ENTRIES: usize = 1000;
+
+    pub fn record(&mut self, elapsed: f32, event: Event) {
+        while self.entries.len() >= Self::MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Entry { elapsed, event });
+        self.recorded += 1;
+    }
+
+    /// The total number of entries ever recorded, including ones that have been dropped
+    pub const fn recorded(&self) -> usize {
+        self.recorded
+    }
+
+    /// Entries recorded after the first `recorded` entries, if they're still retained
+    pub fn since(&self, recorded: usize) -> impl Iterator<Item = &Entry> {
+        let new = self
+            .recorded
+            .saturating_sub(recorded)
+            .min(self.entries.len());
+        self.entries.iter().skip(self.entries.len() - new)
+    }
+
+    /// Drop all but the newest `keep` entries
+    pub fn truncate(&mut self, keep: usize) {
+        let drop = self.entries.len().saturating_sub(keep);
+        self.entries.drain(..drop);
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &Entry> {
+        self.entries.iter()
+    }
+
+    /// Just the arena gauntlets, oldest first
+    pub fn arena(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().filter(|entry| {
+            matches!(
+                entry.event,
+                Event::ArenaEntered { .. } | Event::ArenaFinished { .. }
+            )
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
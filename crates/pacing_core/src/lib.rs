@@ -1,7 +1,36 @@
+pub mod badge;
+pub mod balance;
+pub mod board;
+pub mod card;
+pub mod checkpoint;
+pub mod codex;
+pub mod combat;
+pub mod compress;
 pub mod config;
+#[cfg(feature = "embed")]
+pub mod embed;
+pub mod event_log;
+#[cfg(feature = "export")]
+pub mod export;
 pub mod format;
+pub mod highlights;
+pub mod history;
+pub mod integrity;
+pub mod journal;
 pub mod lingo;
 pub mod mechanics;
+pub mod portrait;
+pub mod profile;
+#[cfg(feature = "hot-reload")]
+pub mod reload;
+pub mod rules;
+pub mod runner;
+#[cfg(feature = "save")]
+pub mod save;
+pub mod subscription;
+#[cfg(test)]
+mod testing;
+pub mod world;
 
 mod rand;
-pub use rand::{Rand, SliceExt};
+pub use rand::{Rand, Recent, SliceExt};
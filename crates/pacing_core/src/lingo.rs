@@ -1,22 +1,268 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        RwLock,
+    },
+};
 
 use heck::ToTitleCase as _;
 
+mod grammar;
+pub use grammar::{English, French, German, Grammar};
+
 use crate::{
+    config::{self, Language, NameStyle},
     format::Roman,
     rand::{Rand, SliceExt},
 };
 
+pub trait NameGenerator: Send + Sync {
+    fn generate(&self, rng: &Rand) -> String;
+}
+
+/// The classic generator, alternating consonant and vowel fragments
+pub struct Phonemes {
+    max_fragments: usize,
+}
+
+impl Phonemes {
+    pub const fn new(max_fragments: usize) -> Self {
+        Self { max_fragments }
+    }
+}
+
+impl Default for Phonemes {
+    fn default() -> Self {
+        Self::new(6)
+    }
+}
+
+impl NameGenerator for Phonemes {
+    fn generate(&self, rng: &Rand) -> String {
+        #[rustfmt::skip]
+        const PARTS: [&[&str]; 3] = [
+            ["br", "cr", "dr", "fr", "gr", "j", "kr", "l", "m", "n", "pr", " ", " ", " ", "r", "sh", "tr", "v", "wh", "x", "y", "z"].as_slice(),
+            ["a", "a", "e", "e", "i", "i", "o", "o", "u", "u", "ae", "ie", "oo", "ou"].as_slice(),
+            ["b", "ck", "d", "g", "k", "m", "n", "p", "t", "v", "x", "z"].as_slice(),
+        ];
+        (0..self.max_fragments)
+            .fold(String::new(), |a, i| a + PARTS[i % 3].choice(rng))
+            .to_title_case()
+    }
+}
+
+/// Glues together whole syllables from a list
+pub struct Syllables {
+    syllables: Vec<Cow<'static, str>>,
+    min: usize,
+    max: usize,
+}
+
+impl Syllables {
+    pub fn new(
+        syllables: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+        min: usize,
+        max: usize,
+    ) -> Self {
+        Self {
+            syllables: syllables.into_iter().map(Into::into).collect(),
+            min: min.max(1),
+            max: max.max(min.max(1)),
+        }
+    }
+}
+
+impl NameGenerator for Syllables {
+    fn generate(&self, rng: &Rand) -> String {
+        if self.syllables.is_empty() {
+            return String::new();
+        }
+
+        let count = self.min + rng.below(self.max - self.min + 1);
+        (0..count)
+            .fold(String::new(), |a, _| a + self.syllables.choice(rng))
+            .to_title_case()
+    }
+}
+
+/// A character-level markov chain trained on a corpus of names
+pub struct Markov {
+    order: usize,
+    max_len: usize,
+    starts: Vec<Vec<char>>,
+    transitions: HashMap<Vec<char>, Vec<char>>,
+}
+
+impl Markov {
+    const END: char = '\0';
+
+    pub fn train(corpus: impl IntoIterator<Item = impl AsRef<str>>, order: usize) -> Self {
+        let order = order.max(1);
+        let mut starts = vec![];
+        let mut transitions = HashMap::<_, Vec<_>>::new();
+        let mut max_len = 0;
+
+        for word in corpus {
+            let chars = word.as_ref().to_lowercase().chars().collect::<Vec<_>>();
+            if chars.len() <= order {
+                continue;
+            }
+
+            max_len = max_len.max(chars.len());
+            starts.push(chars[..order].to_vec());
+            for (i, window) in chars.windows(order).enumerate() {
+                let next = chars.get(i + order).copied().unwrap_or(Self::END);
+                transitions.entry(window.to_vec()).or_default().push(next);
+            }
+        }
+
+        Self {
+            order,
+            max_len,
+            starts,
+            transitions,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+}
+
+impl NameGenerator for Markov {
+    fn generate(&self, rng: &Rand) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut out = self.starts.choice(rng).clone();
+        while out.len() < self.max_len {
+            let next = match self.transitions.get(&out[out.len() - self.order..]) {
+                Some(next) => *next.choice(rng),
+                None => break,
+            };
+            if next == Self::END {
+                break;
+            }
+            out.push(next);
+        }
+
+        out.into_iter().collect::<String>().to_title_case()
+    }
+}
+
+static NAME_GENERATOR: RwLock<Option<Box<dyn NameGenerator>>> = RwLock::new(None);
+
+/// Replace the generator used by [`generate_name`] everywhere
+pub fn set_name_generator(generator: impl NameGenerator + 'static) {
+    *NAME_GENERATOR.write().unwrap() = Some(Box::new(generator));
+}
+
+pub fn set_name_style(style: NameStyle) {
+    match style {
+        NameStyle::Phonemes => set_name_generator(Phonemes::default()),
+        NameStyle::Syllables => {
+            set_name_generator(Syllables::new(config::NAME_SYLLABLES.iter().copied(), 2, 3))
+        }
+        NameStyle::Markov => set_name_generator(Markov::train(config::NAME_CORPUS, 2)),
+    }
+}
+
+static FILTER_NAMES: AtomicBool = AtomicBool::new(true);
+static DENY_LIST: RwLock<Option<Vec<String>>> = RwLock::new(None);
+
+/// Toggle rejecting names with a word from the deny list in them. This is on by default
+pub fn set_name_filter(enabled: bool) {
+    FILTER_NAMES.store(enabled, Ordering::Relaxed)
+}
+
+pub fn name_filter() -> bool {
+    FILTER_NAMES.load(Ordering::Relaxed)
+}
+
+/// Replace the deny list, which defaults to [`config::NAME_DENY_LIST`]
+pub fn set_deny_list(words: impl IntoIterator<Item = impl AsRef<str>>) {
+    let words = words
+        .into_iter()
+        .map(|word| word.as_ref().trim().to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect();
+    *DENY_LIST.write().unwrap() = Some(words);
+}
+
+/// The words in a name, lowercased. Anything that isn't a letter or digit separates them
+fn words(name: &str) -> impl Iterator<Item = String> + '_ {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Whether the name passes the deny list, if filtering is enabled. Only whole words are matched,
+/// so a name like Dickens is still allowed
+pub fn is_allowed_name(name: &str) -> bool {
+    if !name_filter() {
+        return true;
+    }
+
+    let denied = DENY_LIST.read().unwrap();
+    let mut words = words(name);
+    match &*denied {
+        Some(denied) => !words.any(|word| denied.contains(&word)),
+        None => !words.any(|word| config::NAME_DENY_LIST.contains(&&*word)),
+    }
+}
+
+/// Generate a name with `generator`, retrying until it passes the deny list
+pub fn generate_allowed(generator: &dyn NameGenerator, rng: &Rand) -> String {
+    const MAX_ATTEMPTS: usize = 100;
+
+    for _ in 0..MAX_ATTEMPTS {
+        let name = generator.generate(rng);
+        if is_allowed_name(&name) {
+            return name;
+        }
+    }
+
+    // a deny list that rejects everything shouldn't hang the game
+    String::from("Nameless")
+}
+
 pub fn generate_name(max_fragments: impl Into<Option<usize>>, rng: &Rand) -> String {
-    #[rustfmt::skip]
-    const PARTS: [&[&str]; 3] = [
-        ["br", "cr", "dr", "fr", "gr", "j", "kr", "l", "m", "n", "pr", " ", " ", " ", "r", "sh", "tr", "v", "wh", "x", "y", "z"].as_slice(),
-        ["a", "a", "e", "e", "i", "i", "o", "o", "u", "u", "ae", "ie", "oo", "ou"].as_slice(),
-        ["b", "ck", "d", "g", "k", "m", "n", "p", "t", "v", "x", "z"].as_slice(),
-    ];
-    (0..max_fragments.into().unwrap_or(6))
-        .fold(String::new(), |a, i| a + PARTS[i % 3].choice(rng))
-        .to_title_case()
+    if let Some(max_fragments) = max_fragments.into() {
+        return generate_allowed(&Phonemes::new(max_fragments), rng);
+    }
+
+    match &*NAME_GENERATOR.read().unwrap() {
+        Some(generator) => generate_allowed(&**generator, rng),
+        None => generate_allowed(&Phonemes::default(), rng),
+    }
+}
+
+/// A player can pick at most this many themes, each at most [`MAX_THEME_LENGTH`] characters
+pub const MAX_THEMES: usize = 5;
+pub const MAX_THEME_LENGTH: usize = 32;
+
+/// The themes in a comma separated list like "cheese, volcanoes", skipping any on the deny list
+pub fn themes(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(|theme| {
+            theme
+                .trim()
+                .chars()
+                .take(MAX_THEME_LENGTH)
+                .collect::<String>()
+        })
+        .filter(|theme| !theme.is_empty() && is_allowed_name(theme))
+        .take(MAX_THEMES)
+        .collect()
+}
+
+/// Fill the `{theme}` slot in a template
+pub fn weave(template: &str, theme: &str) -> String {
+    template.replace("{theme}", theme)
 }
 
 pub fn act_name(act: i32) -> String {
@@ -27,38 +273,39 @@ pub fn act_name(act: i32) -> String {
     format!("Act {}", Roman::from_i32(act))
 }
 
-pub fn plural(subject: &str) -> String {
-    match () {
-        _ if subject.ends_with('y') => format!("{}ies", &subject[..subject.len() - 1]),
-        _ if subject.ends_with("us") => format!("{}i", &subject[..subject.len() - 2]),
-        _ if subject.ends_with(['x', 's']) | subject.ends_with("ch") | subject.ends_with("sh") => {
-            format!("{subject}es")
-        }
-        _ if subject.ends_with('f') => format!("{}ves", &subject[..subject.len() - 1]),
-        _ if subject.ends_with("man") | subject.ends_with("Man") => {
-            format!("{}en", &subject[..subject.len() - 2])
-        }
-        _ => format!("{subject}s"),
+static GRAMMAR: RwLock<Option<Box<dyn Grammar>>> = RwLock::new(None);
+
+/// Replace the grammar used by [`plural`], [`indefinite`] and [`definite`] everywhere
+pub fn set_grammar(grammar: impl Grammar + 'static) {
+    *GRAMMAR.write().unwrap() = Some(Box::new(grammar));
+}
+
+/// Use the grammar for `language`, this should match the language of the content
+pub fn set_language(language: Language) {
+    match language {
+        Language::English => set_grammar(English),
+        Language::German => set_grammar(German),
+        Language::French => set_grammar(French),
     }
 }
 
-pub fn indefinite(subject: &str, quantity: usize) -> String {
-    match quantity {
-        1 if subject.starts_with(['A', 'E', 'I', 'O', 'U', 'a', 'e', 'i', 'o', 'u']) => {
-            format!("an {subject}")
-        }
-        1 => format!("a {subject}"),
-        _ => format!("{quantity} {subject}", subject = plural(subject)),
+fn with_grammar<T>(f: impl FnOnce(&dyn Grammar) -> T) -> T {
+    match &*GRAMMAR.read().unwrap() {
+        Some(grammar) => f(&**grammar),
+        None => f(&English),
     }
 }
 
+pub fn plural(subject: &str) -> String {
+    with_grammar(|grammar| grammar.plural(subject))
+}
+
+pub fn indefinite(subject: &str, quantity: usize) -> String {
+    with_grammar(|grammar| grammar.indefinite(subject, quantity))
+}
+
 pub fn definite(subject: &str, quantity: usize) -> String {
-    let subject = if quantity > 1 {
-        Cow::from(plural(subject))
-    } else {
-        Cow::from(subject)
-    };
-    format!("the {subject}",)
+    with_grammar(|grammar| grammar.definite(subject, quantity))
 }
 
 pub fn prefix<'a, 's>(
@@ -107,3 +354,24 @@ pub fn terminate_message(player_name: &str, rng: &Rand) -> String {
     let adjective = ["faithful", "noble", "loyal", "brave"].choice(rng);
     format!("Terminate {adjective} {player_name}?")
 }
+
+#[test]
+fn themes_from_input() {
+    assert_eq!(themes(" cheese,volcanoes ,, "), ["cheese", "volcanoes"]);
+    assert_eq!(themes("a, b, c, d, e, f").len(), MAX_THEMES);
+    assert_eq!(
+        weave("Recover the stolen {theme}", "cheese"),
+        "Recover the stolen cheese"
+    );
+}
+
+#[test]
+fn deny_list() {
+    assert!(is_allowed_name("Bramble"));
+    assert!(!is_allowed_name("Vo Dick"));
+    assert!(!is_allowed_name("Shit-Eater"));
+    // only whole words count
+    assert!(is_allowed_name("Dickens"));
+    assert!(is_allowed_name("Nigel Scunthorpe"));
+    assert!(is_allowed_name("Fa Gu"));
+}
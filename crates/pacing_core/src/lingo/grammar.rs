@@ -0,0 +1,215 @@
+//! Plurals, articles and interface text for the languages content can be written in
+
+/// How nouns are counted and introduced in a language
+pub trait Grammar: Send + Sync {
+    fn plural(&self, subject: &str) -> String;
+    /// "a sword", or "3 swords"
+    fn indefinite(&self, subject: &str, quantity: usize) -> String;
+    /// "the sword", or "the swords"
+    fn definite(&self, subject: &str, quantity: usize) -> String;
+}
+
+fn starts_with_vowel(subject: &str) -> bool {
+    subject.starts_with(['A', 'E', 'I', 'O', 'U', 'a', 'e', 'i', 'o', 'u'])
+}
+
+fn strip_suffix<'a>(subject: &'a str, suffix: &str) -> &'a str {
+    &subject[..subject.len() - suffix.len()]
+}
+
+pub struct English;
+
+impl Grammar for English {
+    fn plural(&self, subject: &str) -> String {
+        match () {
+            _ if subject.ends_with('y') => format!("{}ies", strip_suffix(subject, "y")),
+            _ if subject.ends_with("us") => format!("{}i", strip_suffix(subject, "us")),
+            _ if subject.ends_with(['x', 's'])
+                | subject.ends_with("ch")
+                | subject.ends_with("sh") =>
+            {
+                format!("{subject}es")
+            }
+            _ if subject.ends_with('f') => format!("{}ves", strip_suffix(subject, "f")),
+            _ if subject.ends_with("man") | subject.ends_with("Man") => {
+                format!("{}en", strip_suffix(subject, "an"))
+            }
+            _ => format!("{subject}s"),
+        }
+    }
+
+    fn indefinite(&self, subject: &str, quantity: usize) -> String {
+        match quantity {
+            1 if starts_with_vowel(subject) => format!("an {subject}"),
+            1 => format!("a {subject}"),
+            _ => format!("{quantity} {}", self.plural(subject)),
+        }
+    }
+
+    fn definite(&self, subject: &str, quantity: usize) -> String {
+        match quantity {
+            0 | 1 => format!("the {subject}"),
+            _ => format!("the {}", self.plural(subject)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Gender {
+    Masculine,
+    Feminine,
+    Neuter,
+}
+
+/// German, guessing the gender of a noun from its ending
+pub struct German;
+
+impl German {
+    // the last word of a compound noun decides its gender
+    fn gender(subject: &str) -> Gender {
+        let last = subject.rsplit(' ').next().unwrap_or(subject);
+        match () {
+            _ if ["chen", "lein", "um", "ment", "tum"]
+                .iter()
+                .any(|suffix| last.ends_with(suffix)) =>
+            {
+                Gender::Neuter
+            }
+            _ if ["ung", "heit", "keit", "schaft", "ion", "tät", "ei", "e"]
+                .iter()
+                .any(|suffix| last.ends_with(suffix)) =>
+            {
+                Gender::Feminine
+            }
+            _ => Gender::Masculine,
+        }
+    }
+}
+
+impl Grammar for German {
+    fn plural(&self, subject: &str) -> String {
+        match () {
+            _ if ["chen", "lein", "el", "er", "en"]
+                .iter()
+                .any(|suffix| subject.ends_with(suffix)) =>
+            {
+                subject.to_string()
+            }
+            _ if subject.ends_with('e') => format!("{subject}n"),
+            _ if ["ung", "heit", "keit", "schaft", "ion"]
+                .iter()
+                .any(|suffix| subject.ends_with(suffix)) =>
+            {
+                format!("{subject}en")
+            }
+            _ if subject.ends_with(['a', 'i', 'o', 'u', 'y']) => format!("{subject}s"),
+            _ => format!("{subject}e"),
+        }
+    }
+
+    fn indefinite(&self, subject: &str, quantity: usize) -> String {
+        match (quantity, Self::gender(subject)) {
+            (1, Gender::Feminine) => format!("eine {subject}"),
+            (1, _) => format!("ein {subject}"),
+            _ => format!("{quantity} {}", self.plural(subject)),
+        }
+    }
+
+    fn definite(&self, subject: &str, quantity: usize) -> String {
+        match (quantity, Self::gender(subject)) {
+            (0 | 1, Gender::Masculine) => format!("der {subject}"),
+            (0 | 1, Gender::Feminine) => format!("die {subject}"),
+            (0 | 1, Gender::Neuter) => format!("das {subject}"),
+            _ => format!("die {}", self.plural(subject)),
+        }
+    }
+}
+
+/// French, guessing the gender of a noun from its ending
+pub struct French;
+
+impl French {
+    // the first word is the noun, adjectives usually follow it
+    fn gender(subject: &str) -> Gender {
+        let first = subject.split(' ').next().unwrap_or(subject);
+        match () {
+            _ if ["age", "ège", "isme", "ment"]
+                .iter()
+                .any(|suffix| first.ends_with(suffix)) =>
+            {
+                Gender::Masculine
+            }
+            _ if ["e", "ion", "té"]
+                .iter()
+                .any(|suffix| first.ends_with(suffix)) =>
+            {
+                Gender::Feminine
+            }
+            _ => Gender::Masculine,
+        }
+    }
+
+    fn elides(subject: &str) -> bool {
+        starts_with_vowel(subject) || subject.starts_with(['h', 'H', 'é', 'É'])
+    }
+
+    fn plural_word(word: &str) -> String {
+        match () {
+            _ if word.ends_with(['s', 'x', 'z']) => word.to_string(),
+            _ if word.ends_with("al") => format!("{}aux", strip_suffix(word, "al")),
+            _ if word.ends_with("au") | word.ends_with("eu") => format!("{word}x"),
+            _ => format!("{word}s"),
+        }
+    }
+}
+
+impl Grammar for French {
+    // adjectives agree with the noun, so every word is pluralized
+    fn plural(&self, subject: &str) -> String {
+        subject
+            .split(' ')
+            .map(|word| match word {
+                "de" | "du" | "des" | "la" | "le" | "les" | "d'" | "l'" | "à" | "et" => {
+                    word.to_string()
+                }
+                word => Self::plural_word(word),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn indefinite(&self, subject: &str, quantity: usize) -> String {
+        match (quantity, Self::gender(subject)) {
+            (1, Gender::Feminine) => format!("une {subject}"),
+            (1, _) => format!("un {subject}"),
+            _ => format!("{quantity} {}", self.plural(subject)),
+        }
+    }
+
+    fn definite(&self, subject: &str, quantity: usize) -> String {
+        match (quantity, Self::gender(subject)) {
+            (0 | 1, _) if Self::elides(subject) => format!("l'{subject}"),
+            (0 | 1, Gender::Feminine) => format!("la {subject}"),
+            (0 | 1, _) => format!("le {subject}"),
+            _ => format!("les {}", self.plural(subject)),
+        }
+    }
+}
+
+#[test]
+fn german() {
+    assert_eq!(German.indefinite("Schwert", 1), "ein Schwert");
+    assert_eq!(German.indefinite("Klinge", 1), "eine Klinge");
+    assert_eq!(German.indefinite("Klinge", 3), "3 Klingen");
+    assert_eq!(German.definite("Mädchen", 1), "das Mädchen");
+    assert_eq!(German.definite("Drache", 2), "die Drachen");
+}
+
+#[test]
+fn french() {
+    assert_eq!(French.indefinite("épée", 1), "une épée");
+    assert_eq!(French.definite("épée", 1), "l'épée");
+    assert_eq!(French.definite("bouclier", 1), "le bouclier");
+    assert_eq!(French.indefinite("cheval", 2), "2 chevaux");
+    assert_eq!(French.definite("épée rouillée", 2), "les épées rouillées");
+}
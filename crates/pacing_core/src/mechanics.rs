@@ -1,6 +1,8 @@
 use std::{
     borrow::Cow,
-    collections::{BTreeMap, HashMap, VecDeque},
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    sync::Arc,
     time::Duration,
 };
 
@@ -12,18 +14,133 @@ use std::time::Instant;
 // use time::OffsetDateTime;
 
 use crate::{
-    config::{self, Class, EquipmentPreset, Race, Stat},
-    lingo::{self, act_name, definite, generate_name, indefinite},
-    rand::{Rand, SliceExt},
+    board::QuestBoard,
+    checkpoint::{Checkpoint, Timeline},
+    codex,
+    combat::{self, Attack, Blow},
+    config::{self, Class, EquipmentPreset, GlyphStyle, Goods, Race, Rated, Rating, Stat},
+    event_log::{EventLog, LogEvent},
+    format::{Roman, Time},
+    journal::{Event, Journal},
+    lingo::{self, act_name, definite, generate_name, indefinite, plural},
+    rand::{Rand, Recent, SliceExt},
+    rules::{Ironman, RuleSet},
+    subscription::{Subscribers, SubscriptionId, TaskProgress},
+    world::WorldMap,
 };
 
+fn class_kit<'a>(
+    content: &'a config::ContentPack,
+    class: &Class,
+) -> &'a [(config::Equipment, EquipmentPreset)] {
+    content
+        .starting_kits
+        .iter()
+        .find(|kit| kit.class == class.name)
+        .map_or(config::DEFAULT_KIT, |kit| &kit.equipment)
+}
+
+/// What a new piece of equipment costs at `level`, with the default [`Tuning`](config::Tuning)
+pub const fn equipment_price(level: usize) -> isize {
+    config::Tuning::DEFAULT.equipment_price(level)
+}
+
+/// How long `level` takes, with the default [`Tuning`](config::Tuning)
 pub const fn level_up_time(level: usize) -> Duration {
-    Duration::from_secs((20 * level * 60) as _)
+    config::Tuning::DEFAULT.level_up_time(level)
+}
+
+/// How long the plot of an act takes to play out
+pub const fn plot_time(act: i32) -> Duration {
+    Duration::from_secs((60 * 60 * (1 + 5 * act)) as _)
+}
+
+/// How much game time passes each time a [`Simulation`] is ticked
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum TickPolicy {
+    /// However much real time passed since the last tick
+    #[default]
+    RealTime,
+    /// The same amount every tick no matter how long it's been, so runs are reproducible
+    FixedStep(Duration),
+    /// Finish the current task every tick
+    Turbo,
+}
+
+/// Speeds the game up while nobody is watching it, and eases back to the player's chosen speed
+/// once they are
+#[derive(Copy, Clone, Debug)]
+pub struct TimeController {
+    boost: f32,
+    /// When this was last updated, in real seconds
+    last: Option<f64>,
+}
+
+impl Default for TimeController {
+    fn default() -> Self {
+        Self {
+            boost: 1.0,
+            last: None,
+        }
+    }
+}
+
+impl TimeController {
+    /// Real seconds for most of the boost to wear off
+    const EASE: f64 = 2.0;
+
+    /// How many times faster than the player's chosen speed the game is going
+    pub const fn boost(&self) -> f32 {
+        self.boost
+    }
+
+    /// `background` is how much faster to go while hidden, or `None` while being watched
+    pub fn update(&mut self, now: f64, background: Option<f32>) {
+        let dt = self.last.map_or(0.0, |last| (now - last).max(0.0));
+        self.last = Some(now);
+
+        match background {
+            // there's nobody to see it change, so there's nothing to ease
+            Some(boost) => self.boost = boost.max(1.0),
+            None => {
+                let t = 1.0 - (-dt / Self::EASE).exp();
+                self.boost += (1.0 - self.boost) * t as f32;
+                if self.boost < 1.01 {
+                    self.boost = 1.0
+                }
+            }
+        }
+    }
+
+    /// Run `simulation` at the player's chosen speed, with the boost on top
+    pub fn apply(&self, simulation: &mut Simulation) {
+        simulation.time_scale = simulation.player.time_scale * self.boost;
+    }
 }
 
 pub struct Simulation {
     pub player: Player,
     pub time_scale: f32,
+    history: VecDeque<String>,
+    completed: usize,
+    synthesis: Option<Synthesis>,
+    title_card: Option<TitleCard>,
+    watchdog: Watchdog,
+    diagnostic: Option<String>,
+    event_log: EventLog,
+    timeline: Timeline,
+    subscribers: Subscribers,
+    rng: Rand,
+    seed: u64,
+    /// The player as it was when the simulation started, for replaying
+    origin: Player,
+    policy: TickPolicy,
+    paused: bool,
+    /// Set when a task completes, until [`Simulation::mark_saved`]
+    dirty: bool,
+    rules: RuleSet,
+    /// The content played with, `None` follows [`config::content`]
+    content: Option<Arc<config::ContentPack>>,
     last: Instant,
 }
 
@@ -47,19 +164,559 @@ impl Simulation {
         ),
     ];
 
+    const MAX_HISTORY: usize = 50;
+
+    /// How long avoiding or suffering a hazard bolsters or weakens the hero, in seconds of game time
+    pub const HAZARD_EFFECT: f32 = 10.0 * 60.0;
+    /// Failing a hazard with this many wounds from earlier ones leaves the hero for dead
+    pub const LAST_STAND_WOUNDS: usize = 2;
+    /// How much strength, condition and dexterity the hero loses after a last stand
+    pub const LAST_STAND_WEAKNESS: isize = 2;
+    /// How long the weakness lasts, in seconds of game time
+    pub const LAST_STAND_RECOVERY: f32 = 30.0 * 60.0;
+    const LAST_STAND: &[(&'static str, Duration)] = &[
+        (
+            "Left for dead, you drift in and out of consciousness",
+            Duration::from_millis(6000),
+        ),
+        (
+            "A passing stranger drags you to shelter and tends your wounds",
+            Duration::from_millis(4000),
+        ),
+        (
+            "Slowly, painfully, you find your feet again",
+            Duration::from_millis(4000),
+        ),
+    ];
+    pub const MAX_INJECTED: usize = 20;
+    /// Ticks further apart than this are taken to mean the computer was asleep.
+    ///
+    /// A window that's hidden or minimized can go a good while between ticks without the computer
+    /// sleeping, so this is kept well clear of that.
+    pub const SLEEP_GAP: Duration = Duration::from_secs(30 * 60);
+
     pub fn new(player: Player) -> Self {
+        Self::with_seed(player, Rand::random_seed())
+    }
+
+    /// A simulation where all of the randomness comes from `seed`, so the same player and seed
+    /// always play out the same way.
+    ///
+    /// The first seed a hero is played with is kept in [`Player::seed`], and every later session
+    /// picks up from it, so a saved hero can be replayed with [`Self::replay_to`] long after the
+    /// session that made them. An [`Ironman`] uses their own seed and rules instead, and their
+    /// lock is checked
+    pub fn with_seed(mut player: Player, seed: u64) -> Self {
+        let tasks = player.statistics.tasks_finished as u64;
+        let (seed, rules) = match &mut player.ironman {
+            Some(ironman) => {
+                ironman.check(&config::content());
+                let seed = ironman.session_seed(player.statistics.tasks_finished);
+                (seed, ironman.rules.clone())
+            }
+            None => {
+                let seed = *player.seed.get_or_insert(seed);
+                (seed.wrapping_add(tasks), RuleSet::default())
+            }
+        };
+
         Self {
+            time_scale: player.time_scale,
+            watchdog: Watchdog::new(player.elapsed),
+            origin: player.clone(),
             player,
-            time_scale: 1.0,
+            rng: Rand::seed(seed),
+            seed,
+            history: VecDeque::new(),
+            completed: 0,
+            synthesis: None,
+            title_card: None,
+            diagnostic: None,
+            event_log: EventLog::default(),
+            timeline: Timeline::default(),
+            subscribers: Subscribers::default(),
+            policy: TickPolicy::default(),
+            paused: false,
+            dirty: false,
+            rules,
+            content: None,
             last: Instant::now(),
         }
     }
 
-    pub fn tick(&mut self, rng: &Rand) {
-        let dt = self.last.elapsed().as_secs_f32() * self.time_scale;
+    /// The content this simulation plays with
+    pub fn content(&self) -> Arc<config::ContentPack> {
+        self.content.clone().unwrap_or_else(config::content)
+    }
+
+    /// Play with `content` from now on, instead of whatever the global content is, see
+    /// [`config::ContentPack::shared`]
+    pub fn set_content(&mut self, content: Arc<config::ContentPack>) {
+        if let Some(ironman) = &mut self.player.ironman {
+            ironman.check(&content);
+        }
+        self.content = Some(content);
+    }
+
+    /// Synthesize new monsters and items in the later acts, so they don't repeat as much
+    pub fn set_synthesis(&mut self, enabled: bool) {
+        match (enabled, &self.synthesis) {
+            (true, None) => {
+                self.synthesis = Some(config::with_content(&self.content(), Synthesis::train))
+            }
+            (false, Some(..)) => self.synthesis = None,
+            _ => {}
+        }
+    }
+
+    pub const fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Play by `rules` from now on. An ironman's rules can't be changed, so this does nothing
+    /// for them
+    pub fn set_rules(&mut self, rules: RuleSet) {
+        if self.player.ironman.is_none() {
+            self.rules = rules;
+        }
+    }
+
+    /// Add a task from outside the game to the queue, like one from an [`ExternalEvent`]. It
+    /// starts once the current task is done, so it flows through the normal bars and journal.
+    ///
+    /// At most [`Self::MAX_INJECTED`] injected tasks can be waiting at once, past that the task is
+    /// given back
+    pub fn inject_task(&mut self, task: Task, priority: Priority) -> Result<(), Box<Task>> {
+        let waiting = self
+            .player
+            .queue
+            .iter()
+            .filter(|task| matches!(task.kind, TaskKind::External { .. }))
+            .count();
+        if waiting >= Self::MAX_INJECTED {
+            return Err(Box::new(task));
+        }
+
+        // tasks are taken from the back of the queue
+        match priority {
+            Priority::Next => self.player.queue.push_back(task),
+            Priority::Last => self.player.queue.push_front(task),
+        }
+        Ok(())
+    }
+
+    pub const fn tuning(&self) -> &config::Tuning {
+        &self.player.tuning
+    }
+
+    /// Change the pacing formulas, the current level and inventory switch over straight away. An
+    /// ironman's tuning can't be changed, so this does nothing for them
+    pub fn set_tuning(&mut self, tuning: config::Tuning) {
+        if self.player.ironman.is_some() {
+            return;
+        }
+        self.player.exp_bar.max = tuning.level_up_time(self.player.level).as_secs() as f32;
+        self.player.tuning = tuning;
+        self.update_capacity();
+    }
+
+    fn update_unlocked(&mut self) {
+        let mut unlocked = self.player.unlocked.take();
+        for entry in codex::newly_unlocked(&self.player, &mut unlocked) {
+            let event = Event::Unlocked {
+                name: entry.name,
+                description: entry.description,
+            };
+            self.player.journal.record(self.player.elapsed, event);
+        }
+        self.player.unlocked = unlocked;
+    }
+
+    fn update_capacity(&mut self) {
+        let strength = self.player.stats[Stat::Strength];
+        self.player
+            .inventory
+            .set_capacity(self.player.tuning.capacity(strength));
+    }
+
+    fn synthesis(&self) -> Option<&Synthesis> {
+        self.synthesis
+            .as_ref()
+            .filter(|_| self.player.quest_book.act() >= Synthesis::MIN_ACT)
+    }
+
+    fn unnamed_monster(&self, level: usize, attempts: usize, rng: &Rand) -> config::Monster {
+        match self.synthesis() {
+            Some(synthesis) if rng.odds(1, 3) => synthesis.monster(level, self.player.rating, rng),
+            _ => unnamed_monster(
+                level,
+                attempts,
+                self.player.rating,
+                &self.player.bestiary,
+                rng,
+            ),
+        }
+    }
+
+    /// Like [`Self::unnamed_monster`], but half the time one of a family native to the region the
+    /// hero was last in or the act's theme
+    fn native_monster(&self, level: usize, attempts: usize, rng: &Rand) -> config::Monster {
+        let content = config::content();
+        let native = match rng.odds(1, 2).then(|| self.climate(rng)).flatten() {
+            Some(climate) => content
+                .monsters
+                .iter()
+                .filter(|monster| {
+                    self.player.rating.allows(monster.rating)
+                        && monster
+                            .family
+                            .is_some_and(|family| climate.families.contains(&family))
+                })
+                .cloned()
+                .collect(),
+            None => vec![],
+        };
+        if native.is_empty() {
+            return self.unnamed_monster(level, attempts, rng);
+        }
+        closest_monster(
+            &native,
+            level,
+            attempts,
+            self.player.rating,
+            &self.player.bestiary,
+            rng,
+        )
+    }
+
+    /// A boring item, half the time one the region or act is known for
+    fn boring_item(&self, rng: &Rand) -> String {
+        match rng.odds(1, 2).then(|| self.climate(rng)).flatten() {
+            Some(climate) if !climate.items.is_empty() => climate.items.choice(rng).to_string(),
+            _ => boring_item(self.player.rating, rng),
+        }
+    }
+
+    /// What quests lean toward: the theme of the act, or the climate of the region the hero was
+    /// last in
+    fn climate(&self, rng: &Rand) -> Option<config::ClimateTags> {
+        let climates = [self.player.quest_book.theme(), self.player.world.climate()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        if climates.is_empty() {
+            return None;
+        }
+        config::content().climate(*climates.choice(rng)).cloned()
+    }
+
+    fn interesting_item(&self, rng: &Rand) -> String {
+        match self.synthesis() {
+            Some(synthesis) if rng.odds(1, 3) => {
+                synthesis.interesting_item(self.player.rating, rng)
+            }
+            _ => interesting_item(self.player.rating, rng),
+        }
+    }
+
+    fn special_item(&self, rng: &Rand) -> String {
+        format!(
+            "{} of {}",
+            self.interesting_item(rng),
+            choose_rated(
+                &config::content().item_prepositions,
+                self.player.rating,
+                rng
+            )
+        )
+    }
+
+    /// Descriptions of the most recently completed tasks, oldest first
+    pub fn history(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.history.iter().map(|s| &**s)
+    }
+
+    /// The number of tasks completed since the simulation was created
+    pub const fn completed(&self) -> usize {
+        self.completed
+    }
+
+    /// Tasks completed after the first `completed` tasks, if they're still in the history
+    pub fn history_since(&self, completed: usize) -> impl Iterator<Item = &str> {
+        let new = self
+            .completed
+            .saturating_sub(completed)
+            .min(self.history.len());
+        self.history().skip(self.history.len() - new)
+    }
+
+    /// The title card for an act that just started, if the frontend hasn't shown it yet
+    pub fn take_title_card(&mut self) -> Option<TitleCard> {
+        self.title_card.take()
+    }
+
+    /// The seed the simulation's randomness comes from
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Run `f` with the simulation's random number generator
+    fn with_rng<T>(&mut self, f: impl FnOnce(&mut Self, &Rand) -> T) -> T {
+        // it's moved out so `f` can borrow the rest of the simulation
+        let rng = std::mem::replace(&mut self.rng, Rand::seed(0));
+        let out = match self.content.clone() {
+            Some(content) => config::with_content(&content, || f(self, &rng)),
+            None => f(self, &rng),
+        };
+        self.rng = rng;
+        out
+    }
+
+    /// Seconds of game time per real second: the time scale, capped at
+    /// [`config::Tuning::max_time_scale`]
+    pub fn speed(&self) -> f32 {
+        self.time_scale
+            .clamp(0.0, self.player.tuning.max_time_scale.max(1.0))
+    }
+
+    pub const fn tick_policy(&self) -> TickPolicy {
+        self.policy
+    }
+
+    pub fn set_tick_policy(&mut self, policy: TickPolicy) {
+        self.policy = policy;
+    }
+
+    /// Stop time from passing when ticked, until [`Simulation::resume`] is called
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+        // the time spent paused shouldn't be made up on the next tick
+        self.last = Instant::now();
+    }
+
+    pub const fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Has a task been completed since the player was last saved?
+    pub const fn dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.dirty = false;
+    }
+
+    pub fn tick(&mut self) {
+        let elapsed = self.last.elapsed();
+        self.last = Instant::now();
+        self.tick_after(elapsed);
+    }
+
+    /// Tick as if `elapsed` of real time passed since the last tick.
+    ///
+    /// A gap longer than [`Self::SLEEP_GAP`] means the computer was asleep rather than the game
+    /// running slowly, so it's caught up on like time spent away, see [`Self::catch_up`]
+    pub fn tick_after(&mut self, elapsed: Duration) {
+        if self.paused {
+            return;
+        }
+        if elapsed > Self::SLEEP_GAP && matches!(self.policy, TickPolicy::RealTime) {
+            self.player.journal.record(
+                self.player.elapsed,
+                Event::LostTrackOfTime {
+                    away: elapsed.as_secs(),
+                },
+            );
+            self.catch_up(elapsed);
+            return;
+        }
+        self.player.statistics.played += elapsed.as_secs_f64();
+
+        // these split on task boundaries the same way a replay does
+        match self.policy {
+            TickPolicy::RealTime => self.advance_by(elapsed.as_secs_f32() * self.speed()),
+            TickPolicy::FixedStep(step) => self.advance_by(step.as_secs_f32() * self.speed()),
+            TickPolicy::Turbo => {
+                let remaining = self.player.task_bar.remaining().max(0.0);
+                self.advance(remaining);
+                // and start the next one
+                self.advance(0.0);
+            }
+        }
+    }
+
+    /// Advance by `dt`, finishing as many tasks as fit into it. An infinite `dt` would never
+    /// finish, so it's ignored
+    pub fn advance_by(&mut self, dt: f32) {
+        if !dt.is_finite() {
+            return;
+        }
+        self.with_rng(|this, rng| {
+            let mut left = dt.max(0.0);
+            while left > 0.0 {
+                let step = this.player.task_bar.remaining().max(0.0).min(left);
+                this.advance_with(step, rng);
+                left -= step;
+            }
+        })
+    }
 
+    /// Fast-forward through `elapsed` of real time that passed while the game wasn't running.
+    /// This finishes a task at a time rather than a frame at a time, so hours go by quickly
+    pub fn catch_up(&mut self, elapsed: Duration) {
+        self.advance_by(elapsed.as_secs_f32() * self.speed());
         self.last = Instant::now();
+    }
+
+    /// Play the starting player from scratch with the same seed until `elapsed` game time.
+    ///
+    /// This should end up where advancing this simulation to `elapsed` did, which makes pacing
+    /// bugs reproducible. Ticking in different sized steps can round the clock differently, so
+    /// compare what happened rather than exact times
+    pub fn replay_to(&self, elapsed: f32) -> Self {
+        let mut replay = Self::with_seed(self.origin.clone(), self.seed);
+        replay.content = self.content.clone();
+        replay.time_scale = self.time_scale;
+        replay.set_synthesis(self.synthesis.is_some());
+        replay.set_rules(self.rules.clone());
+        replay.set_tuning(self.player.tuning.clone());
+        replay.advance_by(elapsed - replay.player.elapsed);
+        replay
+    }
+
+    /// Call `callback` as the current task gets a quarter of the way through, halfway, three
+    /// quarters and done, see [`crate::subscription`]
+    pub fn subscribe(
+        &mut self,
+        callback: impl FnMut(&TaskProgress) + Send + 'static,
+    ) -> SubscriptionId {
+        self.subscribers.subscribe(callback)
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        self.subscribers.unsubscribe(id)
+    }
+
+    /// Everything the simulation did recently, oldest first
+    pub const fn event_log(&self) -> &EventLog {
+        &self.event_log
+    }
+
+    pub fn event_log_mut(&mut self) -> &mut EventLog {
+        &mut self.event_log
+    }
+
+    /// How the player was at moments over the last hour of game time
+    pub fn timeline(&self) -> &Timeline {
+        &self.timeline
+    }
+
+    /// A description of the last time the simulation got stuck and had to be recovered
+    pub fn take_diagnostic(&mut self) -> Option<String> {
+        self.diagnostic.take()
+    }
+
+    pub fn advance(&mut self, dt: f32) {
+        self.with_rng(|this, rng| this.advance_with(dt, rng))
+    }
+
+    fn advance_with(&mut self, dt: f32, rng: &Rand) {
+        // ticks without any time passing can only make progress by finishing a task
+        let expected = dt > 0.0 || self.player.task_bar.is_done();
+
+        // anything worth logging happens when a task is finished
+        let before = self
+            .player
+            .task_bar
+            .is_done()
+            .then(|| Checkpoint::of(&self.player));
+        self.step(dt, rng);
+        self.subscribers.update(
+            self.completed,
+            self.player.task.as_ref().map(|task| &*task.description),
+            &self.player.task_bar,
+            self.player.elapsed,
+        );
+        if let Some(before) = before {
+            let diff = Checkpoint::of(&self.player).diff(&before);
+            for event in LogEvent::from_diff(&diff) {
+                self.event_log.record(self.player.elapsed, event);
+            }
+        }
+
+        if expected
+            && self
+                .watchdog
+                .is_stalled(self.progress(), self.player.elapsed)
+        {
+            self.recover(rng);
+        }
+    }
+
+    fn progress(&self) -> Progress {
+        Progress {
+            completed: self.completed,
+            has_task: self.player.task.is_some(),
+            pos: self.player.task_bar.pos.to_bits(),
+            max: self.player.task_bar.max.to_bits(),
+        }
+    }
+
+    /// Throw away whatever the player was stuck on and send them off to fight something
+    fn recover(&mut self, rng: &Rand) {
+        let mut state = format!(
+            "task: {:?}, bar: {}/{}, queue: {} tasks",
+            self.player.task.as_ref().map(|task| &*task.description),
+            self.player.task_bar.pos,
+            self.player.task_bar.max,
+            self.player.queue.len(),
+        );
+
+        // the rest of a dungeon, gauntlet or dig goes with the queue, so they could never finish
+        if let Some(dungeon) = self.player.dungeon.take() {
+            state += &format!(", left {}", dungeon.name);
+        }
+        if let Some(arena) = self.player.arena.take() {
+            state += &format!(", left {}", arena.name);
+        }
+        if let Some(expedition) = self.player.expedition.take() {
+            self.player
+                .inventory
+                .consume(|item| item == Expedition::MAP, 1);
+            state += &format!(", gave up digging for {}", expedition.treasure);
+        }
+
+        self.player.queue.clear();
+        self.player.set_task(Task::monster(
+            self.player.level as _,
+            self.player.quest_book.monster.clone(),
+            self.synthesis(),
+            self.player.rating,
+            &Attack::of(&self.player),
+            &self.player.bestiary,
+            rng,
+        ));
+
+        self.player.journal.record(
+            self.player.elapsed,
+            Event::Recovered {
+                ticks: self.watchdog.stalled,
+                state: state.clone(),
+            },
+        );
+        self.diagnostic = Some(state);
+        self.watchdog = Watchdog::new(self.player.elapsed);
+    }
+
+    fn step(&mut self, dt: f32, rng: &Rand) {
         self.player.elapsed += dt;
+        self.player.update_effects(dt);
+        self.update_rival(dt, rng);
 
         if self.player.task.is_none() {
             self.player
@@ -87,7 +744,7 @@ impl Simulation {
         let gain = matches!(
             self.player.task,
             Some(Task {
-                kind: TaskKind::Kill { .. },
+                kind: TaskKind::Kill { .. } | TaskKind::Delve { .. } | TaskKind::Gauntlet { .. },
                 ..
             })
         );
@@ -97,10 +754,17 @@ impl Simulation {
             return;
         }
 
+        let exp = if self.monster_fled(rng) {
+            self.player.task_bar.max * self.rules.flee_exp_percent as f32 / 100.0
+        } else {
+            self.player.task_bar.max
+        };
+        let exp = exp * self.player.legacy.exp_percent() as f32 / 100.0;
+
         if self.player.exp_bar.is_done() {
             self.player.level_up(rng)
         } else {
-            self.player.exp_bar.increment(self.player.task_bar.max)
+            self.player.exp_bar.increment(exp)
         }
 
         if self.player.quest_book.act() >= 1 {
@@ -128,7 +792,7 @@ impl Simulation {
         self.dequeue(rng);
     }
 
-    pub fn dequeue(&mut self, rng: &Rand) {
+    fn dequeue(&mut self, rng: &Rand) {
         while self.player.task_bar.is_done() {
             let task = self
                 .player
@@ -136,16 +800,84 @@ impl Simulation {
                 .take()
                 .expect("a player should always be on a task");
 
+            while self.history.len() >= Self::MAX_HISTORY {
+                self.history.pop_front();
+            }
+            self.history.push_back(task.description.to_string());
+            self.completed += 1;
+            self.player.statistics.tasks_finished += 1;
+            self.dirty = true;
+            if let TaskKind::Kill { blows, .. } = &task.kind {
+                let foe = foe(&task.description);
+                for &blow in blows {
+                    self.event_log.record(
+                        self.player.elapsed,
+                        LogEvent::Blow {
+                            foe: foe.to_string(),
+                            blow,
+                        },
+                    );
+                }
+            }
+
             let old = task.clone();
 
+            if let TaskKind::Kill { blows, .. } = &task.kind {
+                self.player.statistics.kills += !blows.contains(&Blow::Fled) as usize;
+            }
+            if let TaskKind::Kill {
+                monster: Some(monster),
+                blows,
+                ..
+            } = &task.kind
+            {
+                // quest givers and passers-by have no loot, they aren't monsters
+                if monster.item.is_some() && !blows.contains(&Blow::Fled) {
+                    let title = self.player.bestiary.record(
+                        &monster.name,
+                        monster.family,
+                        self.player.elapsed,
+                    );
+                    if let (Some(title), Some(family)) = (title, monster.family) {
+                        let event = Event::SlayerTitle {
+                            family: family.to_string(),
+                            title,
+                        };
+                        self.player.journal.record(self.player.elapsed, event);
+                    }
+                }
+            }
+            self.update_board(rng);
+            self.update_unlocked();
+            self.update_capacity();
+            self.player.market.drift(self.player.elapsed, rng);
+            self.player.bank.accrue(self.player.elapsed);
+            self.timeline.observe(&self.player);
+
+            match &task.kind {
+                TaskKind::HeadingOut => self.travel(rng),
+                TaskKind::HeadingToMarket => self.player.world.return_to_town(),
+                _ => {}
+            }
+
+            if matches!(task.kind, TaskKind::Kill { .. })
+                && self.player.expedition.is_none()
+                && rng.odds(1, 200)
+            {
+                self.start_expedition(rng);
+            }
+
             match &task.kind {
                 // NPC
                 TaskKind::Kill {
                     monster: Some(monster),
+                    ..
                 } if monster.item.is_none() => {
-                    self.player.choose_item(rng);
+                    let item = self.special_item(rng);
+                    self.player.inventory.add_item(item, 1);
                 }
 
+                // clean kills leave more to pick through
                 TaskKind::Kill {
                     monster:
                         Some(config::Monster {
@@ -153,47 +885,124 @@ impl Simulation {
                             item: Some(item),
                             ..
                         }),
+                    blows,
+                    ..
                 } => {
-                    let item = format!("{} {}", name, item).to_lowercase();
-                    self.player.inventory.add_item(item, 1);
+                    let crits = blows
+                        .iter()
+                        .filter(|blow| matches!(blow, Blow::Crit(..)))
+                        .count();
+
+                    let content = config::content();
+                    let table = content.loot_tables.iter().find(|t| t.monster == **name);
+                    let bestiary = &self.player.bestiary;
+                    match table.and_then(|table| table.roll(|item| bestiary.looted(item), rng)) {
+                        // uniques only ever come one at a time
+                        Some(drop) if drop.unique => {
+                            self.player.inventory.add_item(&drop.item, 1);
+                            self.player.bestiary.loot(name, drop);
+                        }
+                        Some(drop) => {
+                            self.player.inventory.add_item(&drop.item, 1 + crits.min(2));
+                            self.player.bestiary.loot(name, drop);
+                        }
+                        None => {
+                            let item = format!("{} {}", name, item).to_lowercase();
+                            self.player.inventory.add_item(item, 1 + crits.min(2));
+                        }
+                    }
+                }
+
+                TaskKind::Banking { amount } => {
+                    self.player.inventory.add_gold(*amount);
+                    self.player.bank.settle(*amount);
                 }
 
                 TaskKind::Buy => {
-                    self.player
-                        .inventory
-                        .add_gold(-self.player.equipment_price());
-                    self.player.choose_equipment(rng)
+                    let price = self.equipment_price();
+                    self.player.inventory.add_gold(-price);
+                    self.player.statistics.gold_spent += price;
+                    self.player.choose_equipment(rng);
                 }
 
                 task @ TaskKind::HeadingToMarket | task @ TaskKind::Sell
                     if !self.player.inventory.is_empty() =>
                 {
-                    if matches!(task, TaskKind::Sell) {
-                        let item = &self.player.inventory[0];
+                    let sold = match task {
+                        TaskKind::Sell => self.player.inventory.last_sellable(),
+                        _ => None,
+                    };
+                    if let Some(index) = sold {
+                        let item = &self.player.inventory[index];
                         let mut amount = item.quantity * self.player.level;
                         if item.name.contains(" of ") {
                             amount *= 1 + rng.below_low(10) * (1 + rng.below_low(self.player.level))
                         }
-                        self.player.inventory.pop();
-                        self.player.inventory.add_gold(amount as _);
+                        let amount = self.player.market.price(Goods::of(&item.name), amount as _);
+                        self.player.statistics.items_sold += item.quantity;
+                        self.player.statistics.gold_earned += amount;
+                        self.player.inventory.remove(index);
+                        self.player.inventory.add_gold(amount);
+                    }
+
+                    if self.next_market_task() {
+                        break;
                     }
+                }
 
-                    if !self.player.inventory.is_empty() {
-                        let item = &self.player.inventory[self.player.inventory.len() - 1];
-                        self.player.set_task(Task::sell(
-                            format!("Selling {}", indefinite(&item.name, item.quantity)),
-                            Duration::from_millis(1000),
-                        ));
+                TaskKind::Craft { recipe } => {
+                    self.complete_craft(recipe);
+                    if self.next_market_task() {
                         break;
                     }
                 }
 
+                TaskKind::Dig => self.dig(task.duration),
+
+                TaskKind::Gather { item, quantity } => {
+                    self.player.inventory.add_item(item, *quantity);
+                    self.player.statistics.materials_gathered += quantity;
+                }
+
+                TaskKind::HeadingOut
+                    if self.player.quest_book.act() >= 1
+                        && self.player.dungeon.is_none()
+                        && rng.odds(1, 10) =>
+                {
+                    self.start_dungeon(rng)
+                }
+
+                TaskKind::HeadingOut
+                    if self.player.quest_book.act() >= 2
+                        && self.player.dungeon.is_none()
+                        && self.player.arena.is_none()
+                        && rng.odds(1, 20) =>
+                {
+                    self.start_arena(rng)
+                }
+
+                TaskKind::HeadingOut if rng.odds(1, 8) => self.hazard(rng),
+
+                TaskKind::Delve { room } => self.clear_room(room, rng),
+
+                TaskKind::Gauntlet { level, .. } => self.win_round(*level),
+
                 TaskKind::Plot => self.complete_act(rng),
 
+                TaskKind::External { source } => self.player.journal.record(
+                    self.player.elapsed,
+                    Event::External {
+                        source: source.clone(),
+                        description: task.description.to_string(),
+                    },
+                ),
+
                 _ => {}
             }
 
             if self.player.inventory.encumbrance.is_done() {
+                let elapsed = self.player.elapsed;
+                self.player.inventory.encumbrance.mark_completed(elapsed);
                 self.player.set_task(Task::heading_to_market(
                     "Heading to market to sell loot",
                     Duration::from_millis(4000),
@@ -202,7 +1011,12 @@ impl Simulation {
                 let task = self.player.queue.pop_back().unwrap();
                 self.player.set_task(task);
             } else if !matches!(old.kind, TaskKind::Kill { .. } | TaskKind::HeadingOut) {
-                if self.player.inventory.gold > self.player.equipment_price() {
+                let price = self.equipment_price();
+                let transfer = self.player.bank.transfer(self.player.inventory.gold, price);
+                if transfer != 0 && !matches!(old.kind, TaskKind::Banking { .. }) {
+                    self.player
+                        .set_task(Task::banking(transfer, Duration::from_millis(2000)))
+                } else if self.player.inventory.gold > price {
                     self.player.set_task(Task::buy(
                         "Negotiating purchase of better equipment",
                         Duration::from_millis(5000),
@@ -215,70 +1029,749 @@ impl Simulation {
                 }
             } else {
                 self.player.set_task(Task::monster(
-                    self.player.level as _,
+                    self.player.world.monster_level(self.player.level) as _,
                     self.player.quest_book.monster.clone(),
+                    self.synthesis(),
+                    self.player.rating,
+                    &Attack::of(&self.player),
+                    &self.player.bestiary,
                     rng,
                 ))
             }
         }
     }
 
-    pub fn complete_act(&mut self, rng: &Rand) {
-        self.player.quest_book.next_act();
-        let max = (60 * 60 * (1 + 5 * self.player.quest_book.act)) as f32;
-
-        self.player.quest_book.plot.reset(max);
+    /// What the next piece of equipment costs at today's rates
+    fn equipment_price(&self) -> isize {
+        let price = self.player.equipment_price();
+        self.player.market.price(Goods::Equipment, price)
+    }
 
-        if self.player.quest_book.act() > 1 {
-            self.player.choose_item(rng);
-            self.player.choose_equipment(rng);
+    /// Head out to the region for the hero's level, making up the world map on the first trip
+    fn travel(&mut self, rng: &Rand) {
+        if self.player.world.is_empty() {
+            self.player.world = WorldMap::generate(rng);
+        }
+        if let Some(region) = self.player.world.travel(self.player.level) {
+            let event = Event::Travelled {
+                region: region.name.clone(),
+            };
+            self.player.journal.record(self.player.elapsed, event);
         }
     }
 
-    pub fn complete_quest(&mut self, rng: &Rand) {
-        self.player
-            .quest_book
-            .quest
-            .reset((50 + rng.below_low(1000)) as f32);
-        if self.player.quest_book.current_quest().is_some() {
-            [
-                Player::choose_item,
-                Player::choose_spell,
-                Player::choose_equipment,
-                Player::choose_stat,
-            ]
-            .choice(rng)(&mut self.player, rng);
-        }
+    fn start_expedition(&mut self, rng: &Rand) {
+        let treasure = self.interesting_item(rng);
+        let steps = [
+            (String::from("Puzzling over a tattered treasure map"), 2000),
+            (
+                format!(
+                    "Following the map to the ruins of {}",
+                    generate_name(None, rng)
+                ),
+                4000,
+            ),
+            (
+                String::from("Pacing out twenty steps from the crooked tree"),
+                2000,
+            ),
+            (String::from("Digging for buried treasure"), 5000),
+            (format!("Unearthing {}", indefinite(&treasure, 1)), 1000),
+        ];
 
-        self.player.quest_book.monster.take();
+        let total = steps.iter().map(|(_, duration)| duration).sum::<u64>();
+        for (description, duration) in steps {
+            // the queue is drained from the back
+            self.player
+                .queue
+                .push_front(Task::dig(description, Duration::from_millis(duration)))
+        }
 
-        let caption = match rng.below(5) {
-            0 => {
-                let monster = unnamed_monster(self.player.level, 3, rng);
+        self.player.expedition.replace(Expedition {
+            treasure,
+            bar: Bar::with_max(Duration::from_millis(total).as_secs_f32()),
+        });
+        self.player.inventory.add_item(Expedition::MAP, 1);
+        self.player
+            .journal
+            .record(self.player.elapsed, Event::TreasureMapFound);
+    }
+
+    /// A calm spell of gathering between acts. Wiser heroes know where to look, and bring back more
+    fn start_gathering(&mut self, rng: &Rand) {
+        let content = config::content();
+        if content.gatherings.is_empty() {
+            return;
+        }
+
+        let gathering = content.gatherings.choice(rng);
+        if gathering.yields.is_empty() {
+            return;
+        }
+
+        let wisdom = self.player.stats[Stat::Wisdom];
+        for pastime in gathering.pastimes.iter() {
+            let item = gathering.yields.choice(rng);
+            let quantity = 1 + rng.below(1 + wisdom / 6);
+            // the queue is drained from the back
+            self.player.queue.push_front(Task::gather(
+                format!("{}: {pastime}", gathering.name),
+                Duration::from_millis(3000),
+                item,
+                quantity,
+            ))
+        }
+    }
+
+    fn dig(&mut self, duration: Duration) {
+        let expedition = match &mut self.player.expedition {
+            Some(expedition) => expedition,
+            None => return,
+        };
+
+        expedition.bar.increment(duration.as_secs_f32());
+        if !expedition.bar.is_done() {
+            return;
+        }
+
+        let Expedition { treasure, .. } = self.player.expedition.take().unwrap();
+        self.player
+            .inventory
+            .consume(|item| item == Expedition::MAP, 1);
+        self.player.inventory.add_item(&treasure, 1);
+        self.player
+            .journal
+            .record(self.player.elapsed, Event::TreasureFound { item: treasure });
+    }
+
+    fn start_dungeon(&mut self, rng: &Rand) {
+        let name = format!(
+            "the {} of {}",
+            config::content().dungeons.choice(rng),
+            generate_name(None, rng)
+        );
+
+        let level = self.player.level;
+        let count = 3 + rng.below(3 + self.player.quest_book.act() as usize);
+
+        let mut tasks = vec![Task::regular(
+            format!("Descending into {name}"),
+            Duration::from_millis(2000),
+        )];
+
+        for _ in 0..count {
+            let task = match rng.below(3) {
+                0 => {
+                    let monster = self.unnamed_monster(level, 3, rng);
+                    Task::delve(
+                        format!("Clearing a room of {}", lingo::plural(&monster.name)),
+                        Duration::from_millis((2000 + 500 * monster.level.min(10)) as _),
+                        Room::Monster(monster),
+                    )
+                }
+                1 => Task::delve(
+                    "Creeping down a trapped corridor",
+                    Duration::from_millis(2000),
+                    Room::Trap,
+                ),
+                2 => Task::delve(
+                    "Prying open a dusty chest",
+                    Duration::from_millis(1000),
+                    Room::Treasure(self.special_item(rng)),
+                ),
+                _ => unreachable!(),
+            };
+            tasks.push(task);
+        }
+
+        let boss = self.unnamed_monster(level + 3, 4, rng);
+        tasks.push(Task::delve(
+            format!(
+                "Confronting {}, master of {name}",
+                indefinite(&lingo::big(1, &boss.name), 1)
+            ),
+            Duration::from_millis(6000),
+            Room::Boss {
+                name: boss.name.to_string(),
+                loot: self.interesting_item(rng),
+            },
+        ));
+
+        for task in tasks {
+            // the queue is drained from the back
+            self.player.queue.push_front(task)
+        }
+
+        self.player.dungeon.replace(Dungeon {
+            name: name.clone(),
+            bar: Bar::with_max((count + 1) as _),
+            loot: vec![],
+        });
+        self.player
+            .journal
+            .record(self.player.elapsed, Event::DungeonEntered { name });
+    }
+
+    fn clear_room(&mut self, room: &Room, rng: &Rand) {
+        if matches!(room, Room::Trap) {
+            self.hazard(rng)
+        }
+
+        let dungeon = match &mut self.player.dungeon {
+            Some(dungeon) => dungeon,
+            None => return,
+        };
+
+        let loot = match room {
+            Room::Monster(config::Monster {
+                name,
+                item: Some(item),
+                ..
+            }) => Some(format!("{name} {item}").to_lowercase()),
+            Room::Treasure(loot) | Room::Boss { loot, .. } => Some(loot.clone()),
+            Room::Monster(..) | Room::Trap => None,
+        };
+
+        if let Some(loot) = loot {
+            self.player.inventory.add_item(&loot, 1);
+            dungeon.loot.push(loot);
+        }
+
+        dungeon.bar.increment(1.0);
+        if !dungeon.bar.is_done() {
+            return;
+        }
+
+        let Dungeon { name, bar, loot } = self.player.dungeon.take().unwrap();
+        self.player.journal.record(
+            self.player.elapsed,
+            Event::DungeonCleared {
+                name,
+                rooms: bar.max as _,
+                loot,
+            },
+        );
+    }
+
+    fn start_arena(&mut self, rng: &Rand) {
+        let name = format!("the Arena of {}", generate_name(None, rng));
+        let rounds = 3 + (self.player.quest_book.act() as usize).min(5);
+        // a strong hero wins their rounds faster, and the time limit assumes an average one
+        let strength = (self.player.stats[Stat::Strength] * 20).min(1000);
+
+        let mut tasks = vec![Task::regular(
+            format!("Stepping into {name} to face its gauntlet"),
+            Duration::from_millis(2000),
+        )];
+        let mut time_limit = 0;
+        for round in 1..=rounds {
+            let monster = self.unnamed_monster(self.player.level + round, 3, rng);
+            let duration = 2000 + 250 * round + rng.below(2000);
+            time_limit += 3000 + 250 * round;
+            tasks.push(Task {
+                description: format!(
+                    "Round {}: fighting {}",
+                    Roman::from_i32(round as _),
+                    indefinite(&monster.name, 1)
+                )
+                .into(),
+                duration: Duration::from_millis(duration.saturating_sub(strength) as _),
+                kind: TaskKind::Gauntlet {
+                    round,
+                    level: monster.level,
+                },
+            });
+        }
+
+        for task in tasks {
+            // the queue is drained from the back
+            self.player.queue.push_front(task)
+        }
+
+        self.player.arena.replace(Arena {
+            name: name.clone(),
+            bar: Bar::with_max(rounds as _),
+            started: self.player.elapsed,
+            time_limit: Duration::from_millis(time_limit as _).as_secs_f32(),
+            score: 0,
+        });
+        self.player
+            .journal
+            .record(self.player.elapsed, Event::ArenaEntered { name });
+    }
+
+    fn win_round(&mut self, level: usize) {
+        let elapsed = self.player.elapsed;
+        let arena = match &mut self.player.arena {
+            Some(arena) => arena,
+            None => return,
+        };
+
+        let time_left = arena.time_left(elapsed);
+        let trophy = if time_left <= 0.0 {
+            // out of time, so the rest of the gauntlet is forfeit
+            self.player
+                .queue
+                .retain(|task| !matches!(task.kind, TaskKind::Gauntlet { .. }));
+            None
+        } else {
+            arena.score += 10 * level;
+            arena.bar.increment(1.0);
+            if !arena.bar.is_done() {
+                return;
+            }
+            arena.score += 5 * time_left as usize;
+            Some(format!("Gilded Trophy of {}", arena.name))
+        };
+
+        let Arena {
+            name, bar, score, ..
+        } = self.player.arena.take().unwrap();
+        if let Some(trophy) = &trophy {
+            self.player.inventory.add_item(trophy, 1);
+        }
+        self.player.journal.record(
+            elapsed,
+            Event::ArenaFinished {
+                name,
+                rounds: bar.pos as _,
+                score,
+                trophy,
+            },
+        );
+    }
+
+    /// Maybe have a heavily outleveled monster run away before the fight is over. It leaves no
+    /// loot behind
+    fn monster_fled(&mut self, rng: &Rand) -> bool {
+        let rules = &self.rules;
+        let task = match &mut self.player.task {
+            Some(task) => task,
+            None => return false,
+        };
+        let (monster, level, blows) = match &mut task.kind {
+            TaskKind::Kill {
+                monster,
+                level,
+                blows,
+            } => (monster, *level, blows),
+            _ => return false,
+        };
+
+        if rules.flee_chance == 0
+            || self.player.level < level + rules.flee_level_gap
+            || !rng.odds(rules.flee_chance, 100)
+        {
+            return false;
+        }
+
+        *monster = None;
+        blows.push(Blow::Fled);
+        task.description = match task.description.strip_prefix("Attacking ") {
+            Some(foe) => format!("Chasing off {foe}").into(),
+            None => format!("{} (fled)", task.description).into(),
+        };
+        self.player.statistics.monsters_fled += 1;
+        true
+    }
+
+    fn hazard(&mut self, rng: &Rand) {
+        let content = config::content();
+        let hazard = content.hazards.choice(rng);
+        let difficulty = hazard.difficulty + self.player.level / 2;
+        let passed = rng.contest(self.player.stats[hazard.stat], difficulty);
+
+        let statistics = &mut self.player.statistics;
+        let tally = statistics
+            .hazards
+            .entry(hazard.name.to_string())
+            .or_default();
+        let (description, amount) = if passed {
+            statistics.hazards_avoided += 1;
+            tally.avoided += 1;
+            (&hazard.success, 1)
+        } else {
+            statistics.hazards_suffered += 1;
+            tally.suffered += 1;
+            (&hazard.failure, -1)
+        };
+
+        // already this wounded, one more leaves the hero for dead
+        let fallen = !passed && self.player.wounds() >= Self::LAST_STAND_WOUNDS;
+        if fallen {
+            for &(description, duration) in Self::LAST_STAND.iter().rev() {
+                self.player
+                    .queue
+                    .push_back(Task::regular(description, duration));
+            }
+        }
+
+        // the queue is drained from the back, so this happens next
+        self.player.queue.push_back(Task::regular(
+            description.clone(),
+            Duration::from_millis(2000),
+        ));
+
+        self.player.journal.record(
+            self.player.elapsed,
+            Event::Hazard {
+                name: hazard.name.to_string(),
+                passed,
+            },
+        );
+        if fallen {
+            self.last_stand(&hazard.name);
+        } else {
+            self.player
+                .add_effect(hazard.stat, amount, Self::HAZARD_EFFECT);
+        }
+    }
+
+    /// The hero's wounds are traded for a longer, deeper weakness while they recover
+    fn last_stand(&mut self, cause: &str) {
+        self.player.clear_wounds();
+        for stat in [Stat::Strength, Stat::Condition, Stat::Dexterity] {
+            self.player.push_effect(
+                stat,
+                -Self::LAST_STAND_WEAKNESS,
+                Self::LAST_STAND_RECOVERY,
+                true,
+            );
+        }
+
+        self.player.statistics.last_stands += 1;
+        self.player.journal.record(
+            self.player.elapsed,
+            Event::LastStand {
+                cause: cause.to_string(),
+            },
+        );
+    }
+
+    fn next_market_task(&mut self) -> bool {
+        if let Some(task) = self.player.next_craft() {
+            self.player.set_task(task);
+            return true;
+        }
+
+        let Some(index) = self.player.inventory.last_sellable() else {
+            return false;
+        };
+
+        let item = &self.player.inventory[index];
+        self.player.set_task(Task::sell(
+            format!("Selling {}", indefinite(&item.name, item.quantity)),
+            Duration::from_millis(1000),
+        ));
+        true
+    }
+
+    fn complete_craft(&mut self, recipe: &str) {
+        let content = config::content();
+        let recipe = match content.recipes.iter().find(|r| r.name == recipe) {
+            Some(recipe) => recipe,
+            None => return,
+        };
+
+        match &recipe.product {
+            config::Product::Modifier(slot, modifier) => {
+                self.player.equipment.enhance(*slot, modifier)
+            }
+            config::Product::Consumable(item) => self.player.inventory.add_item(item, 1),
+        }
+
+        self.player.journal.record(
+            self.player.elapsed,
+            Event::Crafted {
+                item: recipe.name.to_string(),
+            },
+        );
+    }
+
+    fn complete_act(&mut self, rng: &Rand) {
+        let act = self.player.quest_book.act();
+        if self.rules.final_act.is_some_and(|last| act >= last) {
+            let level = self.player.level;
+            self.player.ascend();
+            self.player.journal.record(
+                self.player.elapsed,
+                Event::Ascended {
+                    ascension: self.player.legacy.ascensions,
+                    level,
+                },
+            );
+        }
+
+        self.player.quest_book.next_act();
+        self.player.quest_book.theme = Some(*config::Climate::ALL.choice(rng));
+        let max = plot_time(self.player.quest_book.act).as_secs() as f32;
+
+        self.player
+            .quest_book
+            .plot
+            .mark_completed(self.player.elapsed);
+        self.player.quest_book.plot.reset(max);
+
+        let card = TitleCard::new(self.player.quest_book.act(), &mut self.player.recent, rng);
+        self.player.journal.record(
+            self.player.elapsed,
+            Event::ActStarted {
+                act: card.act,
+                title: card.title.clone(),
+            },
+        );
+        self.title_card.replace(card);
+
+        if self.player.quest_book.act() > 1 {
+            self.player.choose_item(rng);
+            self.player.choose_equipment(rng);
+
+            if rng.odds(1, 4) {
+                self.start_gathering(rng);
+            }
+        }
+
+        if self.player.rival.is_none() && rng.odds(1, 3) {
+            let rival = Rival::new(self.player.level, rng);
+            self.player.journal.record(
+                self.player.elapsed,
+                Event::RivalAppeared {
+                    name: rival.name.clone(),
+                    class: rival.class.name.to_string(),
+                },
+            );
+            self.player.rival.replace(rival);
+        }
+    }
+
+    /// Pay out finished bounties, and put up new ones when a new act starts
+    fn update_board(&mut self, rng: &Rand) {
+        let player = &mut self.player;
+        let mut board = std::mem::take(&mut player.board);
+        for bounty in board.claim(player) {
+            player.inventory.add_gold(bounty.reward);
+            player.statistics.gold_earned += bounty.reward;
+            player.journal.record(
+                player.elapsed,
+                Event::BountyClaimed {
+                    objective: bounty.objective.to_string(),
+                    reward: bounty.reward,
+                },
+            );
+        }
+
+        if board.is_stale(player) {
+            board.post(player, rng);
+        }
+        player.board = board;
+    }
+
+    fn update_rival(&mut self, dt: f32, rng: &Rand) {
+        let player = &mut self.player;
+        let rival = match &mut player.rival {
+            Some(rival) => rival,
+            None => return,
+        };
+
+        rival.advance(dt);
+
+        let event = match (rival.ahead, rival.level.cmp(&player.level)) {
+            (false, Ordering::Greater) => {
+                rival.ahead = true;
+                Event::RivalOvertook {
+                    name: rival.name.clone(),
+                    level: rival.level,
+                }
+            }
+            (true, Ordering::Less) => {
+                rival.ahead = false;
+                Event::RivalFellBehind {
+                    name: rival.name.clone(),
+                    level: rival.level,
+                }
+            }
+            (_, Ordering::Equal) if rival.level > rival.showdown => {
+                rival.showdown = rival.level;
+                let won =
+                    rng.below(player.stats[player.stats.best_prime()] + rival.level) >= rival.level;
+
+                let name = format!("{} the {}", rival.name, rival.class.name);
+                for (description, duration) in [
+                    (format!("You cross paths with your rival, {name}"), 2000),
+                    (
+                        format!("{name} demands you settle things once and for all"),
+                        2000,
+                    ),
+                    (
+                        if won {
+                            format!("You best {name}, who slinks off to train harder")
+                        } else {
+                            format!("{name} bests you and swaggers off into the distance")
+                        },
+                        3000,
+                    ),
+                ] {
+                    // the queue is drained from the back
+                    player
+                        .queue
+                        .push_front(Task::regular(description, Duration::from_millis(duration)))
+                }
+
+                Event::RivalShowdown {
+                    name: rival.name.clone(),
+                    won,
+                }
+            }
+            _ => return,
+        };
+
+        player.journal.record(player.elapsed, event);
+    }
+
+    fn complete_quest(&mut self, rng: &Rand) {
+        let quest = &mut self.player.quest_book.quest;
+        quest.mark_completed(self.player.elapsed);
+        // older saves didn't keep the length on the quest, but the bar has it too
+        let rolled = quest.max;
+        quest.reset(self.player.tuning.quest_length(rng));
+        if let Some(Quest {
+            caption,
+            giver,
+            length,
+            ..
+        }) = self.player.quest_book.current_quest().cloned()
+        {
+            let difficulty = self
+                .player
+                .tuning
+                .quest_difficulty(length.unwrap_or(rolled));
+            // longer quests give more rewards, and bigger stat bumps among them
+            let count = 1 + (difficulty * 2.0).round() as usize;
+            let bump = 1 + (difficulty * 2.0) as usize;
+
+            let mut rewards = (0..count).map(|_| match rng.below(4) {
+                0 => self.player.choose_item(rng),
+                1 => self.player.choose_spell(rng),
+                2 => self.player.choose_equipment(rng),
+                _ => self.player.choose_stat(bump, rng),
+            });
+            let reward = rewards.next();
+            let bonus = rewards.collect();
+
+            let gold =
+                (difficulty * self.player.tuning.quest_gold as f32) as usize * self.player.level;
+            self.player.inventory.add_gold(gold as isize);
+            self.player.statistics.gold_earned += gold as isize;
+            self.player.statistics.quests_completed += 1;
+
+            self.player.journal.record(
+                self.player.elapsed,
+                Event::QuestCompleted {
+                    caption,
+                    giver,
+                    reward,
+                    bonus,
+                    gold,
+                },
+            );
+        }
+
+        self.player.quest_book.monster.take();
+
+        // now and then the quest is about one of the player's own themes
+        let theme = (!self.player.themes.is_empty() && rng.odds(1, 4))
+            .then(|| self.player.themes.choice(rng).clone());
+        // or about the region the hero was last out in
+        let region = match theme {
+            None => self.player.world.last().filter(|_| rng.odds(1, 6)),
+            Some(..) => None,
+        };
+        let content = config::content();
+        let caption = match (theme, region) {
+            (Some(theme), _) => lingo::weave(content.themed_quests.choice(rng), &theme),
+            (None, Some(region)) => lingo::weave(content.regional_quests.choice(rng), &region.name),
+            (None, None) => self.random_caption(rng),
+        };
+
+        let giver = match self.player.quest_book.returning_giver(rng) {
+            Some(giver) if rng.odds(1, 3) => giver,
+            _ => quest_giver(rng),
+        };
+        self.player
+            .quest_book
+            .add_quest(&caption, giver, self.player.elapsed);
+    }
+
+    fn random_caption(&mut self, rng: &Rand) -> String {
+        match rng.below_fresh(5, "quest", &mut self.player.recent) {
+            0 if self.player.rating.allows(Rating::Classic) => {
+                let monster = self.native_monster(self.player.level, 3, rng);
                 let caption = format!("Exterminate {}", definite(&monster.name, 2));
                 self.player.quest_book.monster.replace(monster);
                 caption
             }
             1 => {
-                format!("Seek {}", definite(&interesting_item(rng), 1))
+                format!("Seek {}", definite(&self.interesting_item(rng), 1))
             }
             2 => {
-                format!("Deliver this {}", boring_item(rng))
+                format!("Deliver this {}", self.boring_item(rng))
             }
             3 => {
-                format!("Fetch me {}", indefinite(boring_item(rng), 1))
+                let item = self.boring_item(rng);
+                format!("Fetch me {}", indefinite(&item, 1))
             }
-            4 => {
-                let monster = unnamed_monster(self.player.level, 1, rng);
+            // exterminating things isn't family friendly, so placate them instead
+            0 | 4 => {
+                let monster = self.native_monster(self.player.level, 1, rng);
                 format!("Placate {}", definite(&monster.name, 2))
             }
             _ => unreachable!(),
+        }
+    }
+
+    /// Beat the nemesis from the cinematic, which either gets away to come back in a later act or
+    /// is slain for good, dropping more loot the more times it was fought
+    fn defeat_nemesis(&mut self, rng: &Rand) -> String {
+        let Some(mut nemesis) = self.player.nemesis.take() else {
+            return String::new();
+        };
+        let title = nemesis.to_string();
+        nemesis.encounters += 1;
+
+        let escaped = nemesis.encounters < Nemesis::MAX_ENCOUNTERS && rng.odds(2, 3);
+        let (event, victory) = if escaped {
+            let event = Event::NemesisEscaped {
+                name: title.clone(),
+                encounters: nemesis.encounters,
+            };
+            self.player.nemesis = Some(nemesis);
+            let victory = format!(
+                "Victory! {title} flees, swearing revenge! Exhausted, you lose consciousness"
+            );
+            (event, victory)
+        } else {
+            let loot = (0..nemesis.encounters)
+                .map(|_| {
+                    let item = self.special_item(rng);
+                    self.player.inventory.add_item(&item, 1);
+                    item
+                })
+                .collect();
+            let event = Event::NemesisSlain {
+                name: title.clone(),
+                encounters: nemesis.encounters,
+                loot,
+            };
+            let victory =
+                format!("Victory! {title} is slain at last! Exhausted, you lose consciousness");
+            (event, victory)
         };
 
-        self.player.quest_book.add_quest(&caption);
+        self.player.journal.record(self.player.elapsed, event);
+        victory
     }
 
-    pub fn cinematic(&mut self, rng: &Rand) {
+    fn cinematic(&mut self, rng: &Rand) {
         trait Queue {
             fn enqueue(&mut self, task: Task, rng: &Rand);
         }
@@ -290,7 +1783,7 @@ impl Simulation {
             }
         }
 
-        match rng.below(3) {
+        match rng.below_fresh(3, "cinematic", &mut self.player.recent) {
             0 => {
                 for (description, duration) in [
                     (
@@ -316,7 +1809,33 @@ impl Simulation {
                     rng,
                 );
 
-                let nemesis = named_monster(self.player.level + 3, rng);
+                // a nemesis from an earlier act comes back, but one that was already fought in
+                // this act leaves the stage to a stranger
+                let act = self.player.quest_book.act();
+                let level = self.player.level + 3;
+                let rating = self.player.rating;
+                let nemesis = self
+                    .player
+                    .nemesis
+                    .get_or_insert_with(|| Nemesis::new(level, act, rating, rng));
+                let recurring = nemesis.encounters == 0 || nemesis.act < act;
+                let returning = recurring && nemesis.encounters > 0;
+                if returning {
+                    nemesis.resurface(level, act);
+                }
+                let nemesis = match recurring {
+                    true => nemesis.to_string(),
+                    false => Nemesis::new(level, act, rating, rng).to_string(),
+                };
+                if returning {
+                    self.enqueue(
+                        Task::regular(
+                            format!("{nemesis} has returned to settle an old score"),
+                            Duration::from_millis(2000),
+                        ),
+                        rng,
+                    );
+                }
                 self.enqueue(
                     Task::regular(
                         format!("A desperate struggle commences with {nemesis}"),
@@ -357,13 +1876,13 @@ impl Simulation {
                     }
                 }
 
-                self.enqueue(
-                    Task::regular(
-                        format!("Victory! {nemesis} is slain! Exhauted, you lose consciousness"),
-                        Duration::from_millis(3000),
-                    ),
-                    rng,
-                );
+                let victory = match recurring {
+                    true => self.defeat_nemesis(rng),
+                    false => {
+                        format!("Victory! {nemesis} is slain! Exhauted, you lose consciousness")
+                    }
+                };
+                self.enqueue(Task::regular(victory, Duration::from_millis(3000)), rng);
 
                 self.enqueue(
                     Task::regular(
@@ -389,7 +1908,10 @@ impl Simulation {
                         3000,
                     ),
                     (
-                        format!("You forgot your {} and go back to get it", boring_item(rng)),
+                        format!(
+                            "You forgot your {} and go back to get it",
+                            boring_item(self.player.rating, rng)
+                        ),
                         2000,
                     ),
                     (
@@ -413,6 +1935,12 @@ impl Simulation {
             _ => unreachable!(),
         };
 
+        if !self.player.themes.is_empty() {
+            let theme = self.player.themes.choice(rng);
+            let scene = lingo::weave(config::content().themed_scenes.choice(rng), theme);
+            self.enqueue(Task::regular(scene, Duration::from_millis(2000)), rng);
+        }
+
         self.enqueue(
             Task::plot(
                 format!("Loading {}", act_name(self.player.quest_book.act() + 1)),
@@ -430,7 +1958,89 @@ pub struct Task {
     pub kind: TaskKind,
 }
 
+/// Something happening outside of the game, like a chat message or a donation, played out as a
+/// task and recorded in the journal once it's done.
+///
+/// Descriptions are tidied up and durations kept within reason, so text from anywhere can be
+/// passed straight in
+#[derive(Clone, Debug)]
+pub struct ExternalEvent {
+    description: String,
+    source: String,
+    duration: Duration,
+}
+
+impl ExternalEvent {
+    /// Longer descriptions are cut short
+    pub const MAX_LENGTH: usize = 120;
+    pub const MIN_DURATION: Duration = Duration::from_secs(1);
+    pub const MAX_DURATION: Duration = Duration::from_secs(60);
+
+    /// An event that takes 5 seconds, like "Reading a donation message from X"
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            source: String::from("external"),
+            duration: Duration::from_secs(5),
+        }
+    }
+
+    /// Where the event came from, like "chat" or "donations"
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = source.into();
+        self
+    }
+
+    pub const fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn into_task(self) -> Task {
+        let clean = |s: &str, max| {
+            s.split_whitespace()
+                .flat_map(|word| std::iter::once(' ').chain(word.chars()))
+                .skip(1)
+                .filter(|ch| !ch.is_control())
+                .take(max)
+                .collect::<String>()
+        };
+
+        let description = match clean(&self.description, Self::MAX_LENGTH) {
+            description if description.is_empty() => String::from("Something happens"),
+            description => description,
+        };
+        let source = match clean(&self.source, 32) {
+            source if source.is_empty() => String::from("external"),
+            source => source,
+        };
+
+        Task {
+            description: description.into(),
+            duration: self.duration.clamp(Self::MIN_DURATION, Self::MAX_DURATION),
+            kind: TaskKind::External { source },
+        }
+    }
+}
+
+/// Where an injected task goes in the queue, see [`Simulation::inject_task`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Priority {
+    /// Right after the current task
+    Next,
+    /// After everything that's already queued
+    Last,
+}
+
 impl Task {
+    /// The description, with the glyph for its category in front
+    pub fn decorated(&self, style: GlyphStyle) -> Cow<'_, str> {
+        match self.kind.category().glyph(style) {
+            Some(glyph) => Cow::from(format!("{glyph} {}", self.description)),
+            None => Cow::from(&*self.description),
+        }
+    }
+
     pub fn regular(description: impl Into<Cow<'static, str>>, duration: Duration) -> Self {
         Self {
             description: description.into(),
@@ -474,42 +2084,107 @@ impl Task {
         }
     }
 
-    pub fn buy(description: impl Into<Cow<'static, str>>, duration: Duration) -> Self {
+    pub fn dig(description: impl Into<Cow<'static, str>>, duration: Duration) -> Self {
         Self {
             description: description.into(),
             duration,
-            kind: TaskKind::Buy,
+            kind: TaskKind::Dig,
         }
     }
 
-    pub fn monster(
-        player_level: isize,
-        quest_monster: Option<config::Monster>,
-        rng: &Rand,
+    pub fn gather(
+        description: impl Into<Cow<'static, str>>,
+        duration: Duration,
+        item: impl ToString,
+        quantity: usize,
     ) -> Self {
-        let mut level = player_level;
-        for _ in 0..player_level {
-            if rng.odds(2, 5) {
-                level += rng.below(2) as isize * 2 - 1
-            }
+        Self {
+            description: description.into(),
+            duration,
+            kind: TaskKind::Gather {
+                item: item.to_string(),
+                quantity,
+            },
         }
+    }
+
+    pub fn delve(
+        description: impl Into<Cow<'static, str>>,
+        duration: Duration,
+        room: Room,
+    ) -> Self {
+        Self {
+            description: description.into(),
+            duration,
+            kind: TaskKind::Delve { room },
+        }
+    }
+
+    pub fn craft(recipe: &config::Recipe, duration: Duration) -> Self {
+        Self {
+            description: format!("Crafting {}", indefinite(&recipe.name, 1)).into(),
+            duration,
+            kind: TaskKind::Craft {
+                recipe: recipe.name.to_string(),
+            },
+        }
+    }
+
+    /// Hand `amount` of gold to the hero, or deposit it if it's negative
+    pub fn banking(amount: isize, duration: Duration) -> Self {
+        let description = if amount < 0 {
+            format!("Depositing {} gold at the bank", -amount)
+        } else {
+            format!("Withdrawing {amount} gold from the bank")
+        };
+        Self {
+            description: description.into(),
+            duration,
+            kind: TaskKind::Banking { amount },
+        }
+    }
+
+    pub fn buy(description: impl Into<Cow<'static, str>>, duration: Duration) -> Self {
+        Self {
+            description: description.into(),
+            duration,
+            kind: TaskKind::Buy,
+        }
+    }
+
+    pub fn monster(
+        player_level: isize,
+        quest_monster: Option<config::Monster>,
+        synthesis: Option<&Synthesis>,
+        rating: Rating,
+        attack: &Attack,
+        bestiary: &Bestiary,
+        rng: &Rand,
+    ) -> Self {
+        let mut level = player_level;
+        for _ in 0..player_level {
+            if rng.odds(2, 5) {
+                level += rng.below(2) as isize * 2 - 1
+            }
+        }
+
+        let mut level = level.max(1);
+
+        let mut is_definite = false;
+        let mut monster = Option::<config::Monster>::None;
+
+        let task_level: isize;
+        let result;
 
-        let mut level = level.max(1);
-
-        let mut is_definite = false;
-        let mut monster = Option::<config::Monster>::None;
-
-        let task_level: isize;
-        let result;
-
         if rng.odds(1, 25) {
-            let race = config::RACES.choice(rng);
+            let content = config::content();
+            let race = content.races.choice(rng);
             if rng.odds(1, 2) {
-                result = format!("passing {} {}", race.name, config::CLASSES.choice(rng).name);
+                result = format!("passing {} {}", race.name, content.classes.choice(rng).name);
             } else {
                 result = format!(
                     "{} {} the {}",
-                    config::TITLES.choice_low(rng),
+                    content.titles.choice_low(rng),
                     generate_name(None, rng),
                     race.name
                 );
@@ -522,7 +2197,10 @@ impl Task {
             task_level = quest_monster.level as isize;
             monster.replace(quest_monster);
         } else {
-            monster.replace(unnamed_monster(level as _, 5, rng));
+            monster.replace(match synthesis {
+                Some(synthesis) if rng.odds(1, 3) => synthesis.monster(level as _, rating, rng),
+                _ => unnamed_monster(level as _, 5, rating, bestiary, rng),
+            });
             let monster = monster.as_ref().unwrap();
             result = monster.name.to_string();
             task_level = monster.level as isize
@@ -575,23 +2253,119 @@ impl Task {
             result = indefinite(&result, qty as _)
         }
 
+        let blows = attack.resolve(level as _, rng);
+        let mut duration = combat::duration(&blows);
+        // a practiced slayer makes shorter work of the family
+        if let Some(family) = monster.as_ref().and_then(|monster| monster.family) {
+            let bonus = Bestiary::SLAYER_PERCENT * bestiary.slayer_rank(family) as u32;
+            duration = duration * (100 - bonus) / 100;
+        }
         Self {
             description: format!("Attacking {result}").into(),
-            duration: Duration::from_millis(((2 * 3 * level * 1000) / player_level) as _),
-            kind: TaskKind::Kill { monster },
+            duration,
+            kind: TaskKind::Kill {
+                monster,
+                level: task_level as _,
+                blows,
+            },
         }
     }
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub enum TaskKind {
-    Kill { monster: Option<config::Monster> },
+    Kill {
+        monster: Option<config::Monster>,
+        /// The level of each monster, the default is for tasks from older saves
+        #[serde(default)]
+        level: usize,
+        /// How the fight plays out, see [`combat`]
+        #[serde(default)]
+        blows: Vec<Blow>,
+    },
     Buy,
+    /// Positive amounts are withdrawn, negative ones deposited
+    Banking {
+        amount: isize,
+    },
     HeadingOut,
     HeadingToMarket,
     Sell,
+    Craft {
+        recipe: String,
+    },
+    Dig,
+    Delve {
+        room: Room,
+    },
+    Gather {
+        item: String,
+        quantity: usize,
+    },
+    Gauntlet {
+        round: usize,
+        level: usize,
+    },
     Regular,
     Plot,
+    /// Injected from outside the game, see [`ExternalEvent`]
+    External {
+        source: String,
+    },
+}
+
+impl TaskKind {
+    pub const fn category(&self) -> TaskCategory {
+        match self {
+            Self::Kill { .. }
+            | Self::Gauntlet { .. }
+            | Self::Delve {
+                room: Room::Monster(..) | Room::Boss { .. },
+            } => TaskCategory::Combat,
+            Self::Buy | Self::Sell | Self::Banking { .. } => TaskCategory::Trade,
+            Self::HeadingOut | Self::HeadingToMarket => TaskCategory::Travel,
+            Self::Craft { .. } | Self::Dig | Self::Delve { .. } | Self::Gather { .. } => {
+                TaskCategory::Work
+            }
+            Self::Regular | Self::Plot | Self::External { .. } => TaskCategory::Story,
+        }
+    }
+}
+
+/// A coarse grouping of tasks, for decorating and filtering them
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TaskCategory {
+    Combat,
+    Trade,
+    Travel,
+    Work,
+    Story,
+}
+
+impl TaskCategory {
+    pub const fn glyph(self, style: GlyphStyle) -> Option<&'static str> {
+        Some(match (style, self) {
+            (GlyphStyle::Off, _) => return None,
+            (GlyphStyle::Emoji, Self::Combat) => "⚔",
+            (GlyphStyle::Emoji, Self::Trade) => "🛒",
+            (GlyphStyle::Emoji, Self::Travel) => "🚶",
+            (GlyphStyle::Emoji, Self::Work) => "⚒",
+            (GlyphStyle::Emoji, Self::Story) => "📜",
+            (GlyphStyle::Ascii, Self::Combat) => "[x]",
+            (GlyphStyle::Ascii, Self::Trade) => "[$]",
+            (GlyphStyle::Ascii, Self::Travel) => "[>]",
+            (GlyphStyle::Ascii, Self::Work) => "[*]",
+            (GlyphStyle::Ascii, Self::Story) => "[!]",
+        })
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Room {
+    Monster(config::Monster),
+    Trap,
+    Treasure(String),
+    Boss { name: String, loot: String },
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -615,7 +2389,7 @@ impl Stats {
         }
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &(Stat, usize)> + ExactSizeIterator + '_ {
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &(Stat, usize)> + '_ {
         self.values.iter()
     }
 
@@ -643,6 +2417,17 @@ impl Stats {
             .find_map(|(s, q)| (*s == stat).then_some(q))
             .unwrap_or_else(|| panic!("stat does not exist: {stat:?}")) += quantity;
     }
+
+    pub fn decrement(&mut self, stat: Stat, quantity: usize) -> usize {
+        let value = self
+            .values
+            .iter_mut()
+            .find_map(|(s, q)| (*s == stat).then_some(q))
+            .unwrap_or_else(|| panic!("stat does not exist: {stat:?}"));
+        let quantity = quantity.min(*value);
+        *value -= quantity;
+        quantity
+    }
 }
 
 impl std::ops::Index<Stat> for Stats {
@@ -655,64 +2440,190 @@ impl std::ops::Index<Stat> for Stats {
     }
 }
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+/// A quest, and the npc that gave it
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(from = "QuestRepr")]
+pub struct Quest {
+    pub caption: String,
+    pub giver: Option<String>,
+    /// The game time the quest was accepted at, if it was saved with one
+    pub accepted: Option<f32>,
+    /// How much progress the quest takes, if it was saved with one
+    pub length: Option<f32>,
+}
+
+impl Quest {
+    /// How much game time has passed since the quest was accepted
+    pub fn age(&self, elapsed: f32) -> Option<f32> {
+        self.accepted.map(|accepted| (elapsed - accepted).max(0.0))
+    }
+}
+
+impl std::fmt::Display for Quest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.caption)
+    }
+}
+
+// older saves only stored the caption
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum QuestRepr {
+    Caption(String),
+    Quest {
+        caption: String,
+        giver: Option<String>,
+        #[serde(default)]
+        accepted: Option<f32>,
+        #[serde(default)]
+        length: Option<f32>,
+    },
+}
+
+impl From<QuestRepr> for Quest {
+    fn from(repr: QuestRepr) -> Self {
+        match repr {
+            QuestRepr::Caption(caption) => Self {
+                caption,
+                giver: None,
+                accepted: None,
+                length: None,
+            },
+            QuestRepr::Quest {
+                caption,
+                giver,
+                accepted,
+                length,
+            } => Self {
+                caption,
+                giver,
+                accepted,
+                length,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct QuestBook {
-    quests: VecDeque<String>,
+    quests: VecDeque<Quest>,
+    #[serde(default)]
+    total: usize,
     act: i32,
+    /// The climate the current act is set in, the prologue and acts from older saves have none
+    #[serde(default)]
+    theme: Option<config::Climate>,
     monster: Option<config::Monster>,
     pub plot: Bar,
     pub quest: Bar,
 }
 
+impl Default for QuestBook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl QuestBook {
     const MAX_QUESTS: usize = 100;
 
     pub fn new() -> Self {
         Self {
             quests: VecDeque::new(),
+            total: 0,
             act: 0,
+            theme: None,
             monster: None,
             plot: Bar::with_max(1.0),
             quest: Bar::with_max(1.0),
         }
     }
 
+    /// Go back to the prologue, keeping the quest history
+    pub fn restart(&mut self) {
+        self.act = 0;
+        self.theme = None;
+        self.plot = Bar::with_max(1.0);
+    }
+
     pub fn next_act(&mut self) {
         self.act += 1;
     }
 
-    pub fn add_quest(&mut self, quest: &str) {
+    pub const fn theme(&self) -> Option<config::Climate> {
+        self.theme
+    }
+
+    pub fn add_quest(&mut self, caption: &str, giver: impl Into<Option<String>>, accepted: f32) {
         while self.quests.len() >= Self::MAX_QUESTS {
             self.quests.pop_front();
         }
-        self.quests.push_back(quest.to_string());
+        self.quests.push_back(Quest {
+            caption: caption.to_string(),
+            giver: giver.into(),
+            accepted: Some(accepted),
+            // the quest bar was just reset for this one
+            length: Some(self.quest.max),
+        });
+        self.total += 1;
+    }
+
+    /// Roughly how much game time the current quest has left, going by how fast it's progressed
+    pub fn remaining(&self, elapsed: f32) -> Option<f32> {
+        let age = self.current_quest()?.age(elapsed)?;
+        (self.quest.pos > 0.0).then(|| age * self.quest.remaining() / self.quest.pos)
     }
 
-    pub fn current_quest(&self) -> Option<&str> {
-        self.quests.back().map(|s| &**s)
+    /// A quest giver from one of the recent quests, so the same npc can come back with more work
+    fn returning_giver(&self, rng: &Rand) -> Option<String> {
+        const RECENT: usize = 10;
+
+        let givers = self
+            .quests
+            .iter()
+            .rev()
+            .take(RECENT)
+            .filter_map(|quest| quest.giver.as_ref())
+            .collect::<Vec<_>>();
+        (!givers.is_empty()).then(|| givers.choice(rng).to_string())
+    }
+
+    /// The total number of quests ever given, including ones no longer in the book
+    pub const fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn current_quest(&self) -> Option<&Quest> {
+        self.quests.back()
     }
 
     pub const fn act(&self) -> i32 {
         self.act
     }
 
-    pub fn quests(&self) -> impl Iterator<Item = &str> + ExactSizeIterator {
-        self.quests.iter().map(|s| &**s)
+    pub fn quests(&self) -> impl ExactSizeIterator<Item = &Quest> {
+        self.quests.iter()
     }
 
-    pub fn completed_quests(&self) -> impl Iterator<Item = &str> + ExactSizeIterator {
+    /// Drop all but the newest `keep` quests
+    pub fn truncate(&mut self, keep: usize) {
+        let drop = self.quests.len().saturating_sub(keep.max(1));
+        self.quests.drain(..drop);
+    }
+
+    pub fn completed_quests(&self) -> impl ExactSizeIterator<Item = &Quest> {
         let n = self.quests.len().saturating_sub(1);
         self.quests().take(n)
     }
 }
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct Spell {
     name: String,
     level: i32,
 }
 
-#[derive(Default, Debug, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Default, Debug, serde::Deserialize, serde::Serialize)]
 pub struct SpellBook {
     spells: Vec<Spell>,
 }
@@ -732,7 +2643,7 @@ impl SpellBook {
         });
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (&str, i32)> + ExactSizeIterator {
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, i32)> {
         self.spells
             .iter()
             .map(|Spell { name, level }| (&**name, *level))
@@ -743,13 +2654,13 @@ impl SpellBook {
     }
 }
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct InventoryItem {
     name: String,
     quantity: usize,
 }
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct Inventory {
     capacity: usize,
     gold: isize,
@@ -767,7 +2678,7 @@ impl Inventory {
         }
     }
 
-    pub fn items(&self) -> impl Iterator<Item = (&String, &usize)> + ExactSizeIterator {
+    pub fn items(&self) -> impl ExactSizeIterator<Item = (&String, &usize)> {
         self.items
             .iter()
             .map(|InventoryItem { name, quantity }| (name, quantity))
@@ -777,8 +2688,13 @@ impl Inventory {
         self.items.len()
     }
 
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     pub fn set_capacity(&mut self, cap: usize) {
         self.capacity = cap;
+        self.encumbrance.max = cap as _;
     }
 
     #[must_use]
@@ -814,11 +2730,37 @@ impl Inventory {
         self.update_bar();
     }
 
+    pub fn consume(&mut self, filter: impl Fn(&str) -> bool, mut quantity: usize) {
+        for item in self.items.iter_mut().filter(|item| filter(&item.name)) {
+            let n = item.quantity.min(quantity);
+            item.quantity -= n;
+            quantity -= n;
+            if quantity == 0 {
+                break;
+            }
+        }
+
+        self.items.retain(|item| item.quantity > 0);
+        self.update_bar();
+    }
+
     pub fn pop(&mut self) {
         let _item = self.items.pop().expect("inventory not empty");
         self.update_bar();
     }
 
+    pub fn remove(&mut self, index: usize) {
+        self.items.remove(index);
+        self.update_bar();
+    }
+
+    /// The last item that can be sold, keeping hold of the [map](Expedition::MAP)
+    pub fn last_sellable(&self) -> Option<usize> {
+        self.items
+            .iter()
+            .rposition(|item| item.name != Expedition::MAP)
+    }
+
     fn update_bar(&mut self) {
         self.encumbrance.pos = self
             .items
@@ -836,43 +2778,303 @@ impl std::ops::Index<usize> for Inventory {
     }
 }
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+/// A piece of equipment: what it's made from, anything that was done to it, and a bonus on top
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(from = "EquipmentPieceRepr")]
+pub struct EquipmentPiece {
+    pub base: EquipmentPreset,
+    /// In the order they're shown, so the most recent comes first
+    pub modifiers: Vec<config::Modifier>,
+    pub bonus: i32,
+}
+
+impl EquipmentPiece {
+    pub const fn new(base: EquipmentPreset) -> Self {
+        Self {
+            base,
+            modifiers: Vec::new(),
+            bonus: 0,
+        }
+    }
+
+    /// Work out a piece from its display name: the bonus in front, any modifiers, and the item
+    /// itself. Words that aren't known modifiers are kept as part of the item's name
+    pub fn parse(name: &str) -> Self {
+        let (bonus, name) = match name.split_once(' ') {
+            Some((bonus, rest)) if bonus.starts_with(['+', '-']) => {
+                (bonus.parse().unwrap_or(0), rest)
+            }
+            _ => (0, name),
+        };
+
+        let content = config::content();
+        let presets = content
+            .weapons
+            .iter()
+            .chain(&content.shields)
+            .chain(&content.armors);
+        let base = presets
+            .filter(|preset| name.ends_with(&*preset.name))
+            .max_by_key(|preset| preset.name.len());
+        let (rest, base) = match base {
+            Some(preset) => (&name[..name.len() - preset.name.len()], preset.clone()),
+            // not from this content pack, so the last word is the item
+            None => {
+                let (rest, item) = name.rsplit_once(' ').unwrap_or(("", name));
+                let base = EquipmentPreset {
+                    name: item.to_string().into(),
+                    quality: 0,
+                };
+                (rest, base)
+            }
+        };
+
+        let modifiers = content
+            .offense_attributes
+            .iter()
+            .chain(&content.defense_attributes)
+            .chain(&content.offense_quirks)
+            .chain(&content.defense_quirks);
+
+        let mut piece = Self::new(base);
+        piece.bonus = bonus;
+        let mut unknown = vec![];
+        for word in rest.split_whitespace() {
+            match modifiers.clone().find(|modifier| modifier.name == word) {
+                Some(modifier) => piece.modifiers.push(modifier.clone()),
+                None => unknown.push(word),
+            }
+        }
+        if !unknown.is_empty() {
+            unknown.push(&piece.base.name);
+            piece.base.name = unknown.join(" ").into();
+        }
+        piece
+    }
+
+    pub fn quality(&self) -> i32 {
+        self.bonus
+            + self.base.quality
+            + self
+                .modifiers
+                .iter()
+                .map(|modifier| modifier.quality)
+                .sum::<i32>()
+    }
+
+    /// What the piece is called, e.g. `+2 Polished Pronged Eelspear`
+    pub fn display(&self) -> String {
+        let bonus = (self.bonus != 0).then(|| format!("{:+}", self.bonus));
+        bonus
+            .as_deref()
+            .into_iter()
+            .chain(self.modifiers.iter().map(|modifier| &*modifier.name))
+            .chain(std::iter::once(&*self.base.name))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// What a merchant gives for it when it's replaced
+    pub fn resale(&self) -> isize {
+        equipment_price(self.quality().max(0) as _) / 4
+    }
+}
+
+// older saves only stored the display name
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum EquipmentPieceRepr {
+    Name(String),
+    Piece {
+        base: EquipmentPreset,
+        modifiers: Vec<config::Modifier>,
+        bonus: i32,
+    },
+}
+
+impl From<EquipmentPieceRepr> for EquipmentPiece {
+    fn from(repr: EquipmentPieceRepr) -> Self {
+        match repr {
+            EquipmentPieceRepr::Name(name) => Self::parse(&name),
+            EquipmentPieceRepr::Piece {
+                base,
+                modifiers,
+                bonus,
+            } => Self {
+                base,
+                modifiers,
+                bonus,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(from = "EquipmentRepr")]
 pub struct Equipment {
-    items: BTreeMap<config::Equipment, String>,
+    items: BTreeMap<config::Equipment, EquipmentPiece>,
     best: String,
+    /// Names the player gave to slots, shown instead of whatever is in them
+    #[serde(default)]
+    styles: BTreeMap<config::Equipment, String>,
+    /// What the pieces in each slot are called, kept alongside them for [`Equipment::get`]
+    #[serde(skip)]
+    names: BTreeMap<config::Equipment, String>,
 }
 
-impl Default for Equipment {
-    fn default() -> Self {
+#[derive(serde::Deserialize)]
+struct EquipmentRepr {
+    items: BTreeMap<config::Equipment, EquipmentPiece>,
+    best: String,
+    #[serde(default)]
+    styles: BTreeMap<config::Equipment, String>,
+}
+
+impl From<EquipmentRepr> for Equipment {
+    fn from(repr: EquipmentRepr) -> Self {
         Self {
-            items: [
-                (config::Equipment::Weapon, "Sharp Rock".into()),
-                (config::Equipment::Hauberk, "-3 Burlap".into()),
-            ]
-            .into_iter()
-            .collect(),
-            best: "Sharp Rock".into(),
+            names: Equipment::names(&repr.items),
+            items: repr.items,
+            best: repr.best,
+            styles: repr.styles,
         }
     }
 }
 
+impl Default for Equipment {
+    fn default() -> Self {
+        Self::starting(config::DEFAULT_KIT, 0)
+    }
+}
+
 impl Equipment {
+    /// The longest name a slot can be restyled to
+    pub const MAX_STYLE_LENGTH: usize = 40;
+
+    /// Equipment from a starting kit, with `bonus` added to the quality of everything in it
+    pub fn starting(kit: &[(config::Equipment, config::EquipmentPreset)], bonus: i32) -> Self {
+        // the quality of a kit is how much better or worse it is than usual, not its base's
+        let items = kit
+            .iter()
+            .map(|(slot, preset)| {
+                let mut piece = EquipmentPiece::new(config::EquipmentPreset {
+                    name: preset.name.clone(),
+                    quality: 0,
+                });
+                piece.bonus = preset.quality + bonus;
+                (*slot, piece)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let best = items
+            .get(&config::Equipment::Weapon)
+            .or_else(|| items.values().next())
+            .map(EquipmentPiece::display)
+            .unwrap_or_default();
+        Self {
+            names: Self::names(&items),
+            items,
+            best,
+            styles: BTreeMap::new(),
+        }
+    }
+
+    fn names(
+        items: &BTreeMap<config::Equipment, EquipmentPiece>,
+    ) -> BTreeMap<config::Equipment, String> {
+        items
+            .iter()
+            .map(|(slot, piece)| (*slot, piece.display()))
+            .collect()
+    }
+
+    /// Put the piece called `name` in `ty`, see [`EquipmentPiece::parse`]
     pub fn add(&mut self, ty: config::Equipment, name: impl ToString) {
-        *self.items.entry(ty).or_default() = name.to_string();
+        self.equip(ty, EquipmentPiece::parse(&name.to_string()));
+    }
 
+    /// Put `piece` in `ty`, returning what was there before
+    pub fn equip(
+        &mut self,
+        ty: config::Equipment,
+        piece: EquipmentPiece,
+    ) -> Option<EquipmentPiece> {
         self.best = format!(
             "{name} {item}",
-            name = name.to_string(),
+            name = piece.display(),
             item = if matches!(ty, config::Equipment::Weapon | config::Equipment::Shield) {
                 ""
             } else {
                 ty.as_str()
             }
-        )
+        );
+        self.names.insert(ty, piece.display());
+        self.items.insert(ty, piece)
+    }
+
+    /// The name of what's in `ty`
+    pub fn get(&self, ty: config::Equipment) -> Option<&str> {
+        self.names.get(&ty).map(|s| &**s)
+    }
+
+    pub fn piece(&self, ty: config::Equipment) -> Option<&EquipmentPiece> {
+        self.items.get(&ty)
+    }
+
+    /// The quality of what's in `ty`
+    pub fn quality(&self, ty: config::Equipment) -> i32 {
+        self.piece(ty).map_or(0, EquipmentPiece::quality)
+    }
+
+    /// The total quality of everything that's equipped
+    pub fn gear_score(&self) -> i32 {
+        self.items.values().map(EquipmentPiece::quality).sum()
+    }
+
+    /// The most recently upgraded piece of equipment
+    pub fn best(&self) -> &str {
+        self.best.trim_end()
+    }
+
+    pub fn enhance(&mut self, ty: config::Equipment, modifier: &config::Modifier) {
+        if let Some(piece) = self.piece(ty) {
+            let mut piece = piece.clone();
+            piece.modifiers.insert(0, modifier.clone());
+            self.equip(ty, piece);
+        }
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (config::Equipment, &str)> {
+        self.names.iter().map(|(eq, name)| (*eq, &**name))
+    }
+
+    pub fn pieces(&self) -> impl ExactSizeIterator<Item = (config::Equipment, &EquipmentPiece)> {
+        self.items.iter().map(|(eq, piece)| (*eq, piece))
+    }
+
+    /// Show `slot` as `name` from now on, whatever ends up in it. An empty name clears the style
+    pub fn restyle(&mut self, slot: config::Equipment, name: &str) {
+        let name = name
+            .trim()
+            .chars()
+            .take(Self::MAX_STYLE_LENGTH)
+            .collect::<String>();
+        if name.is_empty() {
+            self.styles.remove(&slot);
+        } else {
+            self.styles.insert(slot, name);
+        }
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (config::Equipment, &str)> + ExactSizeIterator {
-        self.items.iter().map(|(eq, name)| (*eq, &**name))
+    /// The name the player gave `slot`, if they gave it one
+    pub fn style(&self, slot: config::Equipment) -> Option<&str> {
+        self.styles.get(&slot).map(String::as_str)
+    }
+
+    /// What to call the piece in `slot`: its style, or the piece's own name
+    pub fn display(&self, slot: config::Equipment) -> Option<String> {
+        let name = self.get(slot)?;
+        Some(self.style(slot).unwrap_or(name).to_string())
     }
 }
 
@@ -880,11 +3082,27 @@ impl Equipment {
 pub struct Bar {
     pub pos: f32,
     pub max: f32,
+    /// The game time the bar last filled up at, for frontends to flash it
+    #[serde(default)]
+    pub completed_at: Option<f32>,
 }
 
 impl Bar {
     pub const fn with_max(max: f32) -> Self {
-        Self { pos: 0.0, max }
+        Self {
+            pos: 0.0,
+            max,
+            completed_at: None,
+        }
+    }
+
+    pub fn mark_completed(&mut self, elapsed: f32) {
+        self.completed_at = Some(elapsed);
+    }
+
+    /// How much game time has passed since the bar last filled up
+    pub fn since_completed(&self, elapsed: f32) -> Option<f32> {
+        self.completed_at.map(|at| (elapsed - at).max(0.0))
     }
 
     pub fn remaining(&self) -> f32 {
@@ -899,13 +3117,563 @@ impl Bar {
         self.pos >= self.max
     }
 
-    pub fn reset(&mut self, max: f32) {
-        self.max = max;
-        self.pos = 0.0;
+    pub fn reset(&mut self, max: f32) {
+        self.max = max;
+        self.pos = 0.0;
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Expedition {
+    pub treasure: String,
+    pub bar: Bar,
+}
+
+impl Expedition {
+    /// Found in the inventory for as long as the expedition lasts. It's never sold
+    pub const MAP: &'static str = "Treasure Map";
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Dungeon {
+    pub name: String,
+    pub bar: Bar,
+    pub loot: Vec<String>,
+}
+
+/// A gauntlet of ever stronger monsters to beat before the time runs out
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Arena {
+    pub name: String,
+    /// Rounds won
+    pub bar: Bar,
+    /// The game time the gauntlet started at
+    pub started: f32,
+    pub time_limit: f32,
+    pub score: usize,
+}
+
+impl Arena {
+    pub fn time_left(&self, elapsed: f32) -> f32 {
+        self.started + self.time_limit - elapsed
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Effect {
+    pub stat: Stat,
+    pub amount: isize,
+    pub remaining: f32,
+    /// The weakness from being left for dead, which isn't a wound of its own
+    #[serde(default)]
+    pub recovery: bool,
+}
+
+#[derive(Clone, Default, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Statistics {
+    pub hazards_avoided: usize,
+    pub hazards_suffered: usize,
+    /// Monsters that ran away mid-fight
+    #[serde(default)]
+    pub monsters_fled: usize,
+    #[serde(default)]
+    pub materials_gathered: usize,
+    /// Times the hero was left for dead and recovered
+    #[serde(default)]
+    pub last_stands: usize,
+    /// Real seconds spent playing, not counting time caught up on or paused
+    #[serde(default)]
+    pub played: f64,
+    /// Fights won, including against passers-by
+    #[serde(default)]
+    pub kills: usize,
+    #[serde(default)]
+    pub quests_completed: usize,
+    /// Gold from selling, quests and bounties. Withdrawals from the bank don't count
+    #[serde(default)]
+    pub gold_earned: isize,
+    #[serde(default)]
+    pub gold_spent: isize,
+    #[serde(default)]
+    pub items_sold: usize,
+    #[serde(default)]
+    pub tasks_finished: usize,
+    /// How each hazard went, by name
+    #[serde(default)]
+    pub hazards: BTreeMap<String, HazardTally>,
+}
+
+#[derive(Copy, Clone, Default, Debug, serde::Deserialize, serde::Serialize)]
+pub struct HazardTally {
+    pub avoided: usize,
+    pub suffered: usize,
+}
+
+impl Statistics {
+    /// A label and a value for everything that's tracked, for showing as a table
+    pub fn table(&self, elapsed: f32) -> Vec<(&'static str, String)> {
+        vec![
+            ("Play time", Time::compact(self.played as f32)),
+            ("Game time", Time::compact(elapsed)),
+            ("Tasks finished", self.tasks_finished.to_string()),
+            ("Kills", self.kills.to_string()),
+            ("Monsters fled", self.monsters_fled.to_string()),
+            ("Quests completed", self.quests_completed.to_string()),
+            ("Gold earned", self.gold_earned.to_string()),
+            ("Gold spent", self.gold_spent.to_string()),
+            ("Items sold", self.items_sold.to_string()),
+            ("Materials gathered", self.materials_gathered.to_string()),
+            ("Hazards avoided", self.hazards_avoided.to_string()),
+            ("Hazards suffered", self.hazards_suffered.to_string()),
+            ("Last stands", self.last_stands.to_string()),
+        ]
+    }
+}
+
+/// The monsters a hero has killed
+#[derive(Clone, Default, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Bestiary {
+    entries: BTreeMap<String, BestiaryEntry>,
+    /// Unique drops that have already been looted, so they never drop again
+    #[serde(default)]
+    uniques: BTreeSet<String>,
+    /// Kills across every monster of each family
+    #[serde(default)]
+    families: BTreeMap<config::Family, usize>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct BestiaryEntry {
+    pub kills: usize,
+    /// The game time of the first kill
+    pub first_kill: f32,
+    /// The rarest thing looted from this monster, from its [`LootTable`](config::LootTable)
+    #[serde(default)]
+    pub best_loot: Option<BestLoot>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct BestLoot {
+    pub item: String,
+    pub weight: usize,
+    pub unique: bool,
+}
+
+impl Bestiary {
+    /// Monsters are this many kills further from the hero's level when picking one to fight, so
+    /// heroes don't spend an entire act farming the same thing
+    pub const FARMED: usize = 20;
+    /// Kills of a family needed for each slayer rank
+    pub const SLAYER_KILLS: [usize; 3] = [100, 500, 2000];
+    /// Fights against a family are this many percent shorter for each slayer rank
+    pub const SLAYER_PERCENT: u32 = 5;
+
+    /// Count a kill, returning the slayer title it earned, if any
+    pub fn record(
+        &mut self,
+        monster: &str,
+        family: Option<config::Family>,
+        elapsed: f32,
+    ) -> Option<String> {
+        self.entries
+            .entry(monster.to_string())
+            .or_insert(BestiaryEntry {
+                kills: 0,
+                first_kill: elapsed,
+                best_loot: None,
+            })
+            .kills += 1;
+
+        let family = family?;
+        let rank = self.slayer_rank(family);
+        *self.families.entry(family).or_default() += 1;
+        (self.slayer_rank(family) > rank)
+            .then(|| family.slayer_title(self.slayer_rank(family)))
+            .flatten()
+    }
+
+    pub fn family_kills(&self, family: config::Family) -> usize {
+        self.families.get(&family).copied().unwrap_or(0)
+    }
+
+    /// How many of [`Self::SLAYER_KILLS`] the hero has reached for `family`
+    pub fn slayer_rank(&self, family: config::Family) -> usize {
+        let kills = self.family_kills(family);
+        Self::SLAYER_KILLS
+            .iter()
+            .take_while(|&&needed| kills >= needed)
+            .count()
+    }
+
+    /// The title for the family the hero is the best slayer of
+    pub fn title(&self) -> Option<String> {
+        let family = config::Family::ALL
+            .into_iter()
+            .max_by_key(|&family| (self.slayer_rank(family), self.family_kills(family)))?;
+        family.slayer_title(self.slayer_rank(family))
+    }
+
+    /// Remember `drop` if it's rarer than anything looted from `monster` before
+    pub fn loot(&mut self, monster: &str, drop: &config::Drop) {
+        if drop.unique {
+            self.uniques.insert(drop.item.to_string());
+        }
+
+        let Some(entry) = self.entries.get_mut(monster) else {
+            return;
+        };
+        let rarer = entry.best_loot.as_ref().is_none_or(|best| {
+            (drop.unique, std::cmp::Reverse(drop.weight))
+                > (best.unique, std::cmp::Reverse(best.weight))
+        });
+        if rarer {
+            entry.best_loot = Some(BestLoot {
+                item: drop.item.to_string(),
+                weight: drop.weight,
+                unique: drop.unique,
+            })
+        }
+    }
+
+    /// Whether the unique `item` has already been looted
+    pub fn looted(&self, item: &str) -> bool {
+        self.uniques.contains(item)
+    }
+
+    pub fn kills(&self, monster: &str) -> usize {
+        self.entries.get(monster).map_or(0, |entry| entry.kills)
+    }
+
+    pub fn total_kills(&self) -> usize {
+        self.entries.values().map(|entry| entry.kills).sum()
+    }
+
+    /// Every monster killed so far, by name
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, &BestiaryEntry)> {
+        self.entries.iter().map(|(name, entry)| (&**name, entry))
+    }
+
+    /// Every monster killed so far, in the order they were first killed
+    pub fn discovered(&self) -> impl Iterator<Item = (&str, &BestiaryEntry)> {
+        let mut entries = self.iter().collect::<Vec<_>>();
+        entries.sort_by(|(_, l), (_, r)| l.first_kill.total_cmp(&r.first_kill));
+        entries.into_iter()
+    }
+}
+
+/// What an ascended hero carries over from their past lives
+#[derive(Clone, Default, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Legacy {
+    pub ascensions: usize,
+    /// The level each past life ended at, oldest first
+    pub levels: Vec<usize>,
+}
+
+impl Legacy {
+    /// Prime stats grow by this percent with every ascension
+    pub const STAT_PERCENT: usize = 10;
+    /// Experience comes this many percent faster for every ascension
+    pub const EXP_PERCENT: usize = 20;
+
+    /// How fast experience bars fill, in percent
+    pub const fn exp_percent(&self) -> usize {
+        100 + Self::EXP_PERCENT * self.ascensions
+    }
+}
+
+impl std::fmt::Display for Legacy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Ascension {}, {}% experience",
+            Roman::from_i32(self.ascensions as i32),
+            self.exp_percent()
+        )
+    }
+}
+
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+struct Progress {
+    completed: usize,
+    has_task: bool,
+    pos: u32,
+    max: u32,
+}
+
+/// Notices when the simulation stops making progress, e.g. a task bar that never fills
+#[derive(Default)]
+struct Watchdog {
+    last: Progress,
+    stalled: usize,
+    completed_at: f32,
+}
+
+impl Watchdog {
+    /// Ticks without anything changing
+    const MAX_STALLED: usize = 1000;
+    /// Game time without finishing a task, no task takes anywhere near this long
+    const MAX_TASK_TIME: f32 = 60.0 * 60.0;
+
+    fn new(elapsed: f32) -> Self {
+        Self {
+            completed_at: elapsed,
+            ..Self::default()
+        }
+    }
+
+    fn is_stalled(&mut self, progress: Progress, elapsed: f32) -> bool {
+        if progress.completed != self.last.completed {
+            self.completed_at = elapsed;
+        }
+
+        if progress == self.last {
+            self.stalled += 1;
+        } else {
+            self.last = progress;
+            self.stalled = 0;
+        }
+        self.stalled >= Self::MAX_STALLED || elapsed - self.completed_at > Self::MAX_TASK_TIME
+    }
+}
+
+/// Announces the start of an act, e.g. "Act IV — The Frozen Marches"
+#[derive(Debug, Clone)]
+pub struct TitleCard {
+    pub act: i32,
+    pub title: String,
+}
+
+impl TitleCard {
+    fn new(act: i32, recent: &mut Recent, rng: &Rand) -> Self {
+        let content = config::content();
+        Self {
+            act,
+            title: format!(
+                "The {} {}",
+                rng.choice_fresh(&content.act_title_adjectives, "act_adjective", recent),
+                rng.choice_fresh(&content.act_title_places, "act_place", recent)
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for TitleCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} — {}", act_name(self.act), self.title)
+    }
+}
+
+/// Where gold beyond what the hero needs on hand is kept, earning simple interest every hour of
+/// game time. The hero drops in on the way out of town to deposit or withdraw
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct Bank {
+    balance: isize,
+    /// The part of the balance that was deposited, rather than paid as interest. Older saves
+    /// don't have it, and count the whole balance
+    #[serde(default)]
+    principal: Option<isize>,
+    /// The game time interest was last paid at
+    paid: f32,
+}
+
+impl Bank {
+    pub const PERIOD: f32 = 60.0 * 60.0;
+    /// The interest paid every period, on the deposits but not on earlier interest
+    pub const RATE: f32 = 0.002;
+    /// Carried gold beyond this many times the price of the next piece of equipment is deposited
+    pub const KEEP: isize = 3;
+
+    pub const fn balance(&self) -> isize {
+        self.balance
+    }
+
+    /// The gold a visit would hand to a hero carrying `gold` and saving up for `price`, negative
+    /// for a deposit. This is zero if there's no reason to visit
+    pub fn transfer(&self, gold: isize, price: isize) -> isize {
+        let keep = price * Self::KEEP;
+        if gold > keep {
+            keep - gold
+        } else if gold <= price && gold + self.balance > price {
+            (keep - gold).min(self.balance)
+        } else {
+            0
+        }
+    }
+
+    fn principal(&self) -> isize {
+        self.principal.unwrap_or(self.balance)
+    }
+
+    fn settle(&mut self, amount: isize) {
+        // withdrawals come out of the deposits first
+        let principal = self.principal() - amount;
+        self.balance -= amount;
+        self.principal = Some(principal.clamp(0, self.balance.max(0)));
+    }
+
+    fn accrue(&mut self, elapsed: f32) {
+        let periods = ((elapsed - self.paid) / Self::PERIOD).floor();
+        if periods < 1.0 {
+            return;
+        }
+        self.paid += periods * Self::PERIOD;
+
+        let interest = (self.principal() as f64 * f64::from(Self::RATE)).floor() as isize;
+        self.balance = self
+            .balance
+            .saturating_add(interest.saturating_mul(periods as isize));
+    }
+}
+
+/// What the merchants pay for loot and charge for equipment. Each kind of [`Goods`] has a rate
+/// that drifts every hour of game time, wandering off and slowly settling back to normal
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Market {
+    rates: [f32; Goods::ALL.len()],
+    /// The game time the rates last drifted at
+    updated: f32,
+}
+
+impl Default for Market {
+    fn default() -> Self {
+        Self {
+            rates: [1.0; Goods::ALL.len()],
+            updated: 0.0,
+        }
+    }
+}
+
+impl Market {
+    pub const PERIOD: f32 = 60.0 * 60.0;
+    pub const MIN_RATE: f32 = 0.5;
+    pub const MAX_RATE: f32 = 2.0;
+    /// A rate at least this high makes the market hot for those goods
+    pub const HOT: f32 = 1.25;
+    /// And one at most this low makes it slow
+    pub const SLOW: f32 = 0.8;
+
+    pub fn rate(&self, goods: Goods) -> f32 {
+        self.rates[goods as usize]
+    }
+
+    pub fn rates(&self) -> impl Iterator<Item = (Goods, f32)> + '_ {
+        Goods::ALL.into_iter().zip(self.rates.iter().copied())
+    }
+
+    /// `base` at the going rate for `goods`
+    pub fn price(&self, goods: Goods, base: isize) -> isize {
+        (base as f32 * self.rate(goods)).round() as isize
+    }
+
+    /// The most remarkable rate, like "The market is hot for relics today"
+    pub fn outlook(&self) -> Option<String> {
+        let by_rate = |a: &(Goods, f32), b: &(Goods, f32)| a.1.total_cmp(&b.1);
+        match (self.rates().max_by(by_rate), self.rates().min_by(by_rate)) {
+            (Some((goods, rate)), _) if rate >= Self::HOT => {
+                Some(format!("The market is hot for {goods} today"))
+            }
+            (_, Some((goods, rate))) if rate <= Self::SLOW => {
+                Some(format!("The market is slow for {goods} today"))
+            }
+            _ => None,
+        }
+    }
+
+    fn drift(&mut self, elapsed: f32, rng: &Rand) {
+        while elapsed - self.updated >= Self::PERIOD {
+            self.updated += Self::PERIOD;
+            for rate in &mut self.rates {
+                let settled = 1.0 + (*rate - 1.0) * 0.9;
+                let swing = 0.85 + 0.3 * rng.below(101) as f32 / 100.0;
+                *rate = (settled * swing).clamp(Self::MIN_RATE, Self::MAX_RATE);
+            }
+        }
+    }
+}
+
+/// A named monster from a cinematic that got away, and comes back stronger in later acts
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Nemesis {
+    pub name: String,
+    pub monster: String,
+    pub level: usize,
+    /// How many times it's been fought
+    pub encounters: usize,
+    /// The act it was last fought in
+    pub act: i32,
+}
+
+impl Nemesis {
+    /// It's slain for good by this encounter at the latest
+    pub const MAX_ENCOUNTERS: usize = 5;
+    const TITLES: [&'static str; Self::MAX_ENCOUNTERS] =
+        ["", "Vengeful ", "Dread ", "Undying ", "Eternal "];
+
+    fn new(level: usize, act: i32, rating: Rating, rng: &Rand) -> Self {
+        let monster = unnamed_monster(level, 4, rating, &Bestiary::default(), rng);
+        Self {
+            name: generate_name(None, rng),
+            monster: monster.name.into_owned(),
+            level,
+            encounters: 0,
+            act,
+        }
+    }
+
+    /// Come back in `act`, stronger for each time it was fought
+    fn resurface(&mut self, level: usize, act: i32) {
+        self.level = self.level.max(level) + 2;
+        self.act = act;
+    }
+}
+
+impl std::fmt::Display for Nemesis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = Self::TITLES[self.encounters.min(Self::MAX_ENCOUNTERS - 1)];
+        write!(f, "{} the {title}{}", self.name, self.monster)
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Rival {
+    pub name: String,
+    pub class: Class,
+    pub level: usize,
+    pub exp_bar: Bar,
+    pace: usize,
+    ahead: bool,
+    showdown: usize,
+}
+
+impl Rival {
+    pub fn new(level: usize, rng: &Rand) -> Self {
+        let level = level.saturating_sub(rng.below(3)).max(1);
+        Self {
+            name: generate_name(None, rng),
+            class: config::content().classes.choice(rng).clone(),
+            level,
+            exp_bar: Bar::with_max(level_up_time(level).as_secs() as f32),
+            pace: 50 + rng.below(51),
+            ahead: false,
+            showdown: level,
+        }
+    }
+
+    pub const fn is_ahead(&self) -> bool {
+        self.ahead
+    }
+
+    fn advance(&mut self, dt: f32) {
+        self.exp_bar.increment(dt * self.pace as f32 / 100.0);
+        if self.exp_bar.is_done() {
+            self.level += 1;
+            self.exp_bar
+                .reset(level_up_time(self.level).as_secs() as f32);
+        }
     }
 }
 
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 pub struct Player {
     pub name: String,
 
@@ -917,6 +3685,8 @@ pub struct Player {
 
     pub stats: Stats,
     pub elapsed: f32,
+    #[serde(default = "default_time_scale")]
+    pub time_scale: f32,
 
     pub quest_book: QuestBook,
     pub spell_book: SpellBook,
@@ -928,17 +3698,89 @@ pub struct Player {
 
     pub task_bar: Bar,
     pub exp_bar: Bar,
+
+    #[serde(default)]
+    pub rival: Option<Rival>,
+    #[serde(default)]
+    pub nemesis: Option<Nemesis>,
+    /// Where the hero is, and where they could go
+    #[serde(default)]
+    pub world: WorldMap,
+    #[serde(default)]
+    pub market: Market,
+    #[serde(default)]
+    pub bank: Bank,
+    #[serde(default)]
+    pub expedition: Option<Expedition>,
+    #[serde(default)]
+    pub dungeon: Option<Dungeon>,
+    #[serde(default)]
+    pub arena: Option<Arena>,
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+    #[serde(default)]
+    pub statistics: Statistics,
+    #[serde(default)]
+    pub journal: Journal,
+    #[serde(default)]
+    pub rating: Rating,
+    /// How the stats were rolled, see [`StatsBuilder`]
+    #[serde(default)]
+    pub roll_mode: RollMode,
+    /// Set when a save of this player failed verification, see [`crate::integrity`]
+    #[serde(default)]
+    pub tampered: bool,
+    /// Seconds since the unix epoch when the player was last saved, for [`Simulation::catch_up`]
+    #[serde(default)]
+    pub last_seen: Option<u64>,
+    #[serde(default)]
+    pub legacy: Legacy,
+    #[serde(default)]
+    pub bestiary: Bestiary,
+    #[serde(default)]
+    pub board: QuestBoard,
+    /// Flavor text that was shown lately, so it isn't repeated every act
+    #[serde(default)]
+    pub recent: Recent,
+    /// Whatever the user wants to remember about this character
+    #[serde(default)]
+    pub notes: String,
+    /// Words picked at creation that turn up in quests and cinematics, see [`lingo::themes`]
+    #[serde(default)]
+    pub themes: Vec<String>,
+    /// The seed and rules this player is locked to, if they're an ironman
+    #[serde(default)]
+    pub ironman: Option<Ironman>,
+    /// The ids of the [codex](crate::codex) entries that were announced. Older saves don't have
+    /// them until the first check
+    #[serde(default)]
+    pub unlocked: Option<BTreeSet<String>>,
+    /// The formulas this hero's pacing comes from, see [`Simulation::set_tuning`]
+    #[serde(default)]
+    pub tuning: config::Tuning,
+    /// The seed the hero was first played with, see [`Simulation::with_seed`]. Older saves don't
+    /// have one until they're next played
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 impl Player {
     pub fn new(name: impl Into<String>, race: Race, class: Class, stats: Stats) -> Self {
-        let (spell_book, equipment, task, queue) = <_>::default();
-
-        Self {
-            inventory: Inventory::new(10 + stats[Stat::Strength]),
+        let (spell_book, task, queue) = <_>::default();
+        let (rival, expedition, dungeon, arena) = <_>::default();
+        let (effects, statistics, journal, rating, tampered, last_seen) = <_>::default();
+        let (legacy, bestiary, board, recent, notes, nemesis) = <_>::default();
+        let (roll_mode, world, market, bank, themes, ironman) = <_>::default();
+        let equipment = <_>::default();
+        let tuning = config::Tuning::default();
+        let inventory = Inventory::new(tuning.capacity(stats[Stat::Strength]));
+
+        let mut player = Self {
+            inventory,
             name: name.into(),
             // birthday: OffsetDateTime::now_utc(),
             elapsed: 0.0,
+            time_scale: default_time_scale(),
             level: 1,
 
             race,
@@ -952,18 +3794,180 @@ impl Player {
             queue,
 
             task_bar: Bar::with_max(1.0),
-            exp_bar: Bar::with_max(level_up_time(1).as_secs() as f32),
+            exp_bar: Bar::with_max(tuning.level_up_time(1).as_secs() as f32),
+
+            rival,
+            nemesis,
+            world,
+            market,
+            bank,
+            expedition,
+            dungeon,
+            arena,
+            effects,
+            statistics,
+            journal,
+            rating,
+            roll_mode,
+            themes,
+            ironman,
+            // a new hero has nothing to catch up on
+            unlocked: Some(BTreeSet::new()),
+            tampered,
+            last_seen,
+            legacy,
+            bestiary,
+            board,
+            recent,
+            notes,
+            tuning,
+            seed: None,
+        };
+        player.outfit();
+        player
+    }
+
+    /// Give the hero the starting kit of their class and the provisions of their race, with the
+    /// gear made a little better for a poor roll. Front-ends that change the race, class or stats
+    /// after [`Player::new`] call this again once the hero is finished
+    pub fn outfit(&mut self) {
+        let content = config::content();
+        let kit = class_kit(&content, &self.class);
+
+        // a poor roll makes for a harder start, so make up for it with slightly better gear
+        let total = config::PRIME_STATS
+            .iter()
+            .map(|&stat| self.stats[stat] as i32)
+            .sum::<i32>();
+        self.equipment = Equipment::starting(kit, ((63 - total) / 9).clamp(-2, 2));
+
+        self.inventory = Inventory::new(self.tuning.capacity(self.stats[Stat::Strength]));
+        let provisions = content
+            .provisions
+            .iter()
+            .filter(|provisions| provisions.race == self.race.name)
+            .flat_map(|provisions| provisions.items.iter());
+        for item in provisions {
+            self.inventory.add_item(item, 1);
         }
     }
 
+    /// Start over at level 1 with the starting kit from the prologue, made stronger by the legacy
+    /// of this life. The stats, inventory, journal and rival are kept
+    pub fn ascend(&mut self) {
+        self.legacy.ascensions += 1;
+        self.legacy.levels.push(self.level);
+
+        for stat in config::PRIME_STATS {
+            let bonus = (self.stats[stat] * Legacy::STAT_PERCENT / 100).max(1);
+            self.stats.increment(stat, bonus);
+        }
+
+        self.level = 1;
+        self.exp_bar = Bar::with_max(self.tuning.level_up_time(1).as_secs() as f32);
+        // the slots keep the names the player gave them
+        let styles = std::mem::take(&mut self.equipment.styles);
+        self.equipment = Equipment::starting(class_kit(&config::content(), &self.class), 0);
+        self.equipment.styles = styles;
+        self.spell_book = SpellBook::default();
+        self.quest_book.restart();
+    }
+
+    /// A single number for comparing characters at a glance, counting their level, stats,
+    /// equipment and spells
+    pub fn power_level(&self) -> usize {
+        let stats = self
+            .stats
+            .iter()
+            .filter(|(stat, _)| !matches!(stat, Stat::HpMax | Stat::MpMax))
+            .map(|(_, value)| value)
+            .sum::<usize>();
+        let gear = self.equipment.gear_score().max(0) as usize;
+        let spells = self
+            .spell_book
+            .iter()
+            .map(|(_, level)| level.max(0) as usize)
+            .sum::<usize>();
+
+        10 * self.level + stats + 2 * gear + spells
+    }
+
+    /// Prune old history that nothing needs to keep running, to make saves smaller
+    pub fn compact(&mut self) {
+        const KEEP_JOURNAL: usize = 250;
+        const KEEP_QUESTS: usize = 25;
+
+        self.journal.truncate(KEEP_JOURNAL);
+        self.quest_book.truncate(KEEP_QUESTS);
+    }
+
     pub fn set_task(&mut self, task: Task) {
+        if self.task_bar.is_done() {
+            self.task_bar.mark_completed(self.elapsed);
+        }
         self.task_bar.reset(task.duration.as_secs_f32());
         self.task.replace(task);
     }
 
+    pub fn add_effect(&mut self, stat: Stat, amount: isize, duration: f32) {
+        self.push_effect(stat, amount, duration, false)
+    }
+
+    fn push_effect(&mut self, stat: Stat, amount: isize, duration: f32, recovery: bool) {
+        let amount = if amount < 0 {
+            -(self.stats.decrement(stat, amount.unsigned_abs()) as isize)
+        } else {
+            self.stats.increment(stat, amount as _);
+            amount
+        };
+
+        self.effects.push(Effect {
+            stat,
+            amount,
+            remaining: duration,
+            recovery,
+        })
+    }
+
+    /// Effects that are holding a stat down, like from failed hazards. Recovering from a last
+    /// stand doesn't count
+    pub fn wounds(&self) -> usize {
+        self.effects
+            .iter()
+            .filter(|effect| effect.amount < 0 && !effect.recovery)
+            .count()
+    }
+
+    /// Heal every wound straight away, giving back what they took
+    fn clear_wounds(&mut self) {
+        for effect in &mut self.effects {
+            if effect.amount < 0 && !effect.recovery {
+                effect.remaining = 0.0;
+            }
+        }
+        self.update_effects(0.0);
+    }
+
+    fn update_effects(&mut self, dt: f32) {
+        for effect in &mut self.effects {
+            effect.remaining -= dt;
+        }
+
+        for effect in self.effects.iter().filter(|effect| effect.remaining <= 0.0) {
+            if effect.amount < 0 {
+                self.stats
+                    .increment(effect.stat, effect.amount.unsigned_abs())
+            } else {
+                self.stats.decrement(effect.stat, effect.amount as _);
+            }
+        }
+
+        self.effects.retain(|effect| effect.remaining > 0.0);
+    }
+
+    /// What a new piece of equipment costs at the hero's level
     pub const fn equipment_price(&self) -> isize {
-        // the algorithm
-        (5 * self.level.pow(2) + 10 * self.level + 20) as _
+        self.tuning.equipment_price(self.level)
     }
 
     pub fn level_up(&mut self, rng: &Rand) {
@@ -977,15 +3981,16 @@ impl Player {
             self.stats.increment(stat, adjust(amount));
         }
 
-        self.choose_stat(rng);
-        self.choose_stat(rng);
+        self.choose_stat(1, rng);
+        self.choose_stat(1, rng);
         self.choose_spell(rng);
 
+        self.exp_bar.mark_completed(self.elapsed);
         self.exp_bar
-            .reset(level_up_time(self.level).as_secs() as f32)
+            .reset(self.tuning.level_up_time(self.level).as_secs() as f32)
     }
 
-    fn choose_stat(&mut self, rng: &Rand) {
+    fn choose_stat(&mut self, amount: usize, rng: &Rand) -> String {
         let stat = if rng.odds(1, 2) {
             *config::ALL_STATS.choice(rng)
         } else {
@@ -1003,54 +4008,52 @@ impl Player {
                 .expect("chose a stat")
         };
 
-        self.stats.increment(stat, 1);
-        if stat == Stat::Strength {
-            self.inventory.set_capacity(10 + self.stats[Stat::Strength])
-        }
+        // the simulation keeps the inventory's capacity in line with the hero's strength
+        self.stats.increment(stat, amount);
+        format!("+{amount} {stat}")
     }
 
-    fn choose_spell(&mut self, rng: &Rand) {
+    fn choose_spell(&mut self, rng: &Rand) -> String {
         let choice = self.stats[Stat::Wisdom] + self.level;
-        let index = rng.below_low(choice).min(config::SPELLS.len() - 1);
-        self.spell_book.add(config::SPELLS[index], 1)
+        let spells = &config::content().spells;
+        let spell = std::iter::repeat_with(|| &spells[rng.below_low(choice).min(spells.len() - 1)])
+            .take(10)
+            .find(|spell| self.rating.allows(spell.rating()))
+            .unwrap_or(&spells[0]);
+        self.spell_book.add(spell, 1);
+        spell.to_string()
     }
 
-    fn choose_equipment(&mut self, rng: &Rand) {
+    fn choose_equipment(&mut self, rng: &Rand) -> String {
         use config::Equipment::*;
-        let (stuff, better, worse) = match [
-            Weapon, Shield, Helm, Hauberk, Brassairts, //
-            Vambraces, Gauntlets, Guisses, Greaves, Sollerets,
-        ]
-        .choice(rng)
-        {
+        let content = config::content();
+        let (stuff, better, worse) = match config::Equipment::ALL.choice(rng) {
             Weapon => (
-                config::WEAPONS,
-                config::OFFENSE_ATTRIBUTE,
-                config::OFFENSE_QUIRK,
+                &content.weapons,
+                &content.offense_attributes,
+                &content.offense_quirks,
             ),
             Shield => (
-                config::SHIELDS,
-                config::DEFENSE_ATTRIBUTE,
-                config::DEFENSE_QUIRK,
+                &content.shields,
+                &content.defense_attributes,
+                &content.defense_quirks,
             ),
             _ => (
-                config::ARMORS,
-                config::DEFENSE_ATTRIBUTE,
-                config::DEFENSE_QUIRK,
+                &content.armors,
+                &content.defense_attributes,
+                &content.defense_quirks,
             ),
         };
 
         let equipment = pick_equipment(stuff, self.level as _, rng);
-        let mut name = equipment.name.to_string();
+        let mut piece = EquipmentPiece::new(equipment.clone());
 
         let mut positive = self.level as i32 - equipment.quality;
         let pool = if positive < 0 { worse } else { better };
 
-        let mut count = 0;
-        let mut modifier;
-        while count < 2 && positive > 0 {
-            modifier = rng.choice(pool);
-            if modifier.name == name {
+        while piece.modifiers.len() < 2 && positive > 0 {
+            let modifier = rng.choice(pool);
+            if piece.modifiers.first() == Some(modifier) {
                 break;
             }
 
@@ -1058,58 +4061,151 @@ impl Player {
                 break;
             }
 
-            name = format!("{} {name}", modifier.name);
+            piece.modifiers.insert(0, modifier.clone());
             positive -= modifier.quality;
-            count += 1
         }
+        piece.bonus = positive;
 
-        name = match positive {
-            0 => name,
-            _ => format!(
-                "{delta}{positive} {name}",
-                delta = if positive > 0 { "+" } else { "" }
-            ),
-        };
-
-        self.equipment.add(
+        let name = piece.display();
+        let old = self.equipment.equip(
             *[
                 Weapon, Shield, Helm, Hauberk, Brassairts, Vambraces, Gauntlets, Guisses, Greaves,
                 Sollerets,
             ]
             .choice(rng),
-            name,
+            piece,
         );
+        // the old gear is sold back to the merchant
+        if let Some(old) = old {
+            self.inventory.add_gold(old.resale());
+            self.statistics.gold_earned += old.resale();
+        }
+        name
+    }
+
+    fn next_craft(&mut self) -> Option<Task> {
+        let content = config::content();
+        let recipe = content.recipes.iter().find(|recipe| {
+            self.inventory
+                .items()
+                .filter(|(name, _)| recipe.accepts(name))
+                .map(|(_, qty)| qty)
+                .sum::<usize>()
+                >= recipe.quantity
+        })?;
+
+        self.inventory
+            .consume(|name| recipe.accepts(name), recipe.quantity);
+        Some(Task::craft(recipe, Duration::from_millis(3000)))
+    }
+
+    fn choose_item(&mut self, rng: &Rand) -> String {
+        let item = special_item(self.rating, rng);
+        self.inventory.add_item(item.clone(), 1);
+        item
+    }
+}
+
+/// Markov models trained on the configured content, for making up new entries
+pub struct Synthesis {
+    monsters: lingo::Markov,
+    specials: lingo::Markov,
+}
+
+impl Synthesis {
+    pub const MIN_ACT: i32 = 4;
+
+    pub fn train() -> Self {
+        let content = config::content();
+        Self {
+            // only family friendly entries are used, so the models work for every rating
+            monsters: lingo::Markov::train(
+                content
+                    .monsters
+                    .iter()
+                    .filter(|m| m.rating() == Rating::Family)
+                    .map(|m| &*m.name),
+                3,
+            ),
+            specials: lingo::Markov::train(
+                content
+                    .specials
+                    .iter()
+                    .filter(|s| s.rating() == Rating::Family),
+                2,
+            ),
+        }
+    }
+
+    /// A new monster, borrowing its level and loot from a real one
+    pub fn monster(&self, level: usize, rating: Rating, rng: &Rand) -> config::Monster {
+        let template = unnamed_monster(level, 3, rating, &Bestiary::default(), rng);
+        config::Monster {
+            name: lingo::generate_allowed(&self.monsters, rng).into(),
+            ..template
+        }
+    }
+
+    pub fn interesting_item(&self, rating: Rating, rng: &Rand) -> String {
+        use lingo::NameGenerator as _;
+        format!(
+            "{} {}",
+            choose_rated(&config::content().item_attributes, rating, rng),
+            self.specials.generate(rng)
+        )
     }
+}
+
+const fn default_time_scale() -> f32 {
+    1.0
+}
 
-    fn choose_item(&mut self, rng: &Rand) {
-        self.inventory.add_item(special_item(rng), 1);
+/// Choose from `list`, skipping anything the rating doesn't allow
+fn choose_rated<'a, T: Rated>(list: &'a [T], rating: Rating, rng: &Rand) -> &'a T {
+    let allowed = list
+        .iter()
+        .filter(|item| rating.allows(item.rating()))
+        .collect::<Vec<_>>();
+    if allowed.is_empty() {
+        return list.choice(rng);
     }
+    allowed.choice(rng)
 }
 
-fn special_item(rng: &Rand) -> String {
+/// Who a fight was with, from its description
+fn foe(description: &str) -> &str {
+    ["Attacking ", "Chasing off "]
+        .iter()
+        .find_map(|prefix| description.strip_prefix(prefix))
+        .unwrap_or(description)
+}
+
+fn special_item(rating: Rating, rng: &Rand) -> String {
     format!(
         "{} of {}",
-        interesting_item(rng),
-        config::ITEM_PREPOSITION.choice(rng)
+        interesting_item(rating, rng),
+        choose_rated(&config::content().item_prepositions, rating, rng)
     )
 }
 
-fn interesting_item(rng: &Rand) -> String {
+fn interesting_item(rating: Rating, rng: &Rand) -> String {
+    let content = config::content();
     format!(
         "{} {}",
-        config::ITEM_ATTRIBUTES.choice(rng),
-        config::SPECIALS.choice(rng)
+        choose_rated(&content.item_attributes, rating, rng),
+        choose_rated(&content.specials, rating, rng)
     )
 }
 
-fn boring_item(rng: &Rand) -> &'static str {
-    config::BORING_ITEMS.choice(rng)
+fn boring_item(rating: Rating, rng: &Rand) -> String {
+    choose_rated(&config::content().boring_items, rating, rng).to_string()
 }
 
 fn impressive_npc(rng: &Rand) -> String {
-    let title = config::IMPRESSIVE_TITLES.choice(rng);
+    let content = config::content();
+    let title = content.impressive_titles.choice(rng);
     let (suffix, name) = if rng.odds(1, 3) {
-        ("of the ", Cow::from(&*config::RACES.choice(rng).name))
+        ("of the ", Cow::from(&*content.races.choice(rng).name))
     } else {
         ("of ", Cow::from(generate_name(None, rng)))
     };
@@ -1117,12 +4213,45 @@ fn impressive_npc(rng: &Rand) -> String {
     format!("{title} {suffix} {name}")
 }
 
-fn unnamed_monster(level: usize, attempts: usize, rng: &Rand) -> config::Monster {
-    let mut monster = config::MONSTERS.choice(rng);
+fn quest_giver(rng: &Rand) -> String {
+    let content = config::content();
+    format!(
+        "{} {} of the {}",
+        content.impressive_titles.choice(rng),
+        generate_name(None, rng),
+        plural(&content.races.choice(rng).name)
+    )
+}
+
+/// A monster close to `level`, preferring ones that haven't been killed as often
+fn unnamed_monster(
+    level: usize,
+    attempts: usize,
+    rating: Rating,
+    bestiary: &Bestiary,
+    rng: &Rand,
+) -> config::Monster {
+    let content = config::content();
+    closest_monster(&content.monsters, level, attempts, rating, bestiary, rng)
+}
+
+/// Like [`unnamed_monster`], from `monsters`
+fn closest_monster(
+    monsters: &[config::Monster],
+    level: usize,
+    attempts: usize,
+    rating: Rating,
+    bestiary: &Bestiary,
+    rng: &Rand,
+) -> config::Monster {
+    let distance = |monster: &config::Monster| {
+        level.saturating_sub(monster.level) + bestiary.kills(&monster.name) / Bestiary::FARMED
+    };
+    let mut monster = choose_rated(monsters, rating, rng);
 
     for _ in 0..attempts {
-        let alt = config::MONSTERS.choice(rng);
-        if level.saturating_sub(alt.level) < level.saturating_sub(monster.level) {
+        let alt = choose_rated(monsters, rating, rng);
+        if distance(alt) < distance(monster) {
             monster = alt;
         }
     }
@@ -1130,11 +4259,6 @@ fn unnamed_monster(level: usize, attempts: usize, rng: &Rand) -> config::Monster
     monster.clone()
 }
 
-fn named_monster(level: usize, rng: &Rand) -> String {
-    let monster = unnamed_monster(level, 4, rng);
-    format!("{} the {}", generate_name(None, rng), monster.name)
-}
-
 fn pick_equipment(source: &[config::EquipmentPreset], goal: i32, rng: &Rand) -> EquipmentPreset {
     let mut out = rng.choice(source);
     for _ in 0..5 {
@@ -1146,20 +4270,100 @@ fn pick_equipment(source: &[config::EquipmentPreset], goal: i32, rng: &Rand) ->
     out.clone()
 }
 
+/// How the prime stats are rolled. It's kept on the character, so scores can be compared between
+/// characters rolled the same way
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum RollMode {
+    /// 3d6 for each stat, like the original
+    #[default]
+    Classic,
+    /// 4d6 for each stat, dropping the lowest die
+    DropLowest,
+    /// [`Self::STANDARD_ARRAY`], dealt out to the stats in a random order
+    FixedArray,
+    /// 3d6 for each stat, rolled again until they add up to at least this much
+    RerollUntil(usize),
+}
+
+impl RollMode {
+    pub const STANDARD_ARRAY: [usize; config::PRIME_STATS.len()] = [15, 14, 13, 12, 10, 8];
+    /// The most a [`Self::RerollUntil`] can ask for, so it doesn't roll forever
+    pub const MAX_TOTAL: usize = 90;
+    /// The [`Self::RerollUntil`] offered by default
+    pub const REROLL_TOTAL: usize = 70;
+
+    pub const ALL: [Self; 4] = [
+        Self::Classic,
+        Self::DropLowest,
+        Self::FixedArray,
+        Self::RerollUntil(Self::REROLL_TOTAL),
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Classic => "3d6",
+            Self::DropLowest => "4d6 drop lowest",
+            Self::FixedArray => "Fixed array",
+            Self::RerollUntil(..) => "Reroll until",
+        }
+    }
+
+    fn prime_stats(self, rng: &Rand) -> Vec<usize> {
+        const STATS: usize = config::PRIME_STATS.len();
+        let die = || 1 + rng.below(6);
+        let three_d6 = || (0..STATS).map(|_| 3 + (0..3).map(|_| rng.below(6)).sum::<usize>());
+
+        match self {
+            Self::Classic => three_d6().collect(),
+            Self::DropLowest => (0..STATS)
+                .map(|_| {
+                    let dice = [die(), die(), die(), die()];
+                    dice.iter().sum::<usize>() - dice.iter().min().unwrap()
+                })
+                .collect(),
+            Self::FixedArray => {
+                let mut values = Self::STANDARD_ARRAY.to_vec();
+                for i in (1..values.len()).rev() {
+                    values.swap(i, rng.below(i + 1));
+                }
+                values
+            }
+            Self::RerollUntil(total) => {
+                let total = total.min(Self::MAX_TOTAL);
+                loop {
+                    let values = three_d6().collect::<Vec<_>>();
+                    if values.iter().sum::<usize>() >= total {
+                        break values;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for RollMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RerollUntil(total) => write!(f, "3d6 rerolled until {total}"),
+            mode => f.write_str(mode.as_str()),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct StatsBuilder {
-    history: VecDeque<Stats>,
+    /// How the next roll is made
+    pub mode: RollMode,
+    history: VecDeque<(Stats, RollMode)>,
 }
 
 impl StatsBuilder {
     const MAX_HISTORY: usize = 10;
 
     pub fn roll(&mut self, rng: &Rand) -> Stats {
-        const MAX: usize = config::PRIME_STATS.len();
-
         let mut values: HashMap<Stat, usize> = config::PRIME_STATS
             .into_iter()
-            .map(|stat| (stat, 3 + (0..3).map(|_| rng.below(MAX)).sum::<usize>()))
+            .zip(self.mode.prime_stats(rng))
             .collect();
 
         for (stat, base) in [
@@ -1169,14 +4373,19 @@ impl StatsBuilder {
             values.insert(stat, rng.below(config::ALL_STATS.len()) + values[&base]);
         }
 
-        let stats = Stats::new(values.into_iter());
+        let stats = Stats::new(values);
         while self.history.len() >= Self::MAX_HISTORY {
             self.history.pop_front();
         }
-        self.history.push_back(stats.clone());
+        self.history.push_back((stats.clone(), self.mode));
         stats
     }
 
+    /// How the current stats were rolled
+    pub fn rolled_with(&self) -> RollMode {
+        self.history.back().map_or(self.mode, |&(_, mode)| mode)
+    }
+
     pub fn has_history(&self) -> bool {
         self.history.len() > 1
     }
@@ -1185,6 +4394,839 @@ impl StatsBuilder {
         if self.history.len() > 1 {
             self.history.pop_back();
         }
-        self.history.back().cloned().unwrap()
+        self.history.back().map(|(stats, _)| stats.clone()).unwrap()
+    }
+}
+
+#[test]
+fn roll_modes() {
+    let rng = Rand::seed(42);
+    let prime = |stats: &Stats| {
+        config::PRIME_STATS
+            .iter()
+            .map(|&stat| stats[stat])
+            .collect::<Vec<_>>()
+    };
+
+    let mut builder = StatsBuilder {
+        mode: RollMode::FixedArray,
+        ..StatsBuilder::default()
+    };
+    let mut values = prime(&builder.roll(&rng));
+    values.sort_unstable_by(|a, b| b.cmp(a));
+    assert_eq!(values, RollMode::STANDARD_ARRAY);
+
+    builder.mode = RollMode::DropLowest;
+    let values = prime(&builder.roll(&rng));
+    assert!(values.iter().all(|value| (3..=18).contains(value)));
+
+    builder.mode = RollMode::RerollUntil(80);
+    let values = prime(&builder.roll(&rng));
+    assert!(values.iter().sum::<usize>() >= 80);
+
+    assert_eq!(builder.rolled_with(), RollMode::RerollUntil(80));
+    builder.unroll();
+    assert_eq!(builder.rolled_with(), RollMode::DropLowest);
+}
+
+#[test]
+fn catch_up_is_deterministic() {
+    let run = || {
+        let player = crate::testing::hero();
+        let mut simulation = Simulation::with_seed(player, 42);
+        simulation.catch_up(Duration::from_secs(60 * 60));
+        simulation.player
+    };
+
+    let (a, b) = (run(), run());
+    assert!(a.level > 1);
+    assert_eq!(a.elapsed, b.elapsed);
+    assert_eq!(a.level, b.level);
+    assert_eq!(a.inventory.gold(), b.inventory.gold());
+}
+
+#[test]
+fn watchdog_recovers_a_bar_that_never_fills() {
+    let mut simulation = crate::testing::simulation();
+    simulation.advance(0.0);
+    simulation.player.task_bar.max = f32::NAN;
+
+    for _ in 0..Watchdog::MAX_TASK_TIME as usize + 1 {
+        simulation.advance(1.0);
+    }
+
+    assert!(simulation.take_diagnostic().is_some());
+    assert!(!simulation.player.task_bar.max.is_nan());
+}
+
+#[test]
+fn watchdog_recovers_inside_a_dungeon() {
+    let mut simulation = crate::testing::simulation();
+    simulation.advance(0.0);
+    simulation.with_rng(|this, rng| {
+        this.start_dungeon(rng);
+        this.start_expedition(rng);
+    });
+    simulation.advance(0.0);
+    simulation.player.task_bar.max = f32::NAN;
+
+    for _ in 0..Watchdog::MAX_TASK_TIME as usize + 1 {
+        simulation.advance(1.0);
+        if simulation.player.dungeon.is_none() {
+            break;
+        }
+    }
+
+    let diagnostic = simulation.take_diagnostic().unwrap();
+    assert!(diagnostic.contains(", left the "), "{diagnostic}");
+    assert!(simulation.player.expedition.is_none());
+    assert!(!simulation
+        .player
+        .inventory
+        .items()
+        .any(|(item, _)| item == Expedition::MAP));
+
+    let before = simulation.player.statistics.tasks_finished;
+    for _ in 0..60 {
+        simulation.advance(1.0);
+    }
+    assert!(simulation.player.statistics.tasks_finished > before);
+    assert!(simulation.take_diagnostic().is_none());
+}
+
+#[test]
+fn replay_matches() {
+    let mut simulation = crate::testing::simulation();
+    for _ in 0..60 {
+        simulation.advance_by(60.0);
+    }
+
+    let replay = simulation.replay_to(simulation.player.elapsed);
+    assert_eq!(replay.seed(), 7);
+    assert_eq!(replay.player.level, simulation.player.level);
+    assert_eq!(
+        replay.player.inventory.gold(),
+        simulation.player.inventory.gold()
+    );
+    assert_eq!(replay.completed(), simulation.completed());
+
+    // a later session picks up from the hero's own seed, whatever it's given
+    let saved = simulation.player.clone();
+    let play = |seed| {
+        let mut later = Simulation::with_seed(saved.clone(), seed);
+        later.advance_by(60.0 * 60.0);
+        later
+    };
+    let (later, again) = (play(1), play(2));
+    assert_eq!(later.seed(), again.seed());
+    assert_eq!(later.player.inventory.gold(), again.player.inventory.gold());
+    let replay = later.replay_to(later.player.elapsed);
+    assert_eq!(replay.completed(), later.completed());
+}
+
+#[test]
+fn tick_policies() {
+    let mut simulation = crate::testing::simulation();
+    simulation.time_scale = 2.0;
+
+    simulation.set_tick_policy(TickPolicy::FixedStep(Duration::from_millis(250)));
+    simulation.tick();
+    assert_eq!(simulation.player.elapsed, 0.5);
+
+    simulation.pause();
+    simulation.tick();
+    assert_eq!(simulation.player.elapsed, 0.5);
+    simulation.resume();
+
+    simulation.set_tick_policy(TickPolicy::Turbo);
+    for _ in 0..10 {
+        simulation.tick();
+    }
+    assert_eq!(simulation.completed(), 10);
+    assert!(simulation.dirty());
+    simulation.mark_saved();
+    assert!(!simulation.dirty());
+    let since = simulation
+        .player
+        .task_bar
+        .since_completed(simulation.player.elapsed);
+    assert_eq!(since, Some(0.0));
+}
+
+#[test]
+fn cowardly_monsters() {
+    let fight = |rules: RuleSet| {
+        let mut player = crate::testing::hero();
+        player.level = 20;
+        player.set_task(Task {
+            description: "Attacking a goblin".into(),
+            duration: Duration::from_secs(10),
+            kind: TaskKind::Kill {
+                monster: Some(config::Monster {
+                    name: "Goblin".into(),
+                    level: 1,
+                    item: Some("ear".into()),
+                    rating: Rating::default(),
+                    family: None,
+                }),
+                level: 1,
+                blows: vec![Blow::Hit(20)],
+            },
+        });
+        player.task_bar.pos = player.task_bar.max;
+
+        let mut simulation = Simulation::with_seed(player, 7);
+        simulation.set_rules(rules);
+        simulation.advance(0.0);
+        simulation
+    };
+
+    // fleeing is opt-in
+    let fought = fight(RuleSet::default());
+    assert_eq!(fought.player.exp_bar.pos, 10.0);
+    assert_eq!(fought.player.statistics.monsters_fled, 0);
+    assert!(fought
+        .player
+        .inventory
+        .items()
+        .any(|(name, _)| name == "goblin ear"));
+
+    let fled = fight(RuleSet {
+        flee_chance: 100,
+        ..RuleSet::default()
+    });
+    assert_eq!(fled.player.exp_bar.pos, 5.0);
+    assert_eq!(fled.player.statistics.monsters_fled, 1);
+    assert!(fled
+        .player
+        .inventory
+        .items()
+        .all(|(name, _)| name != "goblin ear"));
+    assert_eq!(fled.history().last(), Some("Chasing off a goblin"));
+}
+
+#[test]
+fn ascension() {
+    let mut player = crate::testing::hero_with(Stats::new(
+        config::ALL_STATS.into_iter().map(|stat| (stat, 20)),
+    ));
+    player.level = 40;
+    for _ in 0..9 {
+        player.quest_book.next_act();
+    }
+
+    let mut simulation = Simulation::with_seed(player, 7);
+    simulation.set_rules(RuleSet {
+        final_act: Some(9),
+        ..RuleSet::default()
+    });
+    simulation.with_rng(|this, rng| this.complete_act(rng));
+
+    let player = &simulation.player;
+    assert_eq!(player.level, 1);
+    assert_eq!(player.quest_book.act(), 1);
+    assert_eq!(player.stats[Stat::Strength], 22);
+    assert_eq!(player.legacy.levels, [40]);
+    assert_eq!(player.legacy.exp_percent(), 100 + Legacy::EXP_PERCENT);
+    assert!(player
+        .journal
+        .iter()
+        .any(|entry| matches!(entry.event, Event::Ascended { ascension: 1, .. })));
+}
+
+#[test]
+fn arena_gauntlet() {
+    let run = |time_limit: f32| {
+        let mut simulation = crate::testing::simulation();
+        simulation.set_tick_policy(TickPolicy::Turbo);
+        simulation.tick();
+        simulation.with_rng(|this, rng| this.start_arena(rng));
+        simulation.player.arena.as_mut().unwrap().time_limit = time_limit;
+
+        while simulation.player.arena.is_some() {
+            simulation.tick();
+        }
+        simulation
+    };
+
+    let won = run(1_000_000.0);
+    let entry = won.player.journal.arena().last().unwrap();
+    assert!(matches!(
+        &entry.event,
+        Event::ArenaFinished { trophy: Some(trophy), rounds: 3, .. } if trophy.contains("Trophy")
+    ));
+
+    let lost = run(0.0);
+    let entry = lost.player.journal.arena().last().unwrap();
+    assert!(matches!(
+        entry.event,
+        Event::ArenaFinished {
+            trophy: None,
+            rounds: 0,
+            ..
+        }
+    ));
+    assert!(lost
+        .player
+        .queue
+        .iter()
+        .all(|task| !matches!(task.kind, TaskKind::Gauntlet { .. })));
+}
+
+#[test]
+fn treasure_map() {
+    let mut simulation = crate::testing::simulation();
+    simulation.set_tick_policy(TickPolicy::Turbo);
+    simulation.tick();
+    simulation.with_rng(|this, rng| this.start_expedition(rng));
+
+    let has_map = |player: &Player| {
+        player
+            .inventory
+            .items()
+            .any(|(item, _)| item == Expedition::MAP)
+    };
+    assert!(has_map(&simulation.player));
+
+    // it's kept through any trips to market along the way
+    for _ in 0..100 {
+        if simulation.player.expedition.is_none() {
+            break;
+        }
+        assert!(has_map(&simulation.player));
+        simulation.tick();
+    }
+    assert!(simulation.player.expedition.is_none());
+    assert!(!has_map(&simulation.player));
+    assert!(simulation
+        .player
+        .journal
+        .iter()
+        .any(|entry| matches!(entry.event, Event::TreasureFound { .. })));
+}
+
+#[test]
+fn gathering() {
+    let mut simulation = crate::testing::simulation();
+    simulation.set_tick_policy(TickPolicy::Turbo);
+    simulation.tick();
+    simulation.with_rng(|this, rng| this.start_gathering(rng));
+
+    let gathered = simulation
+        .player
+        .queue
+        .iter()
+        .filter_map(|task| match task.kind {
+            TaskKind::Gather { quantity, .. } => Some(quantity),
+            _ => None,
+        })
+        .sum::<usize>();
+    assert!(gathered > 0);
+
+    while simulation
+        .player
+        .queue
+        .iter()
+        .any(|task| matches!(task.kind, TaskKind::Gather { .. }))
+    {
+        simulation.tick();
+    }
+    simulation.tick();
+    assert_eq!(simulation.player.statistics.materials_gathered, gathered);
+}
+
+#[test]
+fn starting_kit() {
+    let mage = config::CLASSES
+        .iter()
+        .find(|class| class.name == "Mage Illusioner")
+        .unwrap();
+    let player = Player::new(
+        "Gorbash",
+        config::RACES[7].clone(),
+        mage.clone(),
+        Stats::new(config::ALL_STATS.into_iter().map(|stat| (stat, 7))),
+    );
+
+    // a poor roll, so the -2 wand is made up for
+    let weapon = player.equipment.get(config::Equipment::Weapon).unwrap();
+    assert_eq!(weapon, "Cracked Wand");
+
+    let piece = EquipmentPiece::parse("+2 Polished Pronged Eelspear");
+    assert_eq!(piece.quality(), 2 + 1 + 2 + 2);
+    assert_eq!(piece.display(), "+2 Polished Pronged Eelspear");
+    assert_eq!(
+        EquipmentPiece::parse("-1 Mystery Thing").display(),
+        "-1 Mystery Thing"
+    );
+
+    let mut equipment = Equipment::default();
+    let score = equipment.gear_score();
+    let old = equipment.equip(config::Equipment::Weapon, piece);
+    assert_eq!(
+        equipment.gear_score(),
+        score - old.map_or(0, |old| old.quality()) + 7
+    );
+    assert!(player
+        .inventory
+        .items()
+        .any(|(item, _)| item == "sugar cube"));
+
+    // changing the class and stats on the creation screen
+    let mut player = player;
+    player.class = config::CLASSES[0].clone();
+    player.stats = Stats::new(config::ALL_STATS.into_iter().map(|stat| (stat, 10)));
+    player.outfit();
+    let weapon = player.equipment.get(config::Equipment::Weapon).unwrap();
+    assert_ne!(weapon, "Cracked Wand");
+}
+
+#[test]
+fn power_level() {
+    let rng = Rand::seed(42);
+    let mut player = crate::testing::hero();
+
+    let before = player.power_level();
+    player.level_up(&rng);
+    assert!(player.power_level() > before);
+}
+
+#[test]
+fn bestiary() {
+    let rng = Rand::seed(42);
+    let mut simulation = crate::testing::simulation();
+    simulation.set_tick_policy(TickPolicy::Turbo);
+    for _ in 0..500 {
+        simulation.tick();
+    }
+
+    let bestiary = &simulation.player.bestiary;
+    assert!(bestiary.total_kills() > 0);
+    let firsts = bestiary
+        .discovered()
+        .map(|(_, entry)| entry.first_kill)
+        .collect::<Vec<_>>();
+    assert!(firsts.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    // a farmed monster loses out to one that's further away
+    let mut farmed = Bestiary::default();
+    let content = config::content();
+    let near = content.monsters.iter().find(|m| m.level == 1).unwrap();
+    for _ in 0..Bestiary::FARMED * 10 {
+        farmed.record(&near.name, near.family, 0.0);
+    }
+    let picks = (0..50)
+        .filter(|_| unnamed_monster(1, 10, Rating::Classic, &farmed, &rng).name == near.name)
+        .count();
+    assert_eq!(picks, 0);
+}
+
+#[test]
+fn uniques_drop_once() {
+    let rng = Rand::seed(42);
+    let table = config::LOOT_TABLES
+        .iter()
+        .find(|table| table.monster == "Orcus")
+        .unwrap();
+
+    let mut bestiary = Bestiary::default();
+    bestiary.record("Orcus", Some(config::Family::Fiends), 0.0);
+    let mut uniques = 0;
+    for _ in 0..1000 {
+        let drop = table.roll(|item| bestiary.looted(item), &rng).unwrap();
+        uniques += drop.unique as usize;
+        bestiary.loot("Orcus", drop);
+    }
+    assert_eq!(uniques, 1);
+
+    let (_, entry) = bestiary.iter().next().unwrap();
+    assert!(entry.best_loot.as_ref().unwrap().unique);
+}
+
+#[test]
+fn tuning() {
+    let play = |tuning: config::Tuning| {
+        let mut simulation = crate::testing::simulation();
+        simulation.set_tuning(tuning);
+        simulation.set_tick_policy(TickPolicy::Turbo);
+        while simulation.player.elapsed < 4.0 * 60.0 * 60.0 {
+            simulation.tick();
+        }
+        simulation.player
+    };
+
+    let mut quick = play(config::Tuning {
+        level_up_minutes: 5,
+        base_capacity: 50,
+        ..config::Tuning::default()
+    });
+    let normal = play(config::Tuning::default());
+    assert!(quick.level > normal.level);
+
+    let mut simulation = Simulation::new(normal);
+    simulation.time_scale = 500.0;
+    assert_eq!(simulation.speed(), config::Tuning::DEFAULT.max_time_scale);
+    assert_eq!(quick.inventory.capacity(), 50 + quick.stats[Stat::Strength]);
+
+    // the tuning stays with the hero
+    quick.ascend();
+    assert_eq!(quick.exp_bar.max, 5.0 * 60.0);
+}
+
+#[test]
+fn quest_rewards() {
+    let mut simulation = crate::testing::simulation();
+    let quests = &mut simulation.player.quest_book;
+    quests.quest.reset(1000.0);
+    quests.add_quest("Fetch me a sock", None, 0.0);
+    let gold = simulation.player.inventory.gold();
+    simulation.complete_quest(&Rand::seed(1));
+
+    let completed = simulation
+        .player
+        .journal
+        .iter()
+        .filter_map(|entry| match &entry.event {
+            Event::QuestCompleted {
+                reward,
+                bonus,
+                gold,
+                ..
+            } => Some((reward.is_some(), bonus.len(), *gold)),
+            _ => None,
+        })
+        .last();
+    // 1000 is near the top of the default lengths
+    assert_eq!(completed, Some((true, 2, 47)));
+    assert_eq!(simulation.player.inventory.gold(), gold + 47);
+}
+
+#[test]
+fn nemesis_returns() {
+    let mut simulation = crate::testing::simulation();
+    let rng = Rand::seed(3);
+    simulation.player.nemesis = Some(Nemesis::new(5, 0, Rating::Classic, &rng));
+
+    let mut fights = 0;
+    while let Some(nemesis) = &mut simulation.player.nemesis {
+        nemesis.resurface(5, fights as i32 + 1);
+        fights += 1;
+        simulation.defeat_nemesis(&rng);
+    }
+    assert!(fights <= Nemesis::MAX_ENCOUNTERS);
+
+    let slain = simulation
+        .player
+        .journal
+        .iter()
+        .find_map(|entry| match &entry.event {
+            Event::NemesisSlain {
+                encounters, loot, ..
+            } => Some((*encounters, loot.len())),
+            _ => None,
+        });
+    assert_eq!(slain, Some((fights, fights)));
+}
+
+#[test]
+fn market_drifts() {
+    let rng = Rand::seed(42);
+    let mut market = Market::default();
+    assert_eq!(market.outlook(), None);
+    assert_eq!(market.price(Goods::Relics, 100), 100);
+
+    market.drift(Market::PERIOD * 0.5, &rng);
+    assert!(market.rates().all(|(_, rate)| rate == 1.0));
+
+    market.drift(Market::PERIOD * 200.0, &rng);
+    assert!(market.rates().any(|(_, rate)| rate != 1.0));
+    assert!(market
+        .rates()
+        .all(|(_, rate)| (Market::MIN_RATE..=Market::MAX_RATE).contains(&rate)));
+
+    market.rates[Goods::Relics as usize] = 1.5;
+    assert_eq!(
+        market.outlook().as_deref(),
+        Some("The market is hot for relics today")
+    );
+}
+
+#[test]
+fn banking() {
+    let mut bank = Bank::default();
+    assert_eq!(bank.transfer(50, 100), 0);
+    assert_eq!(bank.transfer(500, 100), -200);
+
+    bank.settle(-200);
+    assert_eq!(bank.transfer(80, 100), 200);
+    assert_eq!(bank.transfer(250, 100), 0);
+
+    bank.settle(-800);
+    bank.accrue(Bank::PERIOD * 10.0);
+    assert_eq!(bank.balance(), 1020);
+}
+
+#[test]
+fn bank_interest_over_years() {
+    const YEAR: f32 = 365.0 * 24.0 * Bank::PERIOD;
+
+    let mut bank = Bank::default();
+    bank.settle(-1000);
+    for year in 1..=50 {
+        bank.accrue(YEAR * year as f32);
+    }
+    // 2 gold an hour on the deposit, and nothing on the interest
+    let hours = (bank.balance() - 1000) / 2;
+    assert!((hours - 50 * 365 * 24).abs() <= 1, "{}", bank.balance());
+
+    // withdrawals come out of the deposits first
+    bank.settle(bank.balance() - 100);
+    assert_eq!(bank.principal(), 0);
+    bank.accrue(YEAR * 51.0);
+    assert_eq!(bank.balance(), 100);
+
+    // and a small balance doesn't earn anything
+    let mut bank = Bank::default();
+    bank.settle(-100);
+    bank.accrue(YEAR);
+    assert_eq!(bank.balance(), 100);
+}
+
+#[test]
+fn injected_tasks() {
+    let mut simulation = crate::testing::simulation();
+    simulation.set_tick_policy(TickPolicy::Turbo);
+    for _ in 0..100 {
+        simulation.tick();
+    }
+
+    let task = ExternalEvent::new("  Reading a donation\nmessage from Bob  ")
+        .source("donations")
+        .duration(Duration::from_secs(3600))
+        .into_task();
+    assert_eq!(task.description, "Reading a donation message from Bob");
+    assert_eq!(task.duration, ExternalEvent::MAX_DURATION);
+    simulation.inject_task(task, Priority::Next).unwrap();
+
+    for _ in 0..10 {
+        simulation.tick();
+    }
+    assert!(simulation.player.journal.iter().any(|entry| matches!(
+        &entry.event,
+        Event::External { source, .. } if source == "donations"
+    )));
+
+    for _ in 0..Simulation::MAX_INJECTED {
+        let task = ExternalEvent::new("Waving to the chat").into_task();
+        simulation.inject_task(task, Priority::Last).unwrap();
+    }
+    let task = ExternalEvent::new("One too many").into_task();
+    assert!(simulation.inject_task(task, Priority::Last).is_err());
+}
+
+#[test]
+fn last_stand() {
+    let rng = Rand::seed(42);
+    let mut simulation = crate::testing::simulation();
+    let stats = simulation.player.stats.clone();
+
+    // without time passing, failed hazards pile up until the hero falls
+    for _ in 0..1000 {
+        if simulation.player.statistics.last_stands > 0 {
+            break;
+        }
+        simulation.hazard(&rng);
+    }
+    assert_eq!(simulation.player.statistics.last_stands, 1);
+    assert!(simulation
+        .player
+        .journal
+        .iter()
+        .any(|entry| matches!(entry.event, Event::LastStand { .. })));
+    assert!(simulation
+        .player
+        .queue
+        .iter()
+        .any(|task| task.description == Simulation::LAST_STAND[0].0));
+
+    // the wounds became the weakness, which doesn't count towards the next fall, and it wears off
+    assert_eq!(simulation.player.wounds(), 0);
+    assert_eq!(
+        simulation
+            .player
+            .effects
+            .iter()
+            .filter(|effect| effect.recovery)
+            .count(),
+        3
+    );
+    assert_eq!(
+        simulation.player.stats[Stat::Strength],
+        stats[Stat::Strength] - Simulation::LAST_STAND_WEAKNESS as usize
+    );
+    simulation
+        .player
+        .update_effects(Simulation::LAST_STAND_RECOVERY);
+    assert_eq!(
+        simulation.player.stats[Stat::Strength],
+        stats[Stat::Strength]
+    );
+}
+
+#[test]
+fn time_controller_eases_back() {
+    let mut time = TimeController::default();
+    time.update(0.0, Some(10.0));
+    assert_eq!(time.boost(), 10.0);
+
+    time.update(1.0, None);
+    let eased = time.boost();
+    assert!(1.0 < eased && eased < 10.0);
+
+    time.update(60.0, None);
+    assert_eq!(time.boost(), 1.0);
+}
+
+#[test]
+fn slayer_ranks() {
+    use config::Family;
+
+    let mut bestiary = Bestiary::default();
+    let mut titles = vec![];
+    for _ in 0..Bestiary::SLAYER_KILLS[1] {
+        titles.extend(bestiary.record("Zombie", Some(Family::Undead), 0.0));
+        bestiary.record("Fly", None, 0.0);
+    }
+    assert_eq!(titles, ["Slayer of Undead", "Scourge of Undead"]);
+    assert_eq!(bestiary.slayer_rank(Family::Undead), 2);
+    assert_eq!(bestiary.slayer_rank(Family::Beasts), 0);
+    assert_eq!(bestiary.title().as_deref(), Some("Scourge of Undead"));
+}
+
+#[test]
+fn statistics() {
+    let mut simulation = crate::testing::simulation();
+    simulation.set_tick_policy(TickPolicy::Turbo);
+    while simulation.player.elapsed < 2.0 * 60.0 * 60.0 {
+        simulation.tick();
+    }
+
+    let statistics = &simulation.player.statistics;
+    assert_eq!(statistics.tasks_finished, simulation.completed());
+    assert!(statistics.kills >= simulation.player.bestiary.total_kills());
+    assert!(statistics.quests_completed > 0);
+    assert!(statistics.items_sold > 0 && statistics.gold_earned > 0);
+    assert_eq!(statistics.table(simulation.player.elapsed)[1].1, "2h");
+}
+
+#[test]
+fn restyle() {
+    let mut equipment = Equipment::default();
+    let slot = config::Equipment::Weapon;
+    equipment.restyle(slot, "  Grandpa's Stick ");
+    assert_eq!(equipment.display(slot).as_deref(), Some("Grandpa's Stick"));
+
+    let piece = EquipmentPiece::parse("+3 Vorpal Sword");
+    equipment.equip(slot, piece.clone());
+    assert_eq!(equipment.display(slot).as_deref(), Some("Grandpa's Stick"));
+    assert_eq!(equipment.get(slot), Some(&*piece.display()));
+
+    equipment.restyle(slot, "");
+    assert_eq!(equipment.display(slot), Some(piece.display()));
+
+    let mut player = crate::testing::hero_with(Stats::new(
+        config::ALL_STATS.into_iter().map(|stat| (stat, 10)),
+    ));
+    player.equipment.restyle(slot, "Grandpa's Stick");
+    player.ascend();
+    assert_eq!(player.equipment.style(slot), Some("Grandpa's Stick"));
+}
+
+#[test]
+fn sleep_gap() {
+    let mut simulation = crate::testing::simulation();
+    let slept = |simulation: &Simulation| {
+        simulation
+            .player
+            .journal
+            .iter()
+            .filter(|entry| matches!(entry.event, Event::LostTrackOfTime { .. }))
+            .count()
+    };
+
+    for _ in 0..10 {
+        simulation.tick_after(Duration::from_millis(500));
     }
+    assert_eq!(slept(&simulation), 0);
+    assert_eq!(simulation.player.statistics.played, 5.0);
+
+    // a window left in the background for a few minutes is still played
+    simulation.tick_after(Duration::from_secs(5 * 60));
+    assert_eq!(slept(&simulation), 0);
+    assert_eq!(simulation.player.statistics.played, 305.0);
+
+    let before = simulation.player.elapsed;
+    simulation.tick_after(Duration::from_secs(3 * 60 * 60));
+    assert_eq!(slept(&simulation), 1);
+    assert!(simulation.player.elapsed - before >= 3.0 * 60.0 * 60.0 - 1.0);
+    assert!(simulation.player.level > 1);
+    // sleeping isn't playing
+    assert_eq!(simulation.player.statistics.played, 305.0);
+
+    simulation.pause();
+    simulation.tick_after(Duration::from_secs(3 * 60 * 60));
+    assert_eq!(slept(&simulation), 1);
+}
+
+#[test]
+fn own_content() {
+    let mut simulation = crate::testing::simulation();
+    let content = config::ContentPack {
+        dungeons: vec![Cow::Borrowed("Pantry")],
+        ..config::ContentPack::default()
+    };
+    simulation.set_content(content.shared());
+    simulation.with_rng(|this, rng| this.start_dungeon(rng));
+
+    let dungeon = simulation.player.dungeon.as_ref().unwrap();
+    assert!(
+        dungeon.name.starts_with("the Pantry of"),
+        "{}",
+        dungeon.name
+    );
+    assert!(!config::content()
+        .dungeons
+        .iter()
+        .any(|name| name == "Pantry"));
+}
+
+#[test]
+fn climate_bias() {
+    let player = crate::testing::hero_with(Stats::new(
+        config::ALL_STATS.into_iter().map(|stat| (stat, 10)),
+    ));
+    let mut simulation = Simulation::with_seed(player, 7);
+    let rng = Rand::seed(7);
+    let frozen = config::content()
+        .climate(config::Climate::Frozen)
+        .cloned()
+        .unwrap();
+
+    let native = |simulation: &Simulation| {
+        (0..200)
+            .filter(|_| {
+                let monster = simulation.native_monster(10, 3, &rng);
+                let item = simulation.boring_item(&rng);
+                monster
+                    .family
+                    .is_some_and(|family| frozen.families.contains(&family))
+                    && frozen.items.iter().any(|frozen| *frozen == item)
+            })
+            .count()
+    };
+    assert_eq!(native(&simulation), 0);
+
+    // half the monsters and half the items come from the act's theme
+    simulation.player.quest_book.theme = Some(config::Climate::Frozen);
+    assert!(native(&simulation) > 200 / 4 / 2);
 }
@@ -0,0 +1,99 @@
+use crate::{mechanics::Player, Rand};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+/// A tiny procedural portrait. The same hero always gets the same portrait
+#[derive(Clone, Debug)]
+pub struct Portrait {
+    pixels: [[Rgb; Self::WIDTH]; Self::HEIGHT],
+}
+
+impl Portrait {
+    pub const WIDTH: usize = 10;
+    pub const HEIGHT: usize = 12;
+
+    const BACKGROUNDS: &'static [Rgb] = &[
+        Rgb(0x2b, 0x3a, 0x55),
+        Rgb(0x3d, 0x2b, 0x55),
+        Rgb(0x2b, 0x55, 0x44),
+        Rgb(0x55, 0x3d, 0x2b),
+    ];
+    const SKINS: &'static [Rgb] = &[
+        Rgb(0xf1, 0xc2, 0x7d),
+        Rgb(0xe0, 0xac, 0x69),
+        Rgb(0xc6, 0x86, 0x42),
+        Rgb(0x8d, 0x55, 0x24),
+        Rgb(0x9a, 0xc4, 0x8a),
+        Rgb(0xa8, 0xa8, 0xc8),
+    ];
+    const HAIRS: &'static [Rgb] = &[
+        Rgb(0x1c, 0x1c, 0x1c),
+        Rgb(0x5a, 0x38, 0x25),
+        Rgb(0xb5, 0x65, 0x1d),
+        Rgb(0xe6, 0xce, 0x6a),
+        Rgb(0xdc, 0xdc, 0xdc),
+    ];
+    const CLOTHES: &'static [Rgb] = &[
+        Rgb(0x8b, 0x1e, 0x1e),
+        Rgb(0x1e, 0x4b, 0x8b),
+        Rgb(0x2e, 0x7d, 0x32),
+        Rgb(0x6a, 0x1b, 0x9a),
+        Rgb(0x9e, 0x9e, 0x9e),
+    ];
+    const EYES: Rgb = Rgb(0x10, 0x10, 0x10);
+    const MOUTH: Rgb = Rgb(0x7a, 0x2e, 0x2e);
+
+    /// The seed for a player's portrait, which only depends on who they are
+    pub fn seed(player: &Player) -> u64 {
+        // fnv-1a, so the seed doesn't change between builds
+        [&*player.name, &*player.race.name, &*player.class.name]
+            .into_iter()
+            .flat_map(|s| s.bytes().chain(Some(0)))
+            .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+            })
+    }
+
+    pub fn of(player: &Player) -> Self {
+        Self::generate(Self::seed(player))
+    }
+
+    pub fn generate(seed: u64) -> Self {
+        let rng = Rand::seed(seed);
+        let background = *rng.choice(Self::BACKGROUNDS);
+        let skin = *rng.choice(Self::SKINS);
+        let hair = *rng.choice(Self::HAIRS);
+        let clothes = *rng.choice(Self::CLOTHES);
+        let beard = rng.odds(1, 3);
+
+        // only the left half is generated, the right half mirrors it
+        let mut pixels = [[background; Self::WIDTH]; Self::HEIGHT];
+        for (y, row) in pixels.iter_mut().enumerate() {
+            for (x, pixel) in row.iter_mut().take(Self::WIDTH / 2).enumerate() {
+                *pixel = match (y, x) {
+                    (0, 2..) => hair,
+                    (1..=2, 1..) => hair,
+                    (3, 1) if rng.odds(1, 2) => hair,
+                    (4, 3) => Self::EYES,
+                    (6, 4) => Self::MOUTH,
+                    (6..=7, 1..) if beard => hair,
+                    (3..=7, 1..) => skin,
+                    (8, 4) => skin,
+                    (9.., 1..) => clothes,
+                    (10.., 0) => clothes,
+                    _ => background,
+                };
+            }
+            for x in 0..Self::WIDTH / 2 {
+                row[Self::WIDTH - 1 - x] = row[x];
+            }
+        }
+
+        Self { pixels }
+    }
+
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[Rgb; Self::WIDTH]> {
+        self.pixels.iter()
+    }
+}
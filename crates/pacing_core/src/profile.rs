@@ -0,0 +1,147 @@
+//! Account-wide totals across the whole roster, including heroes that have since been deleted, and
+//! the hall of fame remembering those heroes.
+
+use crate::{
+    config,
+    mechanics::{EquipmentPiece, Player},
+};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Totals {
+    pub heroes: usize,
+    /// Game time, in seconds
+    pub playtime: f32,
+    pub acts_cleared: usize,
+    /// Heroes that have ascended at least once
+    #[serde(alias = "hall_of_fame")]
+    pub ascended: usize,
+}
+
+impl Totals {
+    fn add(&mut self, player: &Player) {
+        self.heroes += 1;
+        self.playtime += player.elapsed;
+        self.acts_cleared += player.quest_book.act().max(0) as usize;
+        self.ascended += (player.legacy.ascensions > 0) as usize;
+    }
+}
+
+/// What's remembered of a hero after they're retired from the roster, for the hall of fame
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Epitaph {
+    pub name: String,
+    pub race: String,
+    pub class: String,
+    pub level: usize,
+    /// Game time, in seconds
+    pub playtime: f32,
+    pub act: i32,
+    /// The best piece of equipment they had on
+    pub best_item: Option<Keepsake>,
+}
+
+/// A piece of equipment kept in full, rather than just its name
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Keepsake {
+    pub slot: config::Equipment,
+    pub piece: EquipmentPiece,
+    /// What the hero called it, see [`crate::mechanics::Equipment::restyle`]
+    pub style: Option<String>,
+}
+
+impl Keepsake {
+    /// What to call it: its style, or the piece's own name
+    pub fn display(&self) -> String {
+        self.style.clone().unwrap_or_else(|| self.piece.display())
+    }
+}
+
+impl Epitaph {
+    pub fn of(player: &Player) -> Self {
+        Self {
+            name: player.name.clone(),
+            race: player.race.name.to_string(),
+            class: player.class.name.to_string(),
+            level: player.level,
+            playtime: player.elapsed,
+            act: player.quest_book.act(),
+            best_item: player
+                .equipment
+                .pieces()
+                .max_by_key(|(_, piece)| piece.quality())
+                .map(|(slot, piece)| Keepsake {
+                    slot,
+                    piece: piece.clone(),
+                    style: player.equipment.style(slot).map(str::to_string),
+                }),
+        }
+    }
+}
+
+/// Kept alongside the roster, so deleting a hero doesn't erase what they did
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Profile {
+    heroes_created: usize,
+    deleted: Totals,
+    retired: Vec<Epitaph>,
+}
+
+impl Profile {
+    pub fn created(&mut self) {
+        self.heroes_created += 1;
+    }
+
+    /// Keep `player`'s part of the totals once they're gone from the roster
+    pub fn deleted(&mut self, player: &Player) {
+        self.deleted.add(player);
+        self.retired.push(Epitaph::of(player));
+    }
+
+    /// Every hero that was deleted from the roster, oldest first
+    pub fn retired(&self) -> impl ExactSizeIterator<Item = &Epitaph> + DoubleEndedIterator {
+        self.retired.iter()
+    }
+
+    /// The totals for the `roster` and every deleted hero
+    pub fn totals<'a>(&self, roster: impl IntoIterator<Item = &'a Player>) -> Totals {
+        let mut totals = self.deleted;
+        roster.into_iter().for_each(|player| totals.add(player));
+        // heroes from before the profile existed weren't counted when they were created
+        totals.heroes = totals.heroes.max(self.heroes_created);
+        totals
+    }
+}
+
+#[test]
+fn deleted_heroes_still_count() {
+    use crate::{config, mechanics::StatsBuilder, Rand};
+
+    let hero = |name| {
+        Player::new(
+            name,
+            config::RACES[0].clone(),
+            config::CLASSES[0].clone(),
+            StatsBuilder::default().roll(&Rand::seed(42)),
+        )
+    };
+
+    let mut profile = Profile::default();
+    let mut roster = vec![hero("Gorbash"), hero("Mugwort")];
+    roster[0].elapsed = 60.0;
+    roster[1].elapsed = 30.0;
+    profile.created();
+    profile.created();
+
+    let before = profile.totals(&roster);
+    profile.deleted(&roster.remove(0));
+    assert_eq!(profile.totals(&roster), before);
+    let retired = profile
+        .retired()
+        .map(|hero| &*hero.name)
+        .collect::<Vec<_>>();
+    assert_eq!(retired, ["Gorbash"]);
+
+    assert_eq!(Profile::default().totals(&roster).heroes, 1);
+}
@@ -1,7 +1,14 @@
+use std::collections::{BTreeMap, VecDeque};
+
 #[derive(Clone)]
 pub struct Rand {
     rng: fastrand::Rng,
 }
+impl Default for Rand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 impl Rand {
     pub fn new() -> Self {
         Self {
@@ -10,9 +17,14 @@ impl Rand {
     }
 
     pub fn seed(seed: u64) -> Self {
-        let rng = fastrand::Rng::new();
-        rng.seed(seed);
-        Self { rng }
+        Self {
+            rng: fastrand::Rng::with_seed(seed),
+        }
+    }
+
+    /// A seed to start a new generator from
+    pub fn random_seed() -> u64 {
+        fastrand::Rng::new().u64(..)
     }
 
     pub fn choice<'t, T>(&self, slice: &'t [T]) -> &'t T {
@@ -34,6 +46,47 @@ impl Rand {
     pub fn odds(&self, chance: usize, quantum: usize) -> bool {
         self.below(quantum) < chance
     }
+
+    pub fn contest_odds(skill: usize, difficulty: usize) -> f32 {
+        skill as f32 / (skill + difficulty).max(1) as f32
+    }
+
+    pub fn contest(&self, skill: usize, difficulty: usize) -> bool {
+        self.odds(skill, (skill + difficulty).max(1))
+    }
+
+    /// Like [`Self::below`], but skipping the most recent picks from the `list`, which are kept in
+    /// `recent`. At most half of the list is skipped, so there's always some variety left
+    pub fn below_fresh(&self, num: usize, list: &str, recent: &mut Recent) -> usize {
+        let picks = recent.picks.entry(list.to_string()).or_default();
+        let avoid = (num / 2).min(Recent::MEMORY);
+        let fresh = |i: &usize| !picks.iter().take(avoid).any(|pick| pick == i);
+
+        let pick = (0..num)
+            .filter(fresh)
+            .nth(self.below((0..num).filter(fresh).count()))
+            .expect("at least half of the list is fresh");
+
+        picks.push_front(pick);
+        picks.truncate(Recent::MEMORY);
+        pick
+    }
+
+    /// Like [`Self::choice`], but steering away from recent picks, see [`Self::below_fresh`]
+    pub fn choice_fresh<'t, T>(&self, slice: &'t [T], list: &str, recent: &mut Recent) -> &'t T {
+        &slice[self.below_fresh(slice.len(), list, recent)]
+    }
+}
+
+/// The latest picks from each named list of flavor text, newest first
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct Recent {
+    picks: BTreeMap<String, VecDeque<usize>>,
+}
+
+impl Recent {
+    /// How many picks are remembered for each list
+    pub const MEMORY: usize = 8;
 }
 
 pub trait SliceExt {
@@ -53,3 +106,21 @@ impl<T> SliceExt for [T] {
         rng.choice_low(self)
     }
 }
+
+#[test]
+fn fresh_picks_avoid_repeats() {
+    let rng = Rand::seed(42);
+    let mut recent = Recent::default();
+
+    let picks = (0..100)
+        .map(|_| rng.below_fresh(4, "test", &mut recent))
+        .collect::<Vec<_>>();
+    // the last two picks are always skipped
+    assert!(picks
+        .windows(3)
+        .all(|w| w[0] != w[1] && w[0] != w[2] && w[1] != w[2]));
+
+    // a list of one can only ever pick the one
+    assert_eq!(rng.below_fresh(1, "single", &mut recent), 0);
+    assert_eq!(rng.below_fresh(1, "single", &mut recent), 0);
+}
@@ -0,0 +1,97 @@
+//! Reloading a content pack whenever its file changes, for iterating on mods without restarting.
+//!
+//! The file is polled rather than watched, so this works the same everywhere. A reloaded pack
+//! replaces the old one all at once through [`config::set_content`], so anything generated from
+//! then on uses the new tables while anything already generated keeps what it had.
+
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::config::{self, ContentPack};
+
+pub struct ContentWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    checked: Option<Instant>,
+    /// So a missing file is only reported once
+    missing: bool,
+}
+
+impl ContentWatcher {
+    /// Look at the file at most this often
+    pub const INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Watch the ron content pack at `path`. Nothing is loaded until the first [`Self::poll`]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            modified: None,
+            checked: None,
+            missing: false,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Load the pack if the file changed since it was last loaded. This is `None` if it's
+    /// unchanged or was checked too recently
+    pub fn poll(&mut self) -> Option<Result<(), String>> {
+        if self
+            .checked
+            .is_some_and(|checked| checked.elapsed() < Self::INTERVAL)
+        {
+            return None;
+        }
+        self.checked = Some(Instant::now());
+
+        let modified = match std::fs::metadata(&self.path).and_then(|meta| meta.modified()) {
+            Ok(modified) => modified,
+            Err(..) if self.missing => return None,
+            Err(err) => {
+                self.missing = true;
+                return Some(Err(self.error(err)));
+            }
+        };
+        self.missing = false;
+        if self.modified == Some(modified) {
+            return None;
+        }
+        // a broken file is only reported once, until it changes again
+        self.modified = Some(modified);
+
+        let pack = std::fs::read_to_string(&self.path)
+            .map_err(|err| self.error(err))
+            .and_then(|data| ron::from_str::<ContentPack>(&data).map_err(|err| self.error(err)));
+        Some(pack.map(config::set_content))
+    }
+
+    fn error(&self, err: impl std::fmt::Display) -> String {
+        format!("cannot load content from {}: {err}", self.path.display())
+    }
+}
+
+#[test]
+fn reloads_when_written() {
+    let path = std::env::temp_dir().join(format!("pacing-reload-{}.ron", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut watcher = ContentWatcher::new(&path);
+
+    assert!(matches!(watcher.poll(), Some(Err(..))));
+    watcher.checked = None;
+    assert_eq!(watcher.poll(), None, "a missing file is only reported once");
+
+    // the built-in content, so the tests running alongside don't notice the swap
+    let pack = ron::to_string(&ContentPack::default()).unwrap();
+    std::fs::write(&path, pack).unwrap();
+    watcher.checked = None;
+    assert_eq!(watcher.poll(), Some(Ok(())));
+    assert_eq!(config::content().id, config::BUILTIN_CONTENT);
+
+    watcher.checked = None;
+    assert_eq!(watcher.poll(), None, "nothing changed since");
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,159 @@
+//! Tunable rules of the game, the defaults are how it's meant to be played.
+
+use crate::config;
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct RuleSet {
+    /// Monsters at least this many levels below the hero might run away mid-fight
+    pub flee_level_gap: usize,
+    /// The chance, out of 100, that an outleveled monster runs away. Monsters fight to the end
+    /// unless this is set
+    pub flee_chance: usize,
+    /// The share of the experience, out of 100, for a monster that ran away. They drop no loot
+    pub flee_exp_percent: usize,
+    /// Finishing this act ascends the hero, starting over with a [`Legacy`](crate::mechanics::Legacy).
+    /// The acts go on forever without one, like the original
+    pub final_act: Option<i32>,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self {
+            flee_level_gap: 10,
+            flee_chance: 0,
+            flee_exp_percent: 50,
+            final_act: None,
+        }
+    }
+}
+
+impl RuleSet {
+    /// The rules of the original, where monsters fight to the end and the acts go on forever
+    pub const fn classic() -> Self {
+        Self {
+            flee_level_gap: 0,
+            flee_chance: 0,
+            flee_exp_percent: 100,
+            final_act: None,
+        }
+    }
+}
+
+/// An ironman character plays by the rules they were created with, with all of their randomness
+/// coming from one seed, so challenges and duels between them are fair.
+///
+/// The lock is a hash of the seed, the rules and the [`id`](config::ContentPack::id) of the
+/// content pack that was in play at creation. If the character is ever played with different
+/// content the lock breaks, for good. The tuning can't be changed for them either, see
+/// [`Simulation::set_tuning`](crate::mechanics::Simulation::set_tuning)
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Ironman {
+    pub seed: u64,
+    pub rules: RuleSet,
+    /// The id of the content pack the run is locked to
+    #[serde(default = "builtin_content")]
+    pub content: String,
+    pub lock: String,
+    /// How the lock was made, see [`Self::LOCK_VERSION`]
+    #[serde(default)]
+    pub lock_version: u32,
+    #[serde(default)]
+    pub broken: bool,
+}
+
+fn builtin_content() -> String {
+    String::from(config::BUILTIN_CONTENT)
+}
+
+impl Ironman {
+    /// Locks from before version 1 hashed the whole content pack, so any update broke them.
+    /// Version 1 locks were SHA-256s
+    pub const LOCK_VERSION: u32 = 2;
+
+    /// Lock in `seed` and `rules`, along with the id of the current content
+    pub fn new(seed: u64, rules: RuleSet) -> Self {
+        let content = config::content().id.to_string();
+        Self {
+            lock: Self::hash(seed, &rules, &content),
+            seed,
+            rules,
+            content,
+            lock_version: Self::LOCK_VERSION,
+            broken: false,
+        }
+    }
+
+    /// Break the lock if the seed, rules or content changed since it was made, with `content`
+    /// being what's in play now. Returns whether it still holds
+    pub fn check(&mut self, content: &config::ContentPack) -> bool {
+        if self.lock_version < Self::LOCK_VERSION {
+            // the old lock can't be checked, since the content it hashed has changed since
+            self.lock = Self::hash(self.seed, &self.rules, &self.content);
+            self.lock_version = Self::LOCK_VERSION;
+        }
+        self.broken |= self.content != content.id
+            || Self::hash(self.seed, &self.rules, &self.content) != self.lock;
+        !self.broken
+    }
+
+    /// Where the randomness picks up for a session that starts after `tasks` finished tasks, so a
+    /// run plays out the same way however often it's saved and loaded
+    pub const fn session_seed(&self, tasks: usize) -> u64 {
+        self.seed.wrapping_add(tasks as u64)
+    }
+
+    /// FNV-1a, which unlike std's hasher is the same from one build to the next
+    fn hash(seed: u64, rules: &RuleSet, content: &str) -> String {
+        let hash = format!("{seed}\n{rules:?}\n{content}")
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            });
+        format!("{hash:016x}")
+    }
+}
+
+impl std::fmt::Display for Ironman {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.broken {
+            return write!(f, "seed {}, lock broken", self.seed);
+        }
+        // enough to tell locks apart at a glance
+        write!(f, "seed {}, lock {:.12}", self.seed, self.lock)
+    }
+}
+
+#[test]
+fn ironman_lock() {
+    let content = config::ContentPack::default();
+    let mut ironman = Ironman::new(42, RuleSet::default());
+    assert!(ironman.check(&content));
+    assert_eq!(ironman, Ironman::new(42, RuleSet::default()));
+    assert_ne!(ironman.lock, Ironman::new(43, RuleSet::default()).lock);
+
+    // other content with the same id is the same content
+    let patched = config::ContentPack {
+        dungeons: vec![],
+        ..config::ContentPack::default()
+    };
+    assert!(ironman.check(&patched));
+    let modded = config::ContentPack {
+        id: "frozen-north-2".into(),
+        ..config::ContentPack::default()
+    };
+    assert!(!ironman.clone().check(&modded));
+
+    ironman.rules = RuleSet::classic();
+    assert!(!ironman.check(&content));
+    // and it stays broken
+    ironman.rules = RuleSet::default();
+    assert!(!ironman.check(&content));
+
+    // older locks are remade rather than broken
+    let mut old = Ironman::new(42, RuleSet::default());
+    old.lock = String::from("hash of the whole content pack");
+    old.lock_version = 0;
+    assert!(old.check(&content));
+    assert_eq!(old, Ironman::new(42, RuleSet::default()));
+}
@@ -0,0 +1,106 @@
+//! Keeps a game ticking on a thread of its own, so it goes at the same pace however often the
+//! frontend redraws it, even while it isn't redrawn at all.
+//!
+//! There are no threads on the web, so there the frontend calls [`Runner::poll`] every frame to
+//! tick in the thread's place.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crate::mechanics::Simulation;
+
+/// Something a [`Runner`] can keep going
+pub trait Tick {
+    fn tick(&mut self);
+}
+
+impl Tick for Simulation {
+    fn tick(&mut self) {
+        Simulation::tick(self)
+    }
+}
+
+pub struct Runner<T> {
+    state: Arc<Mutex<T>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl<T: Tick + Send + 'static> Runner<T> {
+    /// How long the thread waits between ticks
+    pub const INTERVAL: Duration = Duration::from_millis(50);
+
+    /// Start ticking `state`
+    pub fn spawn(state: T) -> Self {
+        let state = Arc::new(Mutex::new(state));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let thread = {
+            let (state, stop) = (Arc::clone(&state), Arc::clone(&stop));
+            Some(std::thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    lock(&state).tick();
+                    std::thread::sleep(Self::INTERVAL);
+                }
+            }))
+        };
+        #[cfg(target_arch = "wasm32")]
+        let thread = None;
+
+        Self {
+            state,
+            stop,
+            thread,
+        }
+    }
+
+    /// Tick now if there's no thread to do it
+    pub fn poll(&self) {
+        if self.thread.is_none() {
+            self.lock().tick()
+        }
+    }
+}
+
+impl<T> Runner<T> {
+    /// Hold off the ticking while the game is looked at or changed
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        lock(&self.state)
+    }
+}
+
+impl<T> Drop for Runner<T> {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+// a tick that panicked doesn't leave the game any less playable than a tick that never happened
+fn lock<T>(state: &Mutex<T>) -> MutexGuard<'_, T> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[test]
+fn runs_in_the_background() {
+    use crate::{mechanics::TickPolicy, testing};
+
+    let mut simulation = testing::simulation();
+    simulation.set_tick_policy(TickPolicy::Turbo);
+
+    let runner = Runner::spawn(simulation);
+    let start = std::time::Instant::now();
+    while runner.lock().player.statistics.tasks_finished < 3 {
+        assert!(start.elapsed() < Duration::from_secs(10), "nothing ticked");
+        std::thread::sleep(Runner::<Simulation>::INTERVAL);
+    }
+}
@@ -0,0 +1,145 @@
+//! The versioned save format, holding every player along with a frontend's settings.
+//!
+//! A save is gzipped RON with a header line giving its version, sealed with a checksum (see
+//! [`integrity`]) so corrupted and edited saves can be caught. Saves from older versions are
+//! upgraded on load by the migrations at the bottom of this module, one version at a time.
+
+use crate::{compress, integrity, mechanics::Player, profile::Profile};
+
+/// The version written by [`SaveFile::encode`]
+pub const VERSION: u32 = 1;
+
+const HEADER: &str = "// pacing save v";
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct SaveFile<S> {
+    pub version: u32,
+    pub players: Vec<Player>,
+    #[serde(default)]
+    pub profile: Profile,
+    #[serde(default)]
+    pub settings: S,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The save couldn't be decompressed or parsed
+    Corrupted(String),
+    /// The save was written by a newer version, so loading it would lose data
+    TooNew(u32),
+    Serialize(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Corrupted(err) => write!(f, "the save is corrupted: {err}"),
+            Self::TooNew(version) => write!(
+                f,
+                "the save is from version {version}, but only up to {VERSION} is supported"
+            ),
+            Self::Serialize(err) => write!(f, "cannot serialize the save: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl<S> SaveFile<S> {
+    pub fn new(players: Vec<Player>, profile: Profile, settings: S) -> Self {
+        Self {
+            version: VERSION,
+            players,
+            profile,
+            settings,
+        }
+    }
+}
+
+impl<S> SaveFile<S>
+where
+    S: Default + serde::Serialize + serde::de::DeserializeOwned,
+{
+    /// Serialize, seal and compress the save, sealing it with an HMAC if there's a `key`
+    pub fn encode(&self, key: Option<&[u8]>) -> Result<Vec<u8>, Error> {
+        let body = ron::ser::to_string(self).map_err(|err| Error::Serialize(err.to_string()))?;
+        let data = integrity::seal(&format!("{HEADER}{}\n{body}", self.version), key);
+        compress::compress(data.as_bytes()).map_err(|err| Error::Serialize(err.to_string()))
+    }
+
+    /// Load a save, migrating it to the current [`VERSION`].
+    ///
+    /// Players in a save whose seal doesn't match `key` are marked as tampered with
+    pub fn decode(data: &[u8], key: Option<&[u8]>) -> Result<Self, Error> {
+        let data = compress::decompress(data).map_err(corrupted)?;
+        let data = std::str::from_utf8(&data).map_err(corrupted)?;
+        let (data, verification) = integrity::verify(data, key);
+
+        let (version, body) = split_header(data);
+        if version > VERSION {
+            return Err(Error::TooNew(version));
+        }
+
+        let mut save = match version {
+            0 => v0_to_v1(body)?,
+            _ => ron::from_str(body).map_err(corrupted)?,
+        };
+        for player in &mut save.players {
+            player.tampered |= verification.is_tampered();
+        }
+        Ok(save)
+    }
+}
+
+/// `$XDG_DATA_HOME/pacing`, falling back to `~/.local/share/pacing`, or the current directory
+pub fn data_dir() -> std::path::PathBuf {
+    use std::path::{Path, PathBuf};
+
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .map_or_else(|| PathBuf::from("."), |dir| dir.join("pacing"))
+}
+
+/// The version from the header, and the rest of the save. Saves without a header are version 0
+fn split_header(data: &str) -> (u32, &str) {
+    data.strip_prefix(HEADER)
+        .and_then(|rest| rest.split_once('\n'))
+        .and_then(|(version, body)| Some((version.trim().parse().ok()?, body)))
+        .unwrap_or((0, data))
+}
+
+fn corrupted(err: impl std::fmt::Display) -> Error {
+    Error::Corrupted(err.to_string())
+}
+
+/// Version 0 was the bare list of players, from before settings were saved alongside them
+fn v0_to_v1<S: Default>(body: &str) -> Result<SaveFile<S>, Error> {
+    let players = ron::from_str(body).map_err(corrupted)?;
+    Ok(SaveFile {
+        version: 1,
+        players,
+        profile: Profile::default(),
+        settings: S::default(),
+    })
+}
+
+#[test]
+fn versions() {
+    assert_eq!(split_header("[]"), (0, "[]"));
+    assert_eq!(
+        split_header("// pacing save v1\n(version: 1)"),
+        (1, "(version: 1)")
+    );
+    assert_eq!(
+        split_header("// pacing save vX\n[]"),
+        (0, "// pacing save vX\n[]")
+    );
+
+    let newer = integrity::seal("// pacing save v99\n(version: 99)", None);
+    assert!(matches!(
+        SaveFile::<()>::decode(newer.as_bytes(), None),
+        Err(Error::TooNew(99))
+    ));
+}
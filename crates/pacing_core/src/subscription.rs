@@ -0,0 +1,119 @@
+//! Coarse progress notifications for the current task: a quarter of the way through, halfway,
+//! three quarters and done.
+//!
+//! These suit consumers that only want to hear about the game now and then, like webhooks and chat
+//! bots, better than watching the bars every tick. Callbacks are subscribed on the
+//! [`Simulation`](crate::mechanics::Simulation), and called as it crosses each quarter.
+
+use crate::mechanics::Bar;
+
+/// One quarter of a task that was reached
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct TaskProgress {
+    pub task: String,
+    /// 25, 50, 75 or 100
+    pub percent: u8,
+    /// The game time it was reached at
+    pub elapsed: f32,
+}
+
+/// Returned when subscribing, to unsubscribe with later
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(usize);
+
+type Callback = Box<dyn FnMut(&TaskProgress) + Send>;
+
+#[derive(Default)]
+pub struct Subscribers {
+    callbacks: Vec<(SubscriptionId, Callback)>,
+    next: usize,
+    /// The task being followed, by how many tasks were finished before it, and how many quarters
+    /// of it were announced
+    reached: (usize, u8),
+}
+
+impl Subscribers {
+    const QUARTERS: u8 = 4;
+
+    pub fn subscribe(
+        &mut self,
+        callback: impl FnMut(&TaskProgress) + Send + 'static,
+    ) -> SubscriptionId {
+        let id = SubscriptionId(self.next);
+        self.next += 1;
+        self.callbacks.push((id, Box::new(callback)));
+        id
+    }
+
+    /// Returns whether `id` was subscribed
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let len = self.callbacks.len();
+        self.callbacks.retain(|(subscribed, _)| *subscribed != id);
+        self.callbacks.len() != len
+    }
+
+    pub fn len(&self) -> usize {
+        self.callbacks.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+
+    /// Announce the quarters of the task that were crossed since the last update. `completed` is
+    /// how many tasks were finished before this one
+    pub(crate) fn update(&mut self, completed: usize, task: Option<&str>, bar: &Bar, elapsed: f32) {
+        let Some(task) = task else {
+            return;
+        };
+        if self.reached.0 != completed {
+            self.reached = (completed, 0);
+        }
+
+        let quarters = if bar.max > 0.0 {
+            ((bar.pos / bar.max) * f32::from(Self::QUARTERS)) as u8
+        } else {
+            Self::QUARTERS
+        }
+        .min(Self::QUARTERS);
+
+        // a task can cross several quarters in one step, each one is still announced
+        for quarter in self.reached.1 + 1..=quarters {
+            let progress = TaskProgress {
+                task: task.to_string(),
+                percent: quarter * (100 / Self::QUARTERS),
+                elapsed,
+            };
+            for (_, callback) in &mut self.callbacks {
+                callback(&progress)
+            }
+        }
+        self.reached.1 = self.reached.1.max(quarters);
+    }
+}
+
+#[test]
+fn quarters() {
+    use std::sync::{Arc, Mutex};
+
+    let heard = Arc::new(Mutex::new(vec![]));
+    let mut subscribers = Subscribers::default();
+    let id = subscribers.subscribe({
+        let heard = heard.clone();
+        move |progress| heard.lock().unwrap().push(progress.percent)
+    });
+
+    let mut bar = Bar::with_max(8.0);
+    for pos in [1.0, 2.0, 3.0, 7.0, 8.0, 8.0] {
+        bar.pos = pos;
+        subscribers.update(0, Some("Waiting"), &bar, 0.0);
+    }
+    bar.pos = 4.0;
+    subscribers.update(1, Some("Waiting again"), &bar, 0.0);
+    assert_eq!(*heard.lock().unwrap(), [25, 50, 75, 100, 25, 50]);
+
+    assert!(subscribers.unsubscribe(id));
+    assert!(!subscribers.unsubscribe(id));
+    assert!(subscribers.is_empty());
+}
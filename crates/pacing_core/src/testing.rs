@@ -0,0 +1,27 @@
+//! The hero the tests play with, so each test doesn't have to roll its own
+
+use crate::{
+    config,
+    mechanics::{Player, Simulation, Stats, StatsBuilder},
+    Rand,
+};
+
+/// Gorbash, with stats rolled from a fixed seed
+pub fn hero() -> Player {
+    hero_with(StatsBuilder::default().roll(&Rand::seed(42)))
+}
+
+/// Gorbash, with the given `stats`
+pub fn hero_with(stats: Stats) -> Player {
+    Player::new(
+        "Gorbash",
+        config::RACES[0].clone(),
+        config::CLASSES[0].clone(),
+        stats,
+    )
+}
+
+/// A seeded simulation of [`hero`], so it plays out the same way every run
+pub fn simulation() -> Simulation {
+    Simulation::with_seed(hero(), 7)
+}
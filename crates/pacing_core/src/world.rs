@@ -0,0 +1,156 @@
+//! A lightweight world map: a string of regions, each home to monsters of a band of levels.
+//!
+//! The map is made up the first time the hero heads out. Every trip out goes to the region for the
+//! hero's level, and the monsters found there are kept to its band, so a hero who outlevels a
+//! region only finds easy prey until they move on to the next one.
+//!
+//! Each region has a [`Climate`], and the quests handed out after a trip there lean toward it:
+//! fetching glacier ice after a trip to the Frozen Marches, or placating the undead of a haunted
+//! one. Each act has a climate as its theme too, which they lean toward just the same.
+
+use std::ops::Range;
+
+use crate::{
+    config::{self, Climate},
+    lingo::generate_name,
+    Rand, SliceExt,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Region {
+    pub name: String,
+    /// The levels of the monsters found here
+    pub levels: Range<usize>,
+    /// Regions from older saves don't have one
+    #[serde(default)]
+    pub climate: Option<Climate>,
+}
+
+impl Region {
+    /// The level of a monster found here by a hero of `level`
+    pub fn monster_level(&self, level: usize) -> usize {
+        level.clamp(self.levels.start, self.levels.end - 1)
+    }
+}
+
+impl std::fmt::Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct WorldMap {
+    regions: Vec<Region>,
+    /// The region the hero last went out to
+    region: Option<usize>,
+    in_town: bool,
+}
+
+impl WorldMap {
+    pub const REGIONS: usize = 8;
+    /// How many levels each region spans. The last one has no upper bound
+    pub const BAND: usize = 5;
+
+    pub fn generate(rng: &Rand) -> Self {
+        let content = config::content();
+        let regions = (0..Self::REGIONS)
+            .map(|i| {
+                let start = 1 + i * Self::BAND;
+                let end = if i + 1 == Self::REGIONS {
+                    usize::MAX
+                } else {
+                    start + Self::BAND
+                };
+                let climate = *Climate::ALL.choice(rng);
+                Region {
+                    name: format!(
+                        "the {climate} {} of {}",
+                        content.terrains.choice(rng),
+                        generate_name(None, rng),
+                    ),
+                    levels: start..end,
+                    climate: Some(climate),
+                }
+            })
+            .collect();
+
+        Self {
+            regions,
+            region: None,
+            in_town: true,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    pub fn regions(&self) -> impl ExactSizeIterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    /// The region the hero is in, `None` while in town
+    pub fn current(&self) -> Option<&Region> {
+        self.regions.get(self.region.filter(|_| !self.in_town)?)
+    }
+
+    /// Where the hero is, for showing on a character sheet
+    pub fn location(&self) -> String {
+        self.current()
+            .map_or_else(|| String::from("Town"), |region| region.to_string())
+    }
+
+    /// Go out to the region for a hero of `level`, returning it if it's a different one
+    pub fn travel(&mut self, level: usize) -> Option<&Region> {
+        let next = self
+            .regions
+            .iter()
+            .position(|region| region.levels.contains(&level))
+            .or_else(|| self.regions.len().checked_sub(1))?;
+        self.in_town = false;
+        let last = self.region.replace(next);
+        (last != Some(next)).then(|| &self.regions[next])
+    }
+
+    /// The region the hero was last in, even after heading back to town
+    pub fn last(&self) -> Option<&Region> {
+        self.regions.get(self.region?)
+    }
+
+    pub fn climate(&self) -> Option<Climate> {
+        self.last()?.climate
+    }
+
+    pub fn return_to_town(&mut self) {
+        self.in_town = true;
+    }
+
+    /// The level of a monster found by a hero of `level`, kept to the current region's band
+    pub fn monster_level(&self, level: usize) -> usize {
+        self.current()
+            .map_or(level, |region| region.monster_level(level))
+    }
+}
+
+#[test]
+fn regions_cover_every_level() {
+    let mut world = WorldMap::generate(&Rand::seed(42));
+    assert_eq!(world.location(), "Town");
+    assert!(world.climate().is_none());
+    assert_eq!(world.monster_level(7), 7);
+
+    let first = world.travel(3).cloned().unwrap();
+    assert_eq!(first.levels, 1..6);
+    assert!(world.travel(4).is_none());
+    assert_eq!(world.monster_level(9), 5);
+
+    world.return_to_town();
+    assert_eq!(world.location(), "Town");
+    assert_eq!(world.climate(), first.climate);
+    assert!(world.travel(4).is_none());
+
+    let far = world.travel(500).cloned().unwrap();
+    assert_eq!(far.monster_level(500), 500);
+    assert_eq!(world.location(), far.name);
+}
@@ -1,9 +1,21 @@
 #![cfg_attr(debug_assertions, allow(dead_code, unused_variables,))]
 
 mod progress;
+mod save;
+mod settings;
+mod storage;
+mod toast;
+#[cfg(not(target_arch = "wasm32"))]
+mod tray;
+#[cfg(all(feature = "update-check", not(target_arch = "wasm32")))]
+mod update;
 mod view;
 
 use pacing_core::*;
 
 mod main_window;
 pub use main_window::MainWindow;
+#[cfg(not(target_arch = "wasm32"))]
+pub use settings::Launch;
+#[cfg(not(target_arch = "wasm32"))]
+pub use tray::window_icon;
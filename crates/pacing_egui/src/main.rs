@@ -5,37 +5,13 @@ use pacing_egui::MainWindow;
 
 #[cfg(not(target_arch = "wasm32"))]
 fn main() {
-    use tray_icon::TrayIconBuilder;
-
-    let (icon, tray_icon) = {
-        const DATA: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/icon.png"));
-        let img = ::image::load_from_memory_with_format(DATA, ::image::ImageFormat::Png)
-            .expect("valid icon");
-
-        let (width, height) = (img.width(), img.height());
-        let bytes = img.into_bytes();
-        (
-            eframe::IconData {
-                width,
-                height,
-                rgba: bytes.clone(),
-            },
-            tray_icon::icon::Icon::from_rgba(bytes, width, width).unwrap(),
-        )
-    };
-
-    let _tray_icon = TrayIconBuilder::new()
-        .with_tooltip("Pacing")
-        .with_icon(tray_icon)
-        .with_tooltip("Toggle Pacing")
-        .build()
-        .unwrap();
+    let launch = pacing_egui::Launch::load();
 
     eframe::run_native(
         "Pacing",
         eframe::NativeOptions {
-            icon_data: Some(icon),
-            ..Default::default()
+            icon_data: Some(pacing_egui::window_icon()),
+            ..launch.native_options()
         },
         Box::new(|cc| Box::new(MainWindow::new(cc))),
     )
@@ -1,19 +1,33 @@
 use std::time::Duration;
 
 use egui::{
-    style::Margin, Align, Button, CentralPanel, Color32, Frame, Label, Layout, RichText, Rounding,
-    ScrollArea, Sense, SidePanel, Stroke, TextEdit, TopBottomPanel,
+    style::Margin, vec2, Align, Align2, Area, Button, CentralPanel, CollapsingHeader, Color32,
+    FontId, Frame, Label, Layout, Order, Pos2, Rect, RichText, Rounding, ScrollArea, Sense,
+    SidePanel, Slider, Stroke, TextEdit, TopBottomPanel, Window,
 };
 use pacing_core::{Rand, SliceExt};
-use tray_icon::TrayEvent;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::tray::{Command, Tray};
 use crate::{
-    config,
-    format::Roman,
-    lingo::{act_name, generate_name},
-    mechanics::{Player, Simulation, StatsBuilder},
+    balance,
+    checkpoint::{SessionSummary, Timeline},
+    config::{self, Hint},
+    format::{CharacterSheet, Roman, SheetStyle, Time},
+    highlights::Scoring,
+    integrity,
+    lingo::{self, act_name, generate_name},
+    mechanics::{
+        Bar, Equipment, Player, Quest, RollMode, Simulation, StatsBuilder, TickPolicy,
+        TimeController, TitleCard,
+    },
+    profile::Profile,
     progress::Progress,
-    view::View,
+    rules, save,
+    settings::{Palette, Settings},
+    storage,
+    toast::Toasts,
+    view::{self, Game, View},
 };
 
 #[derive(Default)]
@@ -35,8 +49,12 @@ enum CreationResult {
 #[derive(Default)]
 enum SelectionResult {
     Selected(usize),
+    /// Couch mode, with the first hero on the left and the second on the right
+    Together(usize, usize),
     Details(usize),
+    HallOfFame,
     Create,
+    ShowSettings,
     #[default]
     Nothing,
 }
@@ -44,25 +62,129 @@ enum SelectionResult {
 pub struct MainWindow {
     rng: Rand,
     view: View,
+    profile: Profile,
+    settings: Settings,
+    show_settings: bool,
+    /// Show every hint, even ones that were dismissed
+    show_hints: bool,
     is_visible: bool,
+    /// Whether the window is sized for mini mode, so changing the setting resizes it
+    mini: bool,
+    #[cfg(all(feature = "update-check", not(target_arch = "wasm32")))]
+    update_check: crate::update::UpdateCheck,
+    toasts: Toasts,
+    show_debug: bool,
+    /// When the checkpoint picked on the debug timeline was taken, or `None` to follow the latest one
+    scrub: Option<f32>,
+    /// Reloads the content pack named by `PACING_CONTENT` whenever it changes
+    #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+    content_watcher: Option<pacing_core::reload::ContentWatcher>,
+    time: TimeController,
+    /// When the game was last saved while playing, in seconds since the app started
+    last_autosave: f64,
+    /// Shown in place of closing the first time the app is asked to quit
+    farewell: Option<SessionSummary>,
+    #[cfg(not(target_arch = "wasm32"))]
+    tray: Option<Tray>,
+    unreadable_save: Option<UnreadableSave>,
+    /// See [`storage::seal_key`]
+    seal_key: String,
+}
+
+/// A save that couldn't be loaded. It's backed up, and nothing is saved over it until the player
+/// agrees to
+struct UnreadableSave {
+    data: String,
+    error: String,
+    backed_up: bool,
+    replace: bool,
+    dismissed: bool,
 }
 
 impl MainWindow {
     const SETTINGS_KEY: &'static str = concat!(env!("CARGO_PKG_NAME"), "_settings");
-    const FRAME_RATE: Duration = Duration::from_millis(16);
+    const CONFIG_KEY: &'static str = concat!(env!("CARGO_PKG_NAME"), "_config");
 
     pub fn new(cc: &eframe::CreationContext) -> Self {
         // TODO seed this
         let rng = Rand::new();
 
-        if let Some(storage) = cc.storage {
-            if let Some(players) = eframe::get_value(storage, Self::SETTINGS_KEY) {
-                return Self {
-                    rng,
-                    view: View::CharacterSelect { players },
-                    is_visible: true,
-                };
+        let mut toasts = Toasts::default();
+        let mut unreadable_save = None;
+        let seal_key = storage::seal_key(cc.storage);
+        let key = &seal_key;
+        let save = storage::load(cc.storage).and_then(|data| match save::decode(&data, key) {
+            Ok(save) => Some(save),
+            Err(error) => {
+                unreadable_save = Some(UnreadableSave {
+                    data,
+                    error,
+                    backed_up: false,
+                    replace: false,
+                    dismissed: false,
+                });
+                None
+            }
+        });
+
+        // older versions kept the settings apart from the players
+        let (players, profile, settings) = match (cc.storage, save) {
+            (Some(storage), Some(save)) => (
+                Some(save.players),
+                save.profile,
+                eframe::get_value(storage, Self::CONFIG_KEY).unwrap_or(save.settings),
+            ),
+            // and before that, stored the players uncompressed
+            (Some(storage), None) => (
+                eframe::get_value(storage, Self::SETTINGS_KEY),
+                Profile::default(),
+                eframe::get_value(storage, Self::CONFIG_KEY).unwrap_or_default(),
+            ),
+            (None, _) => (None, Profile::default(), Settings::default()),
+        };
+        let settings = settings.clamped();
+        settings.apply();
+        settings.apply_style(&cc.egui_ctx);
+        settings.apply_zoom(&cc.egui_ctx, cc.integration_info.native_pixels_per_point);
+        toasts.set_muted(!settings.notifications);
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Err(err) = settings.load_content() {
+            toasts.error(err);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        let tray = match Tray::new() {
+            Ok(tray) => Some(tray),
+            Err(err) => {
+                toasts.error(format!("The tray icon couldn't be created: {err}"));
+                None
             }
+        };
+
+        if let Some(players) = players {
+            return Self {
+                rng,
+                view: View::CharacterSelect { players },
+                profile,
+                mini: settings.mini,
+                settings,
+                show_settings: false,
+                show_hints: false,
+                is_visible: true,
+                #[cfg(all(feature = "update-check", not(target_arch = "wasm32")))]
+                update_check: crate::update::UpdateCheck::start(&cc.egui_ctx),
+                toasts,
+                show_debug: false,
+                scrub: None,
+                #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+                content_watcher: Self::watch_content(),
+                time: TimeController::default(),
+                last_autosave: 0.0,
+                farewell: None,
+                #[cfg(not(target_arch = "wasm32"))]
+                tray,
+                unreadable_save,
+                seal_key,
+            };
         }
 
         let (player, stats_builder) = Self::make_new_character(&rng);
@@ -73,30 +195,66 @@ impl MainWindow {
                 stats_builder,
                 players: vec![],
             },
+            profile,
+            mini: settings.mini,
+            settings,
+            show_settings: false,
+            show_hints: false,
             is_visible: true,
+            #[cfg(all(feature = "update-check", not(target_arch = "wasm32")))]
+            update_check: crate::update::UpdateCheck::start(&cc.egui_ctx),
+            toasts,
+            show_debug: false,
+            scrub: None,
+            #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+            content_watcher: Self::watch_content(),
+            time: TimeController::default(),
+            last_autosave: 0.0,
+            farewell: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            tray,
+            unreadable_save,
+            seal_key,
         }
     }
 
-    fn success_button(text: impl Into<String>) -> Button {
-        const SUCCESS_FILL: Color32 = Color32::from_rgb(0x21, 0x36, 0x54);
-        const SUCCESS_TEXT: Color32 = Color32::from_rgb(0x8d, 0xb6, 0xf2);
+    #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+    fn watch_content() -> Option<pacing_core::reload::ContentWatcher> {
+        std::env::var_os("PACING_CONTENT").map(pacing_core::reload::ContentWatcher::new)
+    }
 
-        Button::new(RichText::new(text).color(SUCCESS_TEXT)).fill(SUCCESS_FILL)
+    #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+    fn reload_content(&mut self) {
+        let Some(watcher) = &mut self.content_watcher else {
+            return;
+        };
+        match watcher.poll() {
+            Some(Ok(())) => self.toasts.info(format!(
+                "Reloaded the content from {}",
+                watcher.path().display()
+            )),
+            Some(Err(err)) => self.toasts.error(err),
+            None => {}
+        }
     }
 
-    fn caution_button(text: impl Into<String>) -> Button {
-        const CAUTION_FILL: Color32 = Color32::from_rgb(0x57, 0x26, 0x22);
-        const CAUTION_TEXT: Color32 = Color32::from_rgb(0xf2, 0x94, 0x94);
+    fn success_button(ui: &egui::Ui, text: impl Into<String>) -> Button {
+        let palette = Palette::of(ui.ctx());
+        Button::new(RichText::new(text).color(palette.success_text)).fill(palette.success_fill)
+    }
 
-        Button::new(RichText::new(text).color(CAUTION_TEXT)).fill(CAUTION_FILL)
+    fn caution_button(ui: &egui::Ui, text: impl Into<String>) -> Button {
+        let palette = Palette::of(ui.ctx());
+        Button::new(RichText::new(text).color(palette.caution_text)).fill(palette.caution_fill)
     }
 
     fn make_new_character(rng: &Rand) -> (Player, StatsBuilder) {
         let mut stats_builder = StatsBuilder::default();
+        let content = config::content();
         let player = Player::new(
             generate_name(None, rng),
-            config::RACES.choice(rng).clone(),
-            config::CLASSES.choice(rng).clone(),
+            content.races.choice(rng).clone(),
+            content.classes.choice(rng).clone(),
             stats_builder.roll(rng),
         );
 
@@ -113,25 +271,92 @@ impl MainWindow {
         }
     }
 
-    fn display_character_detail(player: &Player, ui: &mut egui::Ui) -> DetailsResult {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_history(player: &Player, toasts: &mut Toasts) {
+        let path = format!(
+            "{}_history.jsonl",
+            player.name.trim().to_lowercase().replace(' ', "_")
+        );
+        match std::fs::File::create(&path)
+            .and_then(|file| crate::export::jsonl(player, std::io::BufWriter::new(file)))
+        {
+            Ok(()) => toasts.info(format!("Wrote the history to {path}")),
+            Err(err) => toasts.error(format!("Cannot write {path}: {err}")),
+        }
+    }
+
+    fn display_character_detail(
+        player: &mut Player,
+        toasts: &mut Toasts,
+        ui: &mut egui::Ui,
+    ) -> DetailsResult {
         let mut out = DetailsResult::default();
         ui.horizontal(|ui| {
             ui.heading(&player.name);
             ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                if ui.add(Self::success_button("Play")).clicked() {
+                if ui.add(Self::success_button(ui, "Play")).clicked() {
                     out = DetailsResult::Play;
                 }
-                if ui.add(Self::caution_button("Close")).clicked() {
+                if ui.add(Self::caution_button(ui, "Close")).clicked() {
                     out = DetailsResult::Close;
                 }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui
+                    .button("Export history")
+                    .on_hover_text("Write the journal to the current directory as JSON Lines")
+                    .clicked()
+                {
+                    Self::export_history(player, toasts);
+                }
+
+                ui.menu_button("Copy sheet", |ui| {
+                    for style in SheetStyle::ALL {
+                        if ui.button(style.as_str()).clicked() {
+                            ui.output().copied_text =
+                                CharacterSheet::new(player, style).to_string();
+                            ui.close_menu();
+                        }
+                    }
+                });
             });
         });
         ui.separator();
 
+        let tab_id = ui.id().with("detail_tab");
+        let mut statistics = ui.data().get_temp::<bool>(tab_id).unwrap_or(false);
+        ui.horizontal(|ui| {
+            if ui.selectable_label(!statistics, "Details").clicked() {
+                statistics = false
+            }
+            if ui.selectable_label(statistics, "Statistics").clicked() {
+                statistics = true
+            }
+        });
+        ui.data().insert_temp(tab_id, statistics);
+        ui.separator();
+
+        if statistics {
+            egui::Grid::new("statistics")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (label, value) in player.statistics.table(player.elapsed) {
+                        ui.monospace(label);
+                        ui.label(value);
+                        ui.end_row();
+                    }
+                });
+            return out;
+        }
+
         ScrollArea::vertical()
             .id_source("detail_list")
             .show(ui, |ui| {
                 ui.heading("Details");
+                if player.tampered {
+                    ui.colored_label(Color32::LIGHT_RED, integrity::TAMPERED);
+                }
                 ui.horizontal(|ui| {
                     ui.monospace("Level");
                     ui.label(player.level.to_string());
@@ -146,6 +371,38 @@ impl MainWindow {
                     ui.monospace("Race");
                     ui.label(&*player.race.name);
                 });
+
+                ui.horizontal(|ui| {
+                    ui.monospace("Rolled");
+                    ui.label(player.roll_mode.to_string());
+                });
+
+                ui.heading("Notes");
+                ui.add(
+                    TextEdit::multiline(&mut player.notes)
+                        .hint_text("Anything worth remembering about this hero")
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(3),
+                );
+
+                let scoring = Scoring::default();
+                let highlights = scoring.top(&player.journal, 5);
+                if !highlights.is_empty() {
+                    ui.heading("Highlights");
+                    for highlight in highlights {
+                        ui.label(highlight.entry.event.to_string());
+                    }
+
+                    for (act, highlights) in scoring.by_act(player, 3) {
+                        CollapsingHeader::new(act_name(act))
+                            .id_source(("highlights", act))
+                            .show(ui, |ui| {
+                                for highlight in highlights {
+                                    ui.label(highlight.entry.event.to_string());
+                                }
+                            });
+                    }
+                }
             });
 
         ui.separator();
@@ -165,9 +422,34 @@ impl MainWindow {
         out
     }
 
-    fn display_character_select(players: &mut Vec<Player>, ui: &mut egui::Ui) -> SelectionResult {
+    fn display_character_select(
+        players: &mut Vec<Player>,
+        profile: &mut Profile,
+        ui: &mut egui::Ui,
+    ) -> SelectionResult {
         let mut selection = SelectionResult::default();
         let mut remove = Option::<usize>::None;
+        // the first hero picked for couch mode, while waiting on the second
+        let pick_id = egui::Id::new("couch_pick");
+        let mut pick = ui
+            .data()
+            .get_temp::<usize>(pick_id)
+            .filter(|&first| first < players.len());
+
+        let totals = profile.totals(players.iter());
+        ui.horizontal(|ui| {
+            for (label, value) in [
+                ("Heroes created", totals.heroes.to_string()),
+                ("Playtime", Time::compact(totals.playtime)),
+                ("Acts cleared", totals.acts_cleared.to_string()),
+                ("Ascended", totals.ascended.to_string()),
+            ] {
+                ui.label(format!("{label}:"));
+                ui.strong(value);
+                ui.separator();
+            }
+        });
+        ui.separator();
 
         ScrollArea::vertical().show(ui, |ui| {
             for (i, player) in players.iter().enumerate() {
@@ -176,14 +458,44 @@ impl MainWindow {
                     .show(ui, |ui| {
                         ui.horizontal(|ui| {
                             ui.heading(&player.name);
+                            ui.monospace(format!("power {}", player.power_level()))
+                                .on_hover_text("Level, stats, equipment and spells combined");
                             ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                                if ui.add(Self::success_button("Play")).clicked() {
+                                if ui.add(Self::success_button(ui, "Play")).clicked() {
                                     selection = SelectionResult::Selected(i);
                                 }
 
-                                if ui.add(Self::caution_button("Delete")).clicked() {
+                                if ui.add(Self::caution_button(ui, "Delete")).clicked() {
                                     remove.replace(i);
                                 }
+
+                                if players.len() < 2 {
+                                    return;
+                                }
+                                match pick {
+                                    Some(first) if first == i => {
+                                        if ui.button("Cancel").clicked() {
+                                            pick = None
+                                        }
+                                    }
+                                    Some(first) => {
+                                        let join = ui.button("Join").on_hover_text(format!(
+                                            "Play side by side with {}",
+                                            players[first].name
+                                        ));
+                                        if join.clicked() {
+                                            selection = SelectionResult::Together(first, i);
+                                        }
+                                    }
+                                    None => {
+                                        let couch = ui
+                                            .button("Couch")
+                                            .on_hover_text("Pick a second hero to play alongside");
+                                        if couch.clicked() {
+                                            pick = Some(i)
+                                        }
+                                    }
+                                }
                             });
                         });
                     })
@@ -207,16 +519,91 @@ impl MainWindow {
         });
 
         if let Some(index) = remove.take() {
-            players.remove(index);
+            profile.deleted(&players.remove(index));
+            pick = None;
         }
-
-        if ui.button("Create new character").clicked() {
-            selection = SelectionResult::Create
+        match pick {
+            Some(first) if matches!(selection, SelectionResult::Nothing) => {
+                ui.data().insert_temp(pick_id, first);
+            }
+            _ => {
+                ui.data().remove::<usize>(pick_id);
+            }
         }
 
+        ui.horizontal(|ui| {
+            if ui.button("Create new character").clicked() {
+                selection = SelectionResult::Create
+            }
+            if ui.button("Hall of fame").clicked() {
+                selection = SelectionResult::HallOfFame
+            }
+            if ui.button("Settings").clicked() {
+                selection = SelectionResult::ShowSettings
+            }
+        });
+
         selection
     }
 
+    /// Returns whether it was closed
+    fn display_hall_of_fame(profile: &Profile, ui: &mut egui::Ui) -> bool {
+        let mut closed = false;
+        ui.horizontal(|ui| {
+            ui.heading("Hall of fame");
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                closed = ui.button("Back").clicked();
+            });
+        });
+        ui.separator();
+
+        if profile.retired().next().is_none() {
+            ui.weak("Heroes you delete are remembered here");
+            return closed;
+        }
+
+        ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("hall_of_fame")
+                .num_columns(5)
+                .striped(true)
+                .show(ui, |ui| {
+                    for heading in ["Name", "Level", "Playtime", "Reached", "Best item"] {
+                        ui.strong(heading);
+                    }
+                    ui.end_row();
+
+                    // the most recently retired first
+                    for hero in profile.retired().rev() {
+                        ui.label(&hero.name)
+                            .on_hover_text(format!("{} {}", hero.race, hero.class));
+                        ui.monospace(hero.level.to_string());
+                        ui.monospace(Time::compact(hero.playtime));
+                        ui.label(act_name(hero.act));
+                        match &hero.best_item {
+                            Some(item) => {
+                                ui.label(item.display()).on_hover_ui(|ui| {
+                                    if item.style.is_some() {
+                                        ui.weak(item.piece.display());
+                                    }
+                                    ui.label(format!(
+                                        "{}, worth {} gold",
+                                        item.slot.body_part(),
+                                        item.piece.resale()
+                                    ));
+                                });
+                            }
+                            None => {
+                                ui.weak("Nothing");
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+        closed
+    }
+
     fn display_character_creation(
         player: &mut Player,
         stats_builder: &mut StatsBuilder,
@@ -260,32 +647,103 @@ impl MainWindow {
                         player.name = generate_name(None, rng);
                     }
 
+                    // kept as typed, so the commas don't vanish while typing them
+                    let themes_id = ui.id().with("themes");
+                    let mut themes = ui
+                        .data()
+                        .get_temp::<String>(themes_id)
+                        .filter(|themes| lingo::themes(themes) == player.themes)
+                        .unwrap_or_else(|| player.themes.join(", "));
+                    let edit = ui
+                        .add(
+                            TextEdit::singleline(&mut themes)
+                                .hint_text("Themes, like cheese, volcanoes")
+                                .desired_width(160.0),
+                        )
+                        .on_hover_text("A few words to weave into quests and cinematics");
+                    if edit.changed() {
+                        player.themes = lingo::themes(&themes);
+                    }
+                    ui.data().insert_temp(themes_id, themes);
+
                     ui.separator();
 
-                    if ui.small_button("Roll").clicked() {
-                        player.stats = stats_builder.roll(rng);
-                    }
+                    let mut roll = ui.small_button("Roll").clicked();
 
                     ui.add_enabled_ui(stats_builder.has_history(), |ui| {
                         if ui.small_button("Unroll").clicked() {
                             player.stats = stats_builder.unroll();
+                            player.roll_mode = stats_builder.rolled_with();
                         }
                     });
 
+                    egui::ComboBox::from_id_source("roll_mode")
+                        .selected_text(stats_builder.mode.as_str())
+                        .show_ui(ui, |ui| {
+                            for mode in RollMode::ALL {
+                                let selected = std::mem::discriminant(&stats_builder.mode)
+                                    == std::mem::discriminant(&mode);
+                                if ui.selectable_label(selected, mode.as_str()).clicked() {
+                                    stats_builder.mode = mode;
+                                    roll = true;
+                                }
+                            }
+                        });
+                    if let RollMode::RerollUntil(total) = &mut stats_builder.mode {
+                        roll |= ui
+                            .add(
+                                egui::DragValue::new(total)
+                                    .clamp_range(18..=RollMode::MAX_TOTAL)
+                                    .prefix("total ≥ "),
+                            )
+                            .drag_released();
+                    }
+
+                    if roll {
+                        player.stats = stats_builder.roll(rng);
+                        player.roll_mode = stats_builder.rolled_with();
+                    }
+
+                    ui.separator();
+
+                    for rating in config::Rating::ALL {
+                        if ui
+                            .selectable_label(player.rating == rating, rating.as_str())
+                            .clicked()
+                        {
+                            player.rating = rating;
+                        }
+                    }
+
+                    ui.separator();
+                    let mut ironman = player.ironman.is_some();
+                    if ui
+                        .toggle_value(&mut ironman, "Ironman")
+                        .on_hover_text(
+                            "Lock in the seed, rules and content for good, with no rewinding or \
+                             debug commands, for fair challenges",
+                        )
+                        .changed()
+                    {
+                        player.ironman = ironman
+                            .then(|| rules::Ironman::new(Rand::random_seed(), Default::default()));
+                    }
+
                     ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                        if ui.add(Self::success_button("Sold!")).clicked() {
+                        if ui.add(Self::success_button(ui, "Sold!")).clicked() {
                             created = CreationResult::Created
                         }
-                        if ui.add(Self::caution_button("Cancel")).clicked() {
+                        if ui.add(Self::caution_button(ui, "Cancel")).clicked() {
                             created = CreationResult::Cancel
                         }
                     });
                 });
             });
 
+        let content = config::content();
         ui.columns(3, |ui| {
             make_frame(&mut ui[0], "Race", |ui| {
-                for race in config::RACES {
+                for race in &content.races {
                     if ui
                         .radio(player.race.name == race.name, &*race.name)
                         .clicked()
@@ -296,7 +754,7 @@ impl MainWindow {
             });
 
             make_frame(&mut ui[1], "Class", |ui| {
-                for class in config::CLASSES {
+                for class in &content.classes {
                     if ui
                         .radio(player.class.name == class.name, &*class.name)
                         .clicked()
@@ -342,7 +800,18 @@ impl MainWindow {
         created
     }
 
-    fn display_game(simulation: &mut Simulation, rng: &Rand, ctx: &egui::Context) {
+    fn display_game(
+        simulation: &mut Simulation,
+        party: &mut [Simulation],
+        mut partner: Option<&mut Simulation>,
+        title_card: &mut Option<(TitleCard, f64)>,
+        settings: &mut Settings,
+        show_settings: &mut bool,
+        show_hints: &mut bool,
+        toasts: &mut Toasts,
+        time: &TimeController,
+        ctx: &egui::Context,
+    ) {
         fn stroke(ui: &mut egui::Ui) -> Stroke {
             Stroke::new(
                 ui.visuals().selection.stroke.width,
@@ -361,8 +830,21 @@ impl MainWindow {
             Label::new(RichText::new(s).monospace())
         }
 
-        fn display_character_sheet(simulation: &mut Simulation, ui: &mut egui::Ui) {
-            Frame::none().stroke(stroke(ui)).show(ui, |ui| {
+        // in real time, so the pulse takes as long at any time scale
+        fn since_completed(simulation: &Simulation, bar: &Bar) -> Option<f32> {
+            bar.since_completed(simulation.player.elapsed)
+                .map(|since| since / simulation.speed())
+        }
+
+        fn eta(after: Option<Duration>) -> String {
+            after.map_or_else(
+                || String::from("Not long now"),
+                |after| format!("About {} to go", Time::compact(after.as_secs_f32())),
+            )
+        }
+
+        fn display_character_sheet(simulation: &mut Simulation, ui: &mut egui::Ui) -> Rect {
+            let panel = Frame::none().stroke(stroke(ui)).show(ui, |ui| {
                 ui.vertical_centered(|ui| {
                     ui.label(RichText::new("Character Sheet").strong());
                 });
@@ -382,6 +864,7 @@ impl MainWindow {
                             ("Race", make_label(&simulation.player.race.name)),
                             ("Class", make_label(&simulation.player.class.name)),
                             ("Level", make_label(&simulation.player.level.to_string())),
+                            ("Location", make_label(&simulation.player.world.location())),
                         ] {
                             ui.horizontal(|ui| {
                                 ui.monospace(k);
@@ -390,6 +873,45 @@ impl MainWindow {
                                 });
                             });
                         }
+                        if let Some(title) = simulation.player.bestiary.title() {
+                            ui.horizontal(|ui| {
+                                ui.monospace("Title");
+                                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                    ui.add(make_label(&title));
+                                });
+                            });
+                        }
+                        if let Some(ironman) = &simulation.player.ironman {
+                            ui.horizontal(|ui| {
+                                ui.monospace("Ironman");
+                                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                    ui.add(make_label(&ironman.to_string()))
+                                        .on_hover_text(&ironman.lock);
+                                });
+                            });
+                        }
+
+                        if let Some(rival) = &simulation.player.rival {
+                            ui.separator();
+                            ui.horizontal(|ui| {
+                                ui.monospace("Rival");
+                                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                    ui.add(make_label(&format!(
+                                        "{} ({})",
+                                        rival.name, rival.level
+                                    )));
+                                });
+                            });
+                        }
+
+                        if let Some(nemesis) = &simulation.player.nemesis {
+                            ui.horizontal(|ui| {
+                                ui.monospace("Nemesis");
+                                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                    ui.add(make_label(&format!("{nemesis} ({})", nemesis.level)));
+                                });
+                            });
+                        }
                     });
 
                     make_frame(ui, |ui| {
@@ -425,13 +947,16 @@ impl MainWindow {
                             exp: simulation.player.exp_bar.remaining() as _,
                         },
                     )
-                    .display(ui);
+                    .completed(since_completed(simulation, &simulation.player.exp_bar))
+                    .display(ui)
+                    .on_hover_text(eta(balance::upcoming(simulation).next_level()));
                 });
             });
+            panel.response.rect
         }
 
-        fn display_spell_book(simulation: &mut Simulation, ui: &mut egui::Ui) {
-            Frame::none().stroke(stroke(ui)).show(ui, |ui| {
+        fn display_spell_book(simulation: &mut Simulation, ui: &mut egui::Ui) -> Rect {
+            let panel = Frame::none().stroke(stroke(ui)).show(ui, |ui| {
                 ui.vertical_centered(|ui| {
                     ui.label(RichText::new("Spell Book").strong());
                 });
@@ -462,34 +987,188 @@ impl MainWindow {
                         });
                 });
             });
+            panel.response.rect
         }
 
-        fn display_equipment(simulation: &mut Simulation, ui: &mut egui::Ui) {
-            Frame::none().stroke(stroke(ui)).show(ui, |ui| {
+        fn display_equipment(
+            simulation: &mut Simulation,
+            settings: &Settings,
+            ui: &mut egui::Ui,
+        ) -> Rect {
+            let panel = Frame::none().stroke(stroke(ui)).show(ui, |ui| {
                 ui.vertical_centered(|ui| {
                     ui.label(RichText::new("Equipment").strong());
                 });
 
+                if settings.paper_doll {
+                    make_frame(ui, |ui| display_paper_doll(&simulation.player, ui));
+                    return;
+                }
+
                 make_frame(ui, |ui| {
                     ScrollArea::vertical()
                         .stick_to_bottom(true)
                         .id_source("equipment_list")
                         .show(ui, |ui| {
-                            for (equipment, name) in simulation.player.equipment.iter() {
+                            let equipment = &mut simulation.player.equipment;
+                            for slot in config::Equipment::ALL {
+                                let Some(name) = equipment.display(slot) else {
+                                    continue;
+                                };
                                 ui.horizontal(|ui| {
-                                    ui.monospace(equipment.as_str());
+                                    ui.monospace(slot.as_str());
                                     ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                                        ui.add(make_label(name));
+                                        let response =
+                                            ui.add(make_label(&name).sense(Sense::click()));
+                                        let response = match equipment.piece(slot) {
+                                            Some(piece) if equipment.style(slot).is_some() => {
+                                                response.on_hover_text(piece.display())
+                                            }
+                                            _ => response.on_hover_text("Right-click to restyle"),
+                                        };
+                                        response.context_menu(|ui| {
+                                            display_restyle(equipment, slot, ui)
+                                        });
                                     });
                                 });
                             }
                         });
                 });
             });
+            panel.response.rect
+        }
+
+        fn display_restyle(equipment: &mut Equipment, slot: config::Equipment, ui: &mut egui::Ui) {
+            let id = ui.id().with(("restyle", slot));
+            let mut name = ui
+                .data()
+                .get_temp::<String>(id)
+                .unwrap_or_else(|| equipment.style(slot).map_or_else(String::new, String::from));
+
+            ui.label(format!(
+                "Call the {} something else",
+                slot.as_str().to_lowercase()
+            ));
+            ui.add(
+                TextEdit::singleline(&mut name).hint_text(
+                    equipment
+                        .get(slot)
+                        .map_or_else(String::new, |piece| piece.display()),
+                ),
+            );
+            let restyled = ui
+                .horizontal(|ui| {
+                    if ui.button("Restyle").clicked() {
+                        equipment.restyle(slot, &name);
+                        return true;
+                    }
+                    let clear = ui
+                        .add_enabled(equipment.style(slot).is_some(), Button::new("Clear"))
+                        .on_hover_text("Go back to the item's own name");
+                    if clear.clicked() {
+                        equipment.restyle(slot, "");
+                        return true;
+                    }
+                    false
+                })
+                .inner;
+
+            if restyled {
+                ui.data().remove::<String>(id);
+                ui.close_menu();
+            } else {
+                ui.data().insert_temp(id, name);
+            }
+        }
+
+        fn display_paper_doll(player: &Player, ui: &mut egui::Ui) {
+            let (columns, rows) = config::Equipment::DOLL_SIZE;
+            let mut cells = vec![None; columns * rows];
+            for slot in config::Equipment::ALL {
+                let (column, row) = slot.doll_position();
+                cells[row * columns + column] = Some(slot);
+            }
+
+            let width = ui.available_width() / columns as f32 - ui.spacing().item_spacing.x;
+            egui::Grid::new("paper_doll")
+                .num_columns(columns)
+                .min_col_width(width)
+                .max_col_width(width)
+                .show(ui, |ui| {
+                    for row in cells.chunks(columns) {
+                        for slot in row {
+                            match slot {
+                                Some(slot) => display_doll_slot(player, *slot, ui),
+                                None => {
+                                    ui.label("");
+                                }
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        }
+
+        fn display_doll_slot(player: &Player, slot: config::Equipment, ui: &mut egui::Ui) {
+            let quality = player.equipment.quality(slot);
+            // what the market sells at the hero's level, give or take
+            let difference = quality - player.level as i32;
+            let color = match difference {
+                d if d >= 5 => Color32::LIGHT_GREEN,
+                d if d >= 0 => Color32::WHITE,
+                d if d >= -5 => Color32::YELLOW,
+                _ => Color32::LIGHT_RED,
+            };
+
+            let response = Frame::none()
+                .stroke(Stroke::new(1.0, color))
+                .inner_margin(Margin::same(2.0))
+                .show(ui, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.small(slot.as_str());
+                        ui.label(
+                            RichText::new(format!("{quality:+}"))
+                                .monospace()
+                                .color(color),
+                        );
+                    });
+                })
+                .response;
+
+            response.on_hover_ui(|ui| {
+                match player.equipment.piece(slot) {
+                    Some(piece) => {
+                        match player.equipment.style(slot) {
+                            Some(style) => {
+                                ui.strong(style);
+                                ui.weak(piece.display());
+                            }
+                            None => {
+                                ui.strong(piece.display());
+                            }
+                        }
+                        ui.label(format!(
+                            "{}, sells for {} gold",
+                            slot.body_part(),
+                            piece.resale()
+                        ));
+                    }
+                    None => {
+                        ui.weak(format!("Nothing on the {}", slot.body_part()));
+                    }
+                }
+                ui.label(match difference {
+                    0 => format!("As good as the market has at level {}", player.level),
+                    d if d > 0 => {
+                        format!("{d} better than the market has at level {}", player.level)
+                    }
+                    d => format!("{} worse than the market has at level {}", -d, player.level),
+                });
+            });
         }
 
-        fn display_inventory(simulation: &mut Simulation, ui: &mut egui::Ui) {
-            Frame::none().stroke(stroke(ui)).show(ui, |ui| {
+        fn display_inventory(simulation: &mut Simulation, ui: &mut egui::Ui) -> Rect {
+            let panel = Frame::none().stroke(stroke(ui)).show(ui, |ui| {
                 TopBottomPanel::bottom("encumbrance_bar")
                     .resizable(false)
                     .show_separator_line(false)
@@ -504,6 +1183,10 @@ impl MainWindow {
                                     max: simulation.player.inventory.encumbrance.max as _,
                                 },
                             )
+                            .completed(since_completed(
+                                simulation,
+                                &simulation.player.inventory.encumbrance,
+                            ))
                             .display(ui);
                         });
                     });
@@ -524,14 +1207,31 @@ impl MainWindow {
                         .stick_to_bottom(true)
                         .id_source("inventory_list")
                         .show(ui, |ui| {
+                            let market = &simulation.player.market;
                             ui.horizontal(|ui| {
-                                ui.monospace("Gold");
+                                ui.monospace("Gold").on_hover_ui(|ui| {
+                                    for (goods, rate) in market.rates() {
+                                        ui.label(format!("{goods}: {:.0}%", rate * 100.0));
+                                    }
+                                });
                                 ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                                     ui.add(make_label(
                                         &simulation.player.inventory.gold().to_string(),
                                     ));
                                 });
                             });
+                            ui.horizontal(|ui| {
+                                ui.monospace("Bank")
+                                    .on_hover_text("Gold kept at the bank, earning interest");
+                                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                    ui.add(make_label(
+                                        &simulation.player.bank.balance().to_string(),
+                                    ));
+                                });
+                            });
+                            if let Some(outlook) = market.outlook() {
+                                ui.small(RichText::new(outlook).weak());
+                            }
 
                             for (name, qty) in simulation.player.inventory.items() {
                                 ui.horizontal(|ui| {
@@ -546,10 +1246,11 @@ impl MainWindow {
                         });
                 });
             });
+            panel.response.rect
         }
 
-        fn display_plot(simulation: &mut Simulation, ui: &mut egui::Ui) {
-            Frame::none().stroke(stroke(ui)).show(ui, |ui| {
+        fn display_plot(simulation: &mut Simulation, ui: &mut egui::Ui) -> Rect {
+            let panel = Frame::none().stroke(stroke(ui)).show(ui, |ui| {
                 ui.vertical_centered(|ui| {
                     ui.label(RichText::new("Plot Development").strong());
                     ui.separator();
@@ -574,14 +1275,54 @@ impl MainWindow {
                                     simulation.player.quest_book.plot,
                                     crate::progress::ProgressInfo::Complete,
                                 )
-                                .display(ui);
+                                .completed(since_completed(
+                                    simulation,
+                                    &simulation.player.quest_book.plot,
+                                ))
+                                .display(ui)
+                                .on_hover_text(eta(balance::upcoming(simulation).next_act()));
+
+                                if let Some(last) = simulation.rules().final_act {
+                                    ui.small(format!("Ascends after {}", act_name(last)));
+                                }
+                                let legacy = &simulation.player.legacy;
+                                if legacy.ascensions > 0 {
+                                    ui.small(legacy.to_string()).on_hover_text(format!(
+                                        "Past lives ended at level {}",
+                                        legacy
+                                            .levels
+                                            .iter()
+                                            .map(ToString::to_string)
+                                            .collect::<Vec<_>>()
+                                            .join(", ")
+                                    ));
+                                }
                             });
                     });
             });
+            panel.response.rect
         }
 
-        fn display_quests(simulation: &mut Simulation, ui: &mut egui::Ui) {
-            Frame::none().stroke(stroke(ui)).show(ui, |ui| {
+        fn quest_details(quest: &Quest, elapsed: f32, remaining: Option<f32>) -> String {
+            let mut details = vec![];
+            if let Some(giver) = &quest.giver {
+                details.push(format!("For {giver}"));
+            }
+            if let (Some(accepted), Some(age)) = (quest.accepted, quest.age(elapsed)) {
+                details.push(format!(
+                    "Accepted at {}, {} ago",
+                    Time::clock(accepted),
+                    Time::compact(age)
+                ));
+            }
+            if let Some(remaining) = remaining {
+                details.push(format!("About {} to go", Time::compact(remaining)));
+            }
+            details.join("\n")
+        }
+
+        fn display_quests(simulation: &mut Simulation, ui: &mut egui::Ui) -> Rect {
+            let panel = Frame::none().stroke(stroke(ui)).show(ui, |ui| {
                 TopBottomPanel::bottom("quest_bar")
                     .resizable(false)
                     .show_separator_line(false)
@@ -591,6 +1332,10 @@ impl MainWindow {
                             simulation.player.quest_book.quest,
                             crate::progress::ProgressInfo::Complete,
                         )
+                        .completed(since_completed(
+                            simulation,
+                            &simulation.player.quest_book.quest,
+                        ))
                         .display(ui);
                     });
 
@@ -606,27 +1351,268 @@ impl MainWindow {
                         Frame::none()
                             .inner_margin(Margin::symmetric(4.0, 2.0))
                             .show(ui, |ui| {
-                                for quest in simulation.player.quest_book.completed_quests() {
-                                    ui.checkbox(&mut true, quest);
+                                fn display_quest(
+                                    quest: &Quest,
+                                    mut done: bool,
+                                    elapsed: f32,
+                                    remaining: Option<f32>,
+                                    ui: &mut egui::Ui,
+                                ) {
+                                    let resp = ui.checkbox(&mut done, quest.caption.as_str());
+                                    let details = quest_details(quest, elapsed, remaining);
+                                    if !details.is_empty() {
+                                        resp.on_hover_text(details);
+                                    }
+                                }
+
+                                let quest_book = &simulation.player.quest_book;
+                                let elapsed = simulation.player.elapsed;
+                                for quest in quest_book.completed_quests() {
+                                    display_quest(quest, true, elapsed, None, ui);
                                 }
 
-                                if let Some(quest) = simulation.player.quest_book.current_quest() {
-                                    ui.checkbox(&mut false, quest);
+                                if let Some(quest) = quest_book.current_quest() {
+                                    let remaining = quest_book.remaining(elapsed);
+                                    display_quest(quest, false, elapsed, remaining, ui);
                                 }
                             });
                         ui.allocate_space(ui.available_size_before_wrap());
                     });
             });
+            panel.response.rect
+        }
+
+        fn display_board(simulation: &mut Simulation, ui: &mut egui::Ui) -> Rect {
+            let panel = Frame::none().stroke(stroke(ui)).show(ui, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.label(RichText::new("Quest Board").strong());
+                    ui.separator();
+                });
+
+                let player = &simulation.player;
+                Frame::none()
+                    .inner_margin(Margin::symmetric(4.0, 2.0))
+                    .show(ui, |ui| {
+                        for bounty in player.board.bounties() {
+                            let mut claimed = bounty.claimed;
+                            ui.checkbox(&mut claimed, bounty.objective.to_string())
+                                .on_hover_text(format!("Pays {} gold", bounty.reward));
+                            let bar = Bar {
+                                pos: bounty.progress(player) as f32,
+                                max: bounty.objective.goal() as f32,
+                                completed_at: None,
+                            };
+                            Progress::from_bar(bar, crate::progress::ProgressInfo::Complete)
+                                .display(ui);
+                        }
+                    });
+            });
+            panel.response.rect
         }
 
-        simulation.tick(rng);
+        fn display_controls(
+            simulation: &mut Simulation,
+            boost: f32,
+            show_settings: &mut bool,
+            show_hints: &mut bool,
+            ui: &mut egui::Ui,
+        ) {
+            const PRESETS: [f32; 4] = [1.0, 2.0, 5.0, 10.0];
+
+            CollapsingHeader::new("Controls")
+                .id_source("controls")
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        let mut paused = simulation.is_paused();
+                        if ui
+                            .toggle_value(&mut paused, if paused { "Resume" } else { "Pause" })
+                            .changed()
+                        {
+                            if paused {
+                                simulation.pause()
+                            } else {
+                                simulation.resume()
+                            }
+                        }
+                        ui.separator();
 
+                        let max = simulation.tuning().max_time_scale.max(1.0);
+                        let chosen = &mut simulation.player.time_scale;
+                        ui.add(Slider::new(chosen, 1.0..=max).logarithmic(true).suffix("×"));
+
+                        for preset in PRESETS.into_iter().filter(|&preset| preset <= max) {
+                            if ui
+                                .selectable_label(*chosen == preset, format!("{preset}×"))
+                                .clicked()
+                            {
+                                *chosen = preset;
+                            }
+                        }
+
+                        // finishes a task every frame, for watching the late game quickly
+                        if cfg!(debug_assertions) && simulation.player.ironman.is_none() {
+                            let mut turbo = matches!(simulation.tick_policy(), TickPolicy::Turbo);
+                            if ui
+                                .toggle_value(&mut turbo, "Turbo")
+                                .on_hover_text("Finish a task every frame (debug builds only)")
+                                .changed()
+                            {
+                                simulation.set_tick_policy(if turbo {
+                                    TickPolicy::Turbo
+                                } else {
+                                    TickPolicy::RealTime
+                                });
+                            }
+                        }
+
+                        ui.separator();
+                        let effective = if paused { 0.0 } else { simulation.speed() };
+                        ui.label(format!("{effective:.1}s of game time per second"));
+                        if boost > 1.0 && !paused {
+                            ui.weak(format!("(catching up from the background, {boost:.1}×)"));
+                        }
+
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ui.toggle_value(show_settings, "Settings");
+                            ui.toggle_value(show_hints, "?")
+                                .on_hover_text("Explain what each panel is");
+                        });
+                    });
+                });
+        }
+
+        fn display_mini(simulation: &Simulation, settings: &mut Settings, ui: &mut egui::Ui) {
+            ui.horizontal(|ui| {
+                ui.strong(&simulation.player.name);
+                ui.label(format!("level {}", simulation.player.level));
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if ui
+                        .small_button("Full")
+                        .on_hover_text("Leave mini mode (F3)")
+                        .clicked()
+                    {
+                        settings.mini = false
+                    }
+                });
+            });
+            if let Some(task) = &simulation.player.task {
+                ui.label(task.decorated(settings.glyph_style()));
+            }
+            Progress::from_bar(
+                simulation.player.task_bar,
+                crate::progress::ProgressInfo::Percent,
+            )
+            .completed(since_completed(simulation, &simulation.player.task_bar))
+            .display(ui);
+        }
+
+        fn display_party(party: &[Simulation], settings: &Settings, ui: &mut egui::Ui) {
+            CollapsingHeader::new("Party")
+                .id_source("party")
+                .show(ui, |ui| {
+                    if !settings.party_ticking {
+                        ui.weak("Everyone else is resting, see Settings to keep them questing");
+                    }
+                    for member in party {
+                        ui.horizontal(|ui| {
+                            ui.strong(&member.player.name);
+                            ui.monospace(format!("level {}", member.player.level));
+                            if let Some(task) = &member.player.task {
+                                ui.label(task.decorated(settings.glyph_style()));
+                            }
+                        });
+                        Progress::from_bar(
+                            member.player.task_bar,
+                            crate::progress::ProgressInfo::Percent,
+                        )
+                        .display(ui);
+                    }
+                });
+        }
+
+        // the other half of the window in couch mode, without any of the controls
+        fn display_partner(partner: &mut Simulation, settings: &Settings, ui: &mut egui::Ui) {
+            TopBottomPanel::bottom("couch_task")
+                .frame(Frame::none())
+                .resizable(false)
+                .show_separator_line(false)
+                .show_inside(ui, |ui| {
+                    if let Some(task) = &partner.player.task {
+                        ui.label(task.decorated(settings.glyph_style()));
+                    }
+                    Progress::from_bar(
+                        partner.player.task_bar,
+                        crate::progress::ProgressInfo::Percent,
+                    )
+                    .completed(since_completed(partner, &partner.player.task_bar))
+                    .display(ui);
+                });
+
+            SidePanel::left("couch_sheet")
+                .frame(Frame::none())
+                .resizable(false)
+                .show_separator_line(false)
+                .show_inside(ui, |ui| {
+                    display_character_sheet(partner, ui);
+                });
+
+            display_equipment(partner, settings, ui);
+            display_inventory(partner, ui);
+        }
+
+        settings.apply_to(simulation);
+
+        // the runner ticks everyone, but a couch partner only keeps pace with the active hero, and
+        // the rest of the party only when asked to
+        let paused = simulation.is_paused();
+        let members = partner.as_deref_mut().map(|member| (member, paused));
+        let members = members.into_iter().chain(
+            party
+                .iter_mut()
+                .map(|member| (member, paused || !settings.party_ticking)),
+        );
+        for (member, paused) in members {
+            settings.apply_to(member);
+            time.apply(member);
+            if paused {
+                member.pause()
+            } else if member.is_paused() {
+                member.resume()
+            }
+        }
+
+        if let Some(card) = simulation.take_title_card() {
+            *title_card = Some((card, ctx.input().time));
+        }
+        if let Some(diagnostic) = simulation.take_diagnostic() {
+            toasts.warn(format!("The game stalled and was recovered: {diagnostic}"))
+        }
+        Self::display_title_card(title_card, ctx);
+
+        if settings.mini {
+            simulation.player.time_scale = simulation
+                .tuning()
+                .clamp_time_scale(simulation.player.time_scale);
+            time.apply(simulation);
+            CentralPanel::default().show(ctx, |ui| display_mini(simulation, settings, ui));
+            ctx.request_repaint_after(settings.repaint.interval());
+            return;
+        }
+
+        let mut hints = vec![];
         CentralPanel::default().show(ctx, |ui| {
-            // ui.horizontal(|ui| {
-            //     ui.add(egui::Slider::new(&mut simulation.time_scale, 1.0..=100.0).step_by(5.0));
-            // });
+            TopBottomPanel::top("controls_panel")
+                .frame(Frame::none())
+                .resizable(false)
+                .show_separator_line(false)
+                .show_inside(ui, |ui| {
+                    display_controls(simulation, time.boost(), show_settings, show_hints, ui);
+                });
 
-            simulation.time_scale = simulation.time_scale.max(1.0);
+            simulation.player.time_scale = simulation
+                .tuning()
+                .clamp_time_scale(simulation.player.time_scale);
+            time.apply(simulation);
 
             TopBottomPanel::bottom("bottom_panel")
                 .frame(Frame::none())
@@ -634,25 +1620,139 @@ impl MainWindow {
                 .show_separator_line(false)
                 .show_inside(ui, |ui| {
                     ui.vertical(|ui| {
+                        if let Some(dungeon) = &simulation.player.dungeon {
+                            ui.horizontal(|ui| {
+                                ui.small(&dungeon.name);
+                                Progress::from_bar(
+                                    dungeon.bar,
+                                    crate::progress::ProgressInfo::Complete,
+                                )
+                                .display(ui);
+                            });
+                        }
+
+                        if let Some(arena) = &simulation.player.arena {
+                            let time_left = arena.time_left(simulation.player.elapsed).max(0.0);
+                            ui.horizontal(|ui| {
+                                ui.small(&arena.name);
+                                ui.small(format!("{} left", Time::compact(time_left)));
+                                Progress::from_bar(
+                                    arena.bar,
+                                    crate::progress::ProgressInfo::Complete,
+                                )
+                                .display(ui);
+                            });
+                        }
+
+                        if let Some(expedition) = &simulation.player.expedition {
+                            ui.horizontal(|ui| {
+                                ui.small("Treasure hunt");
+                                Progress::from_bar(
+                                    expedition.bar,
+                                    crate::progress::ProgressInfo::Percent,
+                                )
+                                .display(ui);
+                            });
+                        }
+
+                        if !party.is_empty() {
+                            display_party(party, settings, ui);
+                        }
+
+                        if simulation.player.journal.arena().next().is_some() {
+                            CollapsingHeader::new("Arena")
+                                .id_source("arena")
+                                .show(ui, |ui| {
+                                    for entry in simulation.player.journal.arena() {
+                                        ui.horizontal(|ui| {
+                                            ui.monospace(
+                                                RichText::new(Time::clock(entry.elapsed)).weak(),
+                                            );
+                                            ui.label(entry.event.to_string());
+                                        });
+                                    }
+                                });
+                        }
+
+                        CollapsingHeader::new("Journal")
+                            .id_source("event_log")
+                            .show(ui, |ui| {
+                                ScrollArea::vertical()
+                                    .stick_to_bottom(true)
+                                    .max_height(120.0)
+                                    .id_source("event_log_list")
+                                    .show(ui, |ui| {
+                                        let lines = simulation
+                                            .event_log()
+                                            .with_journal(&simulation.player.journal);
+                                        for (elapsed, line) in lines {
+                                            ui.horizontal(|ui| {
+                                                ui.monospace(
+                                                    RichText::new(Time::clock(elapsed)).weak(),
+                                                );
+                                                ui.label(line.to_string());
+                                            });
+                                        }
+                                    });
+                            });
+
+                        if !simulation.player.notes.is_empty() {
+                            CollapsingHeader::new("Notes")
+                                .id_source("notes")
+                                .show(ui, |ui| {
+                                    ScrollArea::vertical()
+                                        .max_height(120.0)
+                                        .id_source("notes_text")
+                                        .show(ui, |ui| ui.label(&simulation.player.notes));
+                                });
+                        }
+
+                        ScrollArea::horizontal()
+                            .id_source("history_ticker")
+                            .stick_to_right(true)
+                            .show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    for task in simulation.history() {
+                                        ui.label(RichText::new(task).small().weak());
+                                        ui.weak("·");
+                                    }
+                                });
+                            });
+
                         if let Some(task) = &simulation.player.task {
-                            ui.label(&*task.description);
+                            ui.label(task.decorated(settings.glyph_style()));
                         }
-                        Progress::from_bar(
+                        let task_bar = Progress::from_bar(
                             simulation.player.task_bar,
                             crate::progress::ProgressInfo::Percent,
                         )
+                        .completed(since_completed(simulation, &simulation.player.task_bar))
                         .display(ui);
+                        hints.push((Hint::Task, task_bar.rect));
                         // ui.allocate_space(ui.available_size_before_wrap());
                     });
                 });
 
+            if let Some(partner) = partner.as_deref_mut() {
+                let half = ui.available_width() / 2.0;
+                SidePanel::right("couch_panel")
+                    .frame(Frame::none())
+                    .resizable(false)
+                    .min_width(half)
+                    .max_width(half)
+                    .show_inside(ui, |ui| display_partner(partner, settings, ui));
+            }
+
             SidePanel::left("left_panel")
                 .frame(Frame::none())
                 .resizable(false)
                 .show_separator_line(false)
                 .show_inside(ui, |ui| {
-                    display_character_sheet(simulation, ui);
-                    display_spell_book(simulation, ui);
+                    hints.push((
+                        Hint::CharacterSheet,
+                        display_character_sheet(simulation, ui),
+                    ));
+                    hints.push((Hint::SpellBook, display_spell_book(simulation, ui)));
                 });
 
             SidePanel::right("right_panel")
@@ -660,26 +1760,196 @@ impl MainWindow {
                 .resizable(false)
                 .show_separator_line(false)
                 .show_inside(ui, |ui| {
-                    display_plot(simulation, ui);
-                    display_quests(simulation, ui);
+                    hints.push((Hint::Plot, display_plot(simulation, ui)));
+                    display_board(simulation, ui);
+                    hints.push((Hint::Quests, display_quests(simulation, ui)));
                 });
 
-            display_equipment(simulation, ui);
-            display_inventory(simulation, ui);
+            hints.push((Hint::Equipment, display_equipment(simulation, settings, ui)));
+            hints.push((Hint::Inventory, display_inventory(simulation, ui)));
         });
 
-        ctx.request_repaint_after(Self::FRAME_RATE);
+        for (hint, rect) in hints {
+            Self::display_hint(hint, rect, settings, *show_hints, ctx);
+        }
+
+        ctx.request_repaint_after(settings.repaint.interval());
     }
 
-    fn display_main_view(view: &mut View, rng: &Rand, ctx: &egui::Context) {
+    fn display_title_card(title_card: &mut Option<(TitleCard, f64)>, ctx: &egui::Context) {
+        const HOLD: f64 = 2.5;
+        const FADE: f64 = 1.5;
+
+        let (card, shown) = match title_card {
+            Some(title_card) => title_card,
+            None => return,
+        };
+
+        let elapsed = ctx.input().time - *shown;
+        if elapsed >= HOLD + FADE {
+            *title_card = None;
+            return;
+        }
+        let alpha = (1.0 - (elapsed - HOLD).max(0.0) / FADE) as f32;
+
+        let clicked = Area::new("title_card")
+            .order(Order::Foreground)
+            .fixed_pos(Pos2::ZERO)
+            .show(ctx, |ui| {
+                let rect = ctx.screen_rect();
+                let response = ui.allocate_rect(rect, Sense::click());
+                ui.painter().rect_filled(
+                    rect,
+                    0.0,
+                    Color32::from_black_alpha((220.0 * alpha) as u8),
+                );
+                ui.painter().text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    card.to_string(),
+                    FontId::proportional(32.0),
+                    Color32::WHITE.linear_multiply(alpha),
+                );
+                response.clicked()
+            })
+            .inner;
+
+        if clicked {
+            *title_card = None;
+        }
+        ctx.request_repaint();
+    }
+
+    /// A short explanation of a panel or bar, for new players
+    const fn hint_text(hint: Hint) -> &'static str {
+        match hint {
+            Hint::CharacterSheet => "Who your hero is, a full experience bar means a new level",
+            Hint::SpellBook => "Spells are learned when leveling up, the numeral is their level",
+            Hint::Equipment => "What your hero is wearing, upgraded with gold from selling loot",
+            Hint::Inventory => "Loot from slain monsters, sold off once the encumbrance bar fills",
+            Hint::Plot => "The story so far, a full bar finishes the act",
+            Hint::Quests => "Quests are picked up along the way, each one pays a reward",
+            Hint::Task => "What your hero is doing right now. It all happens on its own",
+        }
+    }
+
+    fn display_hint(
+        hint: Hint,
+        rect: Rect,
+        settings: &mut Settings,
+        show_all: bool,
+        ctx: &egui::Context,
+    ) {
+        if !show_all && !settings.wants_hint(hint) {
+            return;
+        }
+
+        Area::new(("hint", hint))
+            .order(Order::Foreground)
+            .fixed_pos(rect.left_top() + vec2(8.0, 8.0))
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width((rect.width() - 16.0).max(120.0));
+                    ui.label(Self::hint_text(hint));
+                    if !show_all && ui.small_button("Got it").clicked() {
+                        settings.seen_hints.insert(hint);
+                    }
+                });
+            });
+    }
+
+    fn display_unreadable_save(&mut self, ctx: &egui::Context) {
+        let Some(unreadable) = self
+            .unreadable_save
+            .as_mut()
+            .filter(|unreadable| !unreadable.replace && !unreadable.dismissed)
+        else {
+            return;
+        };
+
+        Window::new("Unreadable save")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "The saved characters couldn't be loaded: {}",
+                    unreadable.error
+                ));
+                ui.label(
+                    "A copy of them is kept, and nothing is saved over them until you say so. \
+                     Until then, only the settings are saved.",
+                );
+                ui.horizontal(|ui| {
+                    if ui.button("Keep them").clicked() {
+                        unreadable.dismissed = true;
+                    }
+                    if ui.add(Self::caution_button(ui, "Save over them")).clicked() {
+                        unreadable.replace = true;
+                    }
+                });
+            });
+    }
+
+    /// Save only the settings, where they're looked for when there are no saved characters
+    fn save_settings(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, Self::CONFIG_KEY, &self.settings);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.store_launch();
+    }
+
+    /// Keep what's needed before the window exists where it can be found at launch, see
+    /// [`Launch`](crate::settings::Launch)
+    #[cfg(not(target_arch = "wasm32"))]
+    fn store_launch(&mut self) {
+        if let Err(err) = crate::settings::Launch::of(&self.settings).store() {
+            self.toasts
+                .error(format!("The window settings couldn't be saved: {err}"))
+        }
+    }
+
+    fn display_farewell(summary: &SessionSummary, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        Window::new("Farewell")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(summary.to_string());
+                ui.vertical_centered(|ui| {
+                    if ui.add(Self::caution_button(ui, "Quit")).clicked() {
+                        frame.close()
+                    }
+                });
+            });
+    }
+
+    fn display_main_view(
+        view: &mut View,
+        profile: &mut Profile,
+        settings: &mut Settings,
+        show_settings: &mut bool,
+        show_hints: &mut bool,
+        toasts: &mut Toasts,
+        time: &TimeController,
+        rng: &Rand,
+        ctx: &egui::Context,
+    ) {
         *view = match std::mem::take(view) {
             View::CharacterSelect { mut players } => {
                 CentralPanel::default()
                     .show(ctx, |ui| {
                         use SelectionResult::*;
-                        match Self::display_character_select(&mut players, ui) {
+                        match Self::display_character_select(&mut players, profile, ui) {
                             Selected(active) => View::run_simulation(active, players),
+                            Together(active, partner) => {
+                                View::run_together(active, partner, players)
+                            }
                             Details(active) => View::character_detail(active, players),
+                            HallOfFame => View::hall_of_fame(players),
+                            ShowSettings => {
+                                *show_settings = !*show_settings;
+                                View::character_select(players)
+                            }
                             Create => {
                                 let (player, stats_builder) = Self::make_new_character(rng);
                                 View::character_creation(player, stats_builder, players)
@@ -694,7 +1964,7 @@ impl MainWindow {
                 CentralPanel::default()
                     .show(ctx, |ui| {
                         use DetailsResult::*;
-                        match Self::display_character_detail(&players[active], ui) {
+                        match Self::display_character_detail(&mut players[active], toasts, ui) {
                             Play => View::run_simulation(active, players),
                             Close => View::character_select(players),
                             Nothing => View::character_detail(active, players),
@@ -703,6 +1973,15 @@ impl MainWindow {
                     .inner
             }
 
+            View::HallOfFame { players } => {
+                CentralPanel::default()
+                    .show(ctx, |ui| match Self::display_hall_of_fame(profile, ui) {
+                        true => View::character_select(players),
+                        false => View::hall_of_fame(players),
+                    })
+                    .inner
+            }
+
             View::CharacterCreation {
                 mut player,
                 mut stats_builder,
@@ -719,6 +1998,9 @@ impl MainWindow {
                         );
                         match creation {
                             Created => {
+                                player.outfit();
+                                player.time_scale = settings.time_scale(&config::Tuning::default());
+                                profile.created();
                                 players.push(player);
                                 View::run_simulation(players.len() - 1, players)
                             }
@@ -730,15 +2012,41 @@ impl MainWindow {
             }
 
             View::RunSimulation {
-                mut simulation,
+                game,
+                mut title_card,
+                session,
                 active,
-                players,
             } => {
-                Self::display_game(&mut simulation, rng, ctx);
+                game.poll();
+                {
+                    let mut game = game.lock();
+                    let Game {
+                        simulation,
+                        party,
+                        partner,
+                        diagnostics,
+                    } = &mut *game;
+                    for diagnostic in diagnostics.drain(..) {
+                        toasts.warn(diagnostic)
+                    }
+                    Self::display_game(
+                        simulation,
+                        party,
+                        partner.as_mut(),
+                        &mut title_card,
+                        settings,
+                        show_settings,
+                        show_hints,
+                        toasts,
+                        time,
+                        ctx,
+                    );
+                }
                 View::RunSimulation {
-                    simulation,
+                    game,
+                    title_card,
+                    session,
                     active,
-                    players,
                 }
             }
 
@@ -746,14 +2054,166 @@ impl MainWindow {
         }
     }
 
+    /// Save while playing, so a crash doesn't lose hours of progress
+    fn autosave(&mut self, frame: &mut eframe::Frame, now: f64) {
+        if !self.view.dirty() {
+            return;
+        }
+        if now - self.last_autosave < self.settings.autosave.interval() as f64 {
+            return;
+        }
+
+        self.last_autosave = now;
+        if let Some(storage) = frame.storage_mut() {
+            eframe::App::save(self, storage);
+            storage.flush();
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
     fn maybe_process_tray(&mut self, frame: &mut eframe::Frame) {
-        if let Ok(TrayEvent {
-            event: tray_icon::ClickEvent::Double,
-            ..
-        }) = tray_icon::TrayEvent::receiver().try_recv()
-        {
-            self.is_visible = !self.is_visible;
-            frame.set_visible(self.is_visible)
+        let Some(tray) = &mut self.tray else {
+            return;
+        };
+        for err in tray.bind(&self.settings.hotkeys) {
+            self.toasts.error(err)
+        }
+
+        let characters = self
+            .view
+            .with_players(|players, active| {
+                active
+                    .into_iter()
+                    .chain(players)
+                    .map(|player| {
+                        format!(
+                            "{} — level {}, {}",
+                            player.name,
+                            player.level,
+                            act_name(player.quest_book.act())
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        tray.update(characters, self.view.is_paused(), self.is_visible);
+
+        while let Some(command) = tray.poll() {
+            match command {
+                Command::TogglePause => {
+                    if let Some(paused) = self.view.is_paused() {
+                        self.view.set_paused(!paused)
+                    }
+                }
+                Command::ToggleVisible => {
+                    self.is_visible = !self.is_visible;
+                    frame.set_visible(self.is_visible)
+                }
+                Command::Quit => frame.close(),
+            }
+        }
+    }
+
+    /// Look back through the recent snapshots of the character. Nothing here can be changed
+    fn display_timeline(
+        timeline: &Timeline,
+        elapsed: f32,
+        scrub: &mut Option<f32>,
+        ui: &mut egui::Ui,
+    ) {
+        let Some(last) = timeline.len().checked_sub(1) else {
+            ui.weak("Nothing has been recorded yet");
+            return;
+        };
+
+        // the oldest moments fall off the front, so the pick is kept by when it was taken
+        let mut index = scrub
+            .and_then(|at| {
+                timeline
+                    .checkpoints()
+                    .position(|checkpoint| checkpoint.elapsed >= at)
+            })
+            .unwrap_or(last);
+        ui.add(Slider::new(&mut index, 0..=last).show_value(false));
+        let Some(checkpoint) = timeline.get(index) else {
+            return;
+        };
+        *scrub = (index < last).then_some(checkpoint.elapsed);
+
+        ui.label(format!(
+            "{} ago, at {}",
+            Time::compact(elapsed - checkpoint.elapsed),
+            Time::clock(checkpoint.elapsed)
+        ));
+        egui::Grid::new("timeline_checkpoint")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.monospace("Level");
+                ui.label(checkpoint.level.to_string());
+                ui.end_row();
+                ui.monospace("Act");
+                ui.label(act_name(checkpoint.act));
+                ui.end_row();
+                ui.monospace("Gold");
+                ui.label(checkpoint.gold.to_string());
+                ui.end_row();
+                ui.monospace("Task");
+                ui.label(checkpoint.task.as_deref().unwrap_or("Nothing"));
+                ui.end_row();
+            });
+
+        CollapsingHeader::new("Stats")
+            .id_source("timeline_stats")
+            .show(ui, |ui| {
+                for (stat, value) in checkpoint.stats.iter() {
+                    ui.horizontal(|ui| {
+                        ui.monospace(stat.as_str());
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ui.label(value.to_string());
+                        });
+                    });
+                }
+            });
+        CollapsingHeader::new("Equipment")
+            .id_source("timeline_equipment")
+            .show(ui, |ui| {
+                for (slot, piece) in &checkpoint.equipment {
+                    ui.horizontal(|ui| {
+                        ui.monospace(slot.as_str());
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ui.label(piece);
+                        });
+                    });
+                }
+            });
+        CollapsingHeader::new(format!("Inventory ({})", checkpoint.items.len()))
+            .id_source("timeline_inventory")
+            .show(ui, |ui| {
+                ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                    for (name, quantity) in &checkpoint.items {
+                        ui.horizontal(|ui| {
+                            ui.label(name);
+                            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                ui.monospace(quantity.to_string());
+                            });
+                        });
+                    }
+                });
+            });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn track_window(&mut self, frame: &mut eframe::Frame) {
+        if self.mini != self.settings.mini {
+            self.mini = self.settings.mini;
+            self.settings.switch_window(frame);
+            return;
+        }
+        // hidden in the tray, so there's nothing to follow
+        if self.is_visible {
+            self.settings
+                .geometry_mut()
+                .track(&frame.info().window_info);
         }
     }
 }
@@ -763,19 +2223,177 @@ impl eframe::App for MainWindow {
         const DEBUG_KEY: egui::KeyboardShortcut =
             egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::F12);
         if ctx.input_mut().consume_shortcut(&DEBUG_KEY) {
-            ctx.set_debug_on_hover(!ctx.debug_on_hover())
+            self.show_debug = !self.show_debug
+        }
+        Window::new("Debug")
+            .open(&mut self.show_debug)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let mut debug_on_hover = ctx.debug_on_hover();
+                if ui.checkbox(&mut debug_on_hover, "Debug on hover").changed() {
+                    ctx.set_debug_on_hover(debug_on_hover)
+                }
+                #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+                {
+                    ui.separator();
+                    match &self.content_watcher {
+                        Some(watcher) => ui.label(format!("Watching {}", watcher.path().display())),
+                        None => ui.label("Set PACING_CONTENT to reload a content pack"),
+                    };
+                }
+                if let View::RunSimulation { game, .. } = &self.view {
+                    let game = game.lock();
+                    // looking back is as close to rewinding as it gets
+                    if game.simulation.player.ironman.is_none() {
+                        ui.separator();
+                        ui.strong("Timeline");
+                        Self::display_timeline(
+                            game.simulation.timeline(),
+                            game.simulation.player.elapsed,
+                            &mut self.scrub,
+                            ui,
+                        );
+                    }
+                }
+                ui.separator();
+                ui.strong("Notifications");
+                self.toasts.display_history(ui);
+            });
+        #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+        self.reload_content();
+        if self.settings.zoom_with_keyboard(ctx) {
+            self.settings
+                .apply_zoom(ctx, frame.info().native_pixels_per_point);
+        }
+
+        const SETTINGS_TOGGLE_KEY: egui::KeyboardShortcut =
+            egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::F2);
+        if ctx.input_mut().consume_shortcut(&SETTINGS_TOGGLE_KEY) {
+            self.show_settings = !self.show_settings
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            const MINI_TOGGLE_KEY: egui::KeyboardShortcut =
+                egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::F3);
+            if ctx.input_mut().consume_shortcut(&MINI_TOGGLE_KEY) {
+                self.settings.mini = !self.settings.mini
+            }
         }
-        egui::gui_zoom::zoom_with_keyboard_shortcuts(ctx, frame.info().native_pixels_per_point);
 
+        #[cfg(not(target_arch = "wasm32"))]
         self.maybe_process_tray(frame);
-        Self::display_main_view(&mut self.view, &self.rng, ctx)
+        #[cfg(not(target_arch = "wasm32"))]
+        self.track_window(frame);
+        if self
+            .settings
+            .display(&mut self.show_settings, &mut self.toasts, ctx)
+        {
+            self.settings.apply_style(ctx);
+            self.settings
+                .apply_zoom(ctx, frame.info().native_pixels_per_point);
+            self.toasts.set_muted(!self.settings.notifications);
+        }
+        #[cfg(all(feature = "update-check", not(target_arch = "wasm32")))]
+        self.update_check.display(&mut self.toasts, ctx);
+        self.display_unreadable_save(ctx);
+        if let Some(summary) = &self.farewell {
+            if !self.is_visible {
+                self.is_visible = true;
+                frame.set_visible(true)
+            }
+            Self::display_farewell(summary, ctx, frame);
+        }
+        Self::display_main_view(
+            &mut self.view,
+            &mut self.profile,
+            &mut self.settings,
+            &mut self.show_settings,
+            &mut self.show_hints,
+            &mut self.toasts,
+            &self.time,
+            &self.rng,
+            ctx,
+        );
+        // eframe doesn't say when the window is minimized, but it has no size then
+        #[cfg(not(target_arch = "wasm32"))]
+        let minimized = frame.info().window_info.size == egui::Vec2::ZERO;
+        #[cfg(target_arch = "wasm32")]
+        let minimized = false;
+        let hidden = !self.is_visible || minimized;
+        // the boost applies to the time that passed while hidden, so it's only changed afterwards
+        self.time.update(
+            ctx.input().time,
+            self.settings.background_speed.filter(|_| hidden),
+        );
+        self.autosave(frame, ctx.input().time);
+        self.toasts.display(ctx);
+    }
+
+    fn on_close_event(&mut self) -> bool {
+        if self.farewell.is_some() {
+            return true;
+        }
+
+        // hold off closing for a moment to show what happened this session
+        self.farewell = self.view.end_session();
+        self.farewell.is_none()
     }
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        if let Some((players, active)) = self.view.players() {
+        if let Some(unreadable) = &mut self.unreadable_save {
+            if !unreadable.backed_up {
+                match storage::backup(storage, unreadable.data.clone()) {
+                    Ok(()) => unreadable.backed_up = true,
+                    Err(err) => self
+                        .toasts
+                        .error(format!("The unreadable save couldn't be backed up: {err}")),
+                }
+            }
+            if !(unreadable.backed_up && unreadable.replace) {
+                self.save_settings(storage);
+                return;
+            }
+            self.unreadable_save = None;
+        }
+
+        let players = self.view.with_players_mut(|players, active| {
             // this moves the active player to the first slot
-            let players = active.into_iter().chain(players).collect::<Vec<_>>();
-            eframe::set_value(storage, Self::SETTINGS_KEY, &players);
+            active
+                .into_iter()
+                .chain(players)
+                .map(|player| {
+                    player.last_seen = view::now();
+                    // the live player keeps its full history for the rest of the session
+                    let mut player = player.clone();
+                    player.compact();
+                    player
+                })
+                .collect::<Vec<_>>()
+        });
+        if let Some(players) = players {
+            storage.set_string(storage::SEAL_KEY, self.seal_key.clone());
+            let saved = save::encode(
+                players,
+                self.profile.clone(),
+                self.settings.clone(),
+                &self.seal_key,
+            )
+            .and_then(|data| storage::store(storage, data));
+            match saved {
+                Ok(()) => {
+                    self.view.mark_saved();
+                    storage.set_string(Self::SETTINGS_KEY, String::new());
+                    storage.set_string(Self::CONFIG_KEY, String::new());
+                    #[cfg(not(target_arch = "wasm32"))]
+                    self.store_launch();
+                }
+                Err(err) => self
+                    .toasts
+                    .error(format!("The game couldn't be saved: {err}")),
+            }
+        } else {
+            self.save_settings(storage);
         }
     }
 
@@ -1,4 +1,4 @@
-use egui::{vec2, Align2, NumExt, Pos2, Rect, Rounding, Sense, Stroke, TextStyle};
+use egui::{vec2, Align2, NumExt, Rect, Rounding, Sense, Stroke, TextStyle};
 
 use crate::mechanics::Bar;
 
@@ -21,6 +21,8 @@ pub struct Progress<A = usize, B = usize> {
     pub max: B,
 
     info: ProgressInfo,
+    /// Real seconds since the bar last filled up
+    completed: Option<f32>,
 }
 
 pub trait ToF32 {
@@ -39,8 +41,13 @@ impl ToF32 for f32 {
 }
 
 impl Progress<f32, f32> {
-    pub const fn from_bar(Bar { max, pos }: Bar, info: ProgressInfo) -> Self {
-        Self { pos, max, info }
+    pub const fn from_bar(Bar { max, pos, .. }: Bar, info: ProgressInfo) -> Self {
+        Self {
+            pos,
+            max,
+            info,
+            completed: None,
+        }
     }
 }
 
@@ -49,6 +56,14 @@ where
     A: ToF32,
     B: ToF32,
 {
+    const PULSE: f32 = 0.6;
+
+    /// Pulse the bar if it filled up `since` real seconds ago
+    pub fn completed(mut self, since: Option<f32>) -> Self {
+        self.completed = since;
+        self
+    }
+
     pub fn display(self, ui: &mut egui::Ui) -> egui::Response {
         let row_height = ui
             .fonts()
@@ -75,6 +90,17 @@ where
             Stroke::NONE,
         );
 
+        if let Some(since) = self.completed.filter(|&since| since < Self::PULSE) {
+            let fade = 1.0 - since / Self::PULSE;
+            ui.painter().rect(
+                rect.expand(2.0 * fade),
+                Rounding::none(),
+                visuals.selection.bg_fill.linear_multiply(fade),
+                visuals.selection.stroke,
+            );
+            ui.ctx().request_repaint();
+        }
+
         let resp = resp.interact(Sense::hover());
         if resp.hovered() {
             use ProgressInfo::*;
@@ -91,26 +117,12 @@ where
                 }
             };
 
-            let fid = TextStyle::Monospace.resolve(ui.style());
-            let (width, height) = {
-                let fonts = &*ui.fonts();
-                let width = overlay
-                    .chars()
-                    .fold(0.0, |a, c| a + fonts.glyph_width(&fid, c));
-                (width, row_height)
-            };
-
-            let mid = (rect.width() - width) * 0.5;
-            let left = rect.left_top().x + mid;
-
+            // the monospace font is the one scaled by the text size setting
             ui.painter().text(
-                Pos2 {
-                    x: left + 2.0,
-                    y: rect.left_top().y + (height / 2.0),
-                },
-                Align2::LEFT_CENTER,
+                rect.center(),
+                Align2::CENTER_CENTER,
                 overlay,
-                fid,
+                TextStyle::Monospace.resolve(ui.style()),
                 ui.visuals().strong_text_color(),
             );
         }
@@ -0,0 +1,26 @@
+//! Saves are base64 encoded [`SaveFile`]s, which keeps them small enough for localStorage on the
+//! web. They're sealed with the key from [`storage::seal_key`](crate::storage::seal_key)
+
+use base64::Engine as _;
+use pacing_core::{mechanics::Player, profile::Profile, save::SaveFile};
+
+use crate::settings::Settings;
+
+const ENGINE: base64::engine::GeneralPurpose = base64::engine::general_purpose::STANDARD;
+
+pub fn encode(
+    players: Vec<Player>,
+    profile: Profile,
+    settings: Settings,
+    key: &str,
+) -> Result<String, String> {
+    let data = SaveFile::new(players, profile, settings)
+        .encode(Some(key.as_bytes()))
+        .map_err(|err| err.to_string())?;
+    Ok(ENGINE.encode(data))
+}
+
+pub fn decode(data: &str, key: &str) -> Result<SaveFile<Settings>, String> {
+    let data = ENGINE.decode(data).map_err(|err| err.to_string())?;
+    SaveFile::decode(&data, Some(key.as_bytes())).map_err(|err| err.to_string())
+}
@@ -0,0 +1,904 @@
+use std::{collections::BTreeSet, path::PathBuf, time::Duration};
+
+use egui::{Pos2, TextEdit, Vec2, Window};
+
+use crate::{
+    config::{self, GlyphStyle, Hint},
+    lingo,
+    mechanics::Simulation,
+    toast::Toasts,
+};
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Settings {
+    pub filter_names: bool,
+    pub deny_list: Vec<String>,
+    pub synthesize: bool,
+    pub task_glyphs: bool,
+    pub party_ticking: bool,
+    pub hints: bool,
+    /// Hints that were dismissed, so they aren't shown again
+    pub seen_hints: BTreeSet<Hint>,
+    /// Where the window was when the app was last closed
+    pub window: Geometry,
+    /// Play in a small window with just the task, see [`Self::mini_window`]
+    pub mini: bool,
+    /// Where the window was in mini mode, kept apart from [`Self::window`]
+    pub mini_window: Geometry,
+    pub repaint: Repaint,
+    /// Only read when the window is created, see [`Launch`]
+    pub vsync: bool,
+    pub autosave: Autosave,
+    /// How much faster the game goes while the window is hidden, if at all
+    pub background_speed: Option<f32>,
+    /// Show the equipment laid out on a paper doll, rather than as a list
+    pub paper_doll: bool,
+    /// How much bigger the text is drawn, leaving the rest of the interface its usual size
+    pub text_scale: f32,
+    /// How much bigger everything is drawn, also changed by zooming with ctrl +
+    pub ui_scale: f32,
+    pub hotkeys: Hotkeys,
+    pub theme: Theme,
+    /// Show the informational toasts, problems are always shown
+    pub notifications: bool,
+    /// How fast new characters start out going
+    pub default_time_scale: f32,
+    /// A ron content pack to play with in place of the built-in content
+    pub content: Option<PathBuf>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            filter_names: true,
+            deny_list: config::NAME_DENY_LIST
+                .iter()
+                .copied()
+                .map(String::from)
+                .collect(),
+            synthesize: false,
+            task_glyphs: true,
+            party_ticking: false,
+            hints: true,
+            seen_hints: BTreeSet::new(),
+            window: Geometry::default(),
+            mini: false,
+            mini_window: Geometry::default(),
+            repaint: Repaint::default(),
+            vsync: true,
+            autosave: Autosave::default(),
+            background_speed: None,
+            paper_doll: true,
+            text_scale: 1.0,
+            ui_scale: 1.0,
+            hotkeys: Hotkeys::default(),
+            theme: Theme::default(),
+            notifications: true,
+            default_time_scale: 1.0,
+            content: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    /// The beige and navy of the original Progress Quest
+    Classic,
+    /// White and yellow on black, with heavier outlines
+    HighContrast,
+}
+
+impl Theme {
+    pub const ALL: [Self; 4] = [Self::Dark, Self::Light, Self::Classic, Self::HighContrast];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dark => "Dark",
+            Self::Light => "Light",
+            Self::Classic => "Classic",
+            Self::HighContrast => "High contrast",
+        }
+    }
+
+    pub const fn palette(self) -> Palette {
+        use egui::Color32;
+        match self {
+            Self::Dark => Palette {
+                success_fill: Color32::from_rgb(0x21, 0x36, 0x54),
+                success_text: Color32::from_rgb(0x8d, 0xb6, 0xf2),
+                caution_fill: Color32::from_rgb(0x57, 0x26, 0x22),
+                caution_text: Color32::from_rgb(0xf2, 0x94, 0x94),
+            },
+            Self::Light => Palette {
+                success_fill: Color32::from_rgb(0xc9, 0xdc, 0xf7),
+                success_text: Color32::from_rgb(0x1a, 0x3c, 0x6e),
+                caution_fill: Color32::from_rgb(0xf7, 0xd0, 0xcc),
+                caution_text: Color32::from_rgb(0x7a, 0x1f, 0x19),
+            },
+            Self::Classic => Palette {
+                success_fill: Color32::from_rgb(0x00, 0x00, 0x80),
+                success_text: Color32::WHITE,
+                caution_fill: Color32::from_rgb(0x80, 0x00, 0x00),
+                caution_text: Color32::WHITE,
+            },
+            Self::HighContrast => Palette {
+                success_fill: Color32::BLACK,
+                success_text: Color32::from_rgb(0x00, 0xff, 0xff),
+                caution_fill: Color32::BLACK,
+                caution_text: Color32::from_rgb(0xff, 0x80, 0x80),
+            },
+        }
+    }
+
+    fn visuals(self) -> egui::Visuals {
+        use egui::{Color32, Stroke};
+        match self {
+            Self::Dark => egui::Visuals::dark(),
+            Self::Light => egui::Visuals::light(),
+            Self::Classic => {
+                const BEIGE: Color32 = Color32::from_rgb(0xd4, 0xd0, 0xc8);
+                const SHADOW: Color32 = Color32::from_rgb(0x80, 0x80, 0x80);
+                const NAVY: Color32 = Color32::from_rgb(0x00, 0x00, 0x80);
+
+                let mut visuals = egui::Visuals::light();
+                visuals.window_fill = BEIGE;
+                visuals.panel_fill = BEIGE;
+                visuals.faint_bg_color = Color32::from_rgb(0xdf, 0xdc, 0xd5);
+                visuals.extreme_bg_color = Color32::WHITE;
+                visuals.code_bg_color = Color32::from_rgb(0xc0, 0xbc, 0xb4);
+                visuals.window_stroke = Stroke::new(1.0, SHADOW);
+                visuals.selection.bg_fill = NAVY;
+                visuals.selection.stroke = Stroke::new(1.0, Color32::WHITE);
+                visuals.hyperlink_color = NAVY;
+                visuals.widgets.noninteractive.bg_fill = BEIGE;
+                visuals.widgets.noninteractive.bg_stroke = Stroke::new(1.0, SHADOW);
+                visuals.widgets.noninteractive.fg_stroke = Stroke::new(1.0, Color32::BLACK);
+                visuals.widgets.inactive.bg_fill = Color32::from_rgb(0xc8, 0xc4, 0xbc);
+                visuals.widgets.inactive.fg_stroke = Stroke::new(1.0, Color32::BLACK);
+                visuals.widgets.hovered.bg_fill = Color32::from_rgb(0xe4, 0xe0, 0xd8);
+                visuals.widgets.active.bg_fill = SHADOW;
+                visuals
+            }
+            Self::HighContrast => {
+                let text = Stroke::new(1.5, Color32::WHITE);
+                let outline = Stroke::new(2.0, Color32::WHITE);
+                let mut visuals = egui::Visuals::dark();
+                visuals.override_text_color = Some(Color32::WHITE);
+                visuals.window_fill = Color32::BLACK;
+                visuals.panel_fill = Color32::BLACK;
+                visuals.faint_bg_color = Color32::BLACK;
+                visuals.extreme_bg_color = Color32::BLACK;
+                visuals.code_bg_color = Color32::from_gray(0x40);
+                visuals.window_stroke = outline;
+                visuals.selection.bg_fill = Color32::from_rgb(0xff, 0xd7, 0x00);
+                visuals.selection.stroke = Stroke::new(2.0, Color32::BLACK);
+                visuals.hyperlink_color = Color32::from_rgb(0x00, 0xff, 0xff);
+                for widget in [
+                    &mut visuals.widgets.noninteractive,
+                    &mut visuals.widgets.inactive,
+                    &mut visuals.widgets.hovered,
+                    &mut visuals.widgets.active,
+                    &mut visuals.widgets.open,
+                ] {
+                    widget.bg_fill = Color32::BLACK;
+                    widget.bg_stroke = outline;
+                    widget.fg_stroke = text;
+                }
+                visuals.widgets.hovered.bg_fill = Color32::from_gray(0x30);
+                visuals.widgets.active.bg_fill = Color32::from_gray(0x50);
+                visuals
+            }
+        }
+    }
+}
+
+/// The colors a [`Theme`] uses for buttons that go ahead with something, or undo or remove it
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Palette {
+    pub success_fill: egui::Color32,
+    pub success_text: egui::Color32,
+    pub caution_fill: egui::Color32,
+    pub caution_text: egui::Color32,
+}
+
+impl Palette {
+    /// The palette of the theme in use, as of the last [`Settings::apply_style`]
+    pub fn of(ctx: &egui::Context) -> Self {
+        ctx.data()
+            .get_temp(egui::Id::new("palette"))
+            .unwrap_or_else(|| Theme::default().palette())
+    }
+}
+
+/// Keys that work even while the window isn't focused, like `ctrl+alt+P`. An empty one is off
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Hotkeys {
+    pub pause: String,
+    /// Shows or hides the window
+    pub show: String,
+}
+
+impl Default for Hotkeys {
+    fn default() -> Self {
+        Self {
+            pause: String::from("ctrl+alt+P"),
+            show: String::from("ctrl+alt+O"),
+        }
+    }
+}
+
+/// When to save while playing, on top of the save when the app is closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Autosave {
+    /// After every task, but no more often than [`Autosave::MIN_INTERVAL`]
+    EveryTask,
+    /// Every so many seconds, if a task was completed since the last save
+    Interval(u64),
+}
+
+impl Autosave {
+    const DEFAULT_INTERVAL: u64 = 30;
+    /// Tasks can finish every frame at high speeds, and each save encodes and flushes everything
+    pub const MIN_INTERVAL: u64 = 5;
+
+    /// How many seconds to wait between saves
+    pub const fn interval(self) -> u64 {
+        match self {
+            Self::EveryTask => Self::MIN_INTERVAL,
+            Self::Interval(secs) => secs,
+        }
+    }
+}
+
+impl Default for Autosave {
+    fn default() -> Self {
+        Self::Interval(Self::DEFAULT_INTERVAL)
+    }
+}
+
+/// How often the window is redrawn while playing.
+///
+/// The simulation keeps time by the clock rather than by counting frames, so redrawing less often
+/// only makes the bars move in bigger steps, the game itself goes just as fast
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Repaint {
+    #[default]
+    Smooth,
+    Relaxed,
+    LowPower,
+}
+
+impl Repaint {
+    pub const ALL: [Self; 3] = [Self::Smooth, Self::Relaxed, Self::LowPower];
+
+    pub const fn interval(self) -> Duration {
+        match self {
+            Self::Smooth => Duration::from_millis(16),
+            Self::Relaxed => Duration::from_millis(100),
+            Self::LowPower => Duration::from_secs(1),
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Smooth => "Smooth (60 fps)",
+            Self::Relaxed => "Relaxed (10 fps)",
+            Self::LowPower => "Low power (1 fps)",
+        }
+    }
+}
+
+/// The settings needed before the window exists, which is before the saves can be read.
+///
+/// These are copied to their own file in the data directory whenever the game is saved
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Launch {
+    pub vsync: bool,
+    /// Of whichever mode the window was last in
+    pub window: Geometry,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for Launch {
+    fn default() -> Self {
+        Self {
+            vsync: Settings::default().vsync,
+            window: Geometry::default(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Launch {
+    fn path() -> std::path::PathBuf {
+        pacing_core::save::data_dir().join("egui.ron")
+    }
+
+    pub fn of(settings: &Settings) -> Self {
+        Self {
+            vsync: settings.vsync,
+            window: *settings.geometry(),
+        }
+    }
+
+    /// Open the window where it was left
+    pub fn native_options(&self) -> eframe::NativeOptions {
+        eframe::NativeOptions {
+            vsync: self.vsync,
+            maximized: self.window.maximized,
+            initial_window_pos: self.window.position,
+            initial_window_size: self.window.size,
+            ..Default::default()
+        }
+    }
+
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn store(&self) -> Result<(), String> {
+        let data = ron::ser::to_string(self).map_err(|err| err.to_string())?;
+        let path = Self::path();
+        path.parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, data))
+            .map_err(|err| format!("cannot write {}: {err}", path.display()))
+    }
+}
+
+/// The native window's position and size, restored on the next launch
+#[derive(Debug, Default, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Geometry {
+    pub position: Option<Pos2>,
+    pub size: Option<Vec2>,
+    pub maximized: bool,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Geometry {
+    /// Follow the window as it's moved and resized
+    pub fn track(&mut self, info: &eframe::WindowInfo) {
+        // minimized, so there's nothing to remember
+        if info.size == Vec2::ZERO {
+            return;
+        }
+        // eframe doesn't say whether the window is maximized, but a maximized window spans the
+        // whole monitor
+        self.maximized = info
+            .monitor_size
+            .map_or(false, |monitor| info.size.x >= monitor.x);
+        if self.maximized {
+            return;
+        }
+        self.position = info.position;
+        self.size = Some(info.size);
+    }
+
+    /// Move the window here, when switching in or out of mini mode. The window is opened where
+    /// it was left by [`Launch::native_options`]
+    pub fn restore(&self, frame: &mut eframe::Frame) {
+        if let Some(position) = self.position {
+            frame.set_window_pos(position)
+        }
+        if let Some(size) = self.size {
+            frame.set_window_size(size)
+        }
+    }
+}
+
+impl Settings {
+    /// The version written by [`Self::export`]
+    pub const EXPORT_VERSION: u32 = 1;
+    const EXPORT_HEADER: &'static str = "// pacing settings v";
+    /// Where the settings are exported to, and imported from, in the current directory
+    #[cfg(not(target_arch = "wasm32"))]
+    const EXPORT_PATH: &'static str = "pacing_settings.ron";
+    const ZOOM_STEP: f32 = 0.1;
+    const ZOOM_RANGE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+    const TEXT_RANGE: std::ops::RangeInclusive<f32> = 0.75..=2.0;
+    /// How big the window is in mini mode, until it's resized
+    #[cfg(not(target_arch = "wasm32"))]
+    const MINI_SIZE: Vec2 = Vec2::new(360.0, 100.0);
+
+    /// The window's geometry for the mode it's in
+    pub const fn geometry(&self) -> &Geometry {
+        if self.mini {
+            &self.mini_window
+        } else {
+            &self.window
+        }
+    }
+
+    pub fn geometry_mut(&mut self) -> &mut Geometry {
+        if self.mini {
+            &mut self.mini_window
+        } else {
+            &mut self.window
+        }
+    }
+
+    /// Resize the window for the mode it's just been switched to
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn switch_window(&self, frame: &mut eframe::Frame) {
+        let geometry = self.geometry();
+        match geometry.size {
+            Some(_) => geometry.restore(frame),
+            None if self.mini => frame.set_window_size(Self::MINI_SIZE),
+            None => {}
+        }
+    }
+
+    /// The settings as a file that can be carried to another machine
+    pub fn export(&self) -> Result<String, String> {
+        let body = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())?;
+        Ok(format!(
+            "{}{}\n{body}",
+            Self::EXPORT_HEADER,
+            Self::EXPORT_VERSION
+        ))
+    }
+
+    /// Read settings written by [`Self::export`]. The window's positions are kept from `self`,
+    /// since it belongs to this machine's screen
+    pub fn import(&self, data: &str) -> Result<Self, String> {
+        let (version, body) = data
+            .strip_prefix(Self::EXPORT_HEADER)
+            .and_then(|rest| rest.split_once('\n'))
+            .and_then(|(version, body)| Some((version.trim().parse::<u32>().ok()?, body)))
+            .ok_or_else(|| String::from("this isn't an exported settings file"))?;
+        if version > Self::EXPORT_VERSION {
+            return Err(format!(
+                "the settings are from version {version}, but only up to {} is supported",
+                Self::EXPORT_VERSION
+            ));
+        }
+
+        let settings = ron::from_str::<Self>(body).map_err(|err| err.to_string())?;
+        Ok(Self {
+            window: self.window,
+            mini_window: self.mini_window,
+            ..settings
+        }
+        .clamped())
+    }
+
+    /// Pull the scales back into the range of their sliders, in case they were edited by hand
+    pub fn clamped(self) -> Self {
+        let clamp = |scale: f32, range: std::ops::RangeInclusive<f32>| {
+            if scale.is_finite() {
+                scale.clamp(*range.start(), *range.end())
+            } else {
+                1.0
+            }
+        };
+        Self {
+            text_scale: clamp(self.text_scale, Self::TEXT_RANGE),
+            ui_scale: clamp(self.ui_scale, Self::ZOOM_RANGE),
+            ..self
+        }
+    }
+
+    /// Push the settings into the global name generation state
+    pub fn apply(&self) {
+        lingo::set_name_filter(self.filter_names);
+        lingo::set_deny_list(&self.deny_list);
+    }
+
+    /// Use the [`Self::theme`], and scale the text by [`Self::text_scale`] on top of egui's usual
+    /// sizes
+    pub fn apply_style(&self, ctx: &egui::Context) {
+        let mut style = (*ctx.style()).clone();
+        style.visuals = self.theme.visuals();
+        ctx.data()
+            .insert_temp(egui::Id::new("palette"), self.theme.palette());
+        for (text_style, font) in egui::Style::default().text_styles {
+            style.text_styles.insert(
+                text_style,
+                egui::FontId::new(font.size * self.text_scale, font.family),
+            );
+        }
+        ctx.set_style(style);
+    }
+
+    /// Zoom everything by [`Self::ui_scale`], on top of the screen's own scale
+    pub fn apply_zoom(&self, ctx: &egui::Context, native_pixels_per_point: Option<f32>) {
+        if let Some(native) = native_pixels_per_point {
+            ctx.set_pixels_per_point(native * self.ui_scale)
+        }
+    }
+
+    /// Zoom in and out with ctrl + and ctrl -, or back to the usual size with ctrl 0. Returns
+    /// whether the [`Self::ui_scale`] changed
+    pub fn zoom_with_keyboard(&mut self, ctx: &egui::Context) -> bool {
+        use egui::{Key, KeyboardShortcut, Modifiers};
+        const ZOOM_IN: KeyboardShortcut =
+            KeyboardShortcut::new(Modifiers::COMMAND, Key::PlusEquals);
+        const ZOOM_OUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Minus);
+        const ZOOM_RESET: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Num0);
+
+        let mut input = ctx.input_mut();
+        let scale = if input.consume_shortcut(&ZOOM_RESET) {
+            1.0
+        } else if input.consume_shortcut(&ZOOM_IN) {
+            self.ui_scale + Self::ZOOM_STEP
+        } else if input.consume_shortcut(&ZOOM_OUT) {
+            self.ui_scale - Self::ZOOM_STEP
+        } else {
+            return false;
+        };
+        // so repeated steps land on round numbers
+        let scale = ((scale / Self::ZOOM_STEP).round() * Self::ZOOM_STEP)
+            .clamp(*Self::ZOOM_RANGE.start(), *Self::ZOOM_RANGE.end());
+        let changed = scale != self.ui_scale;
+        self.ui_scale = scale;
+        changed
+    }
+
+    pub const fn glyph_style(&self) -> GlyphStyle {
+        if self.task_glyphs {
+            GlyphStyle::Emoji
+        } else {
+            GlyphStyle::Off
+        }
+    }
+
+    pub fn wants_hint(&self, hint: Hint) -> bool {
+        self.hints && !self.seen_hints.contains(&hint)
+    }
+
+    pub fn apply_to(&self, simulation: &mut Simulation) {
+        simulation.set_synthesis(self.synthesize);
+    }
+
+    /// How fast a new character starts out going, within what `tuning` allows
+    pub fn time_scale(&self, tuning: &config::Tuning) -> f32 {
+        tuning.clamp_time_scale(self.default_time_scale)
+    }
+
+    /// Swap in [`Self::content`]. Without one the content is left alone, so whatever was loaded
+    /// from elsewhere stays
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_content(&self) -> Result<(), String> {
+        let Some(path) = &self.content else {
+            return Ok(());
+        };
+        let pack = std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|data| ron::from_str(&data).map_err(|err| err.to_string()))
+            .map_err(|err| format!("cannot load content from {}: {err}", path.display()))?;
+        config::set_content(pack);
+        Ok(())
+    }
+
+    /// Returns true if anything was changed
+    pub fn display(&mut self, open: &mut bool, toasts: &mut Toasts, ctx: &egui::Context) -> bool {
+        let mut changed = false;
+
+        Window::new("Settings")
+            .open(open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.heading("Names");
+                changed |= ui
+                    .checkbox(&mut self.filter_names, "Filter offensive names")
+                    .changed();
+
+                ui.add_enabled_ui(self.filter_names, |ui| {
+                    ui.label("Deny list, one word per line");
+                    let mut text = self.deny_list.join("\n");
+                    if ui
+                        .add(TextEdit::multiline(&mut text).desired_rows(4))
+                        .changed()
+                    {
+                        self.deny_list = text.split('\n').map(String::from).collect();
+                        changed = true;
+                    }
+                    if ui.small_button("Reset to default").clicked() {
+                        self.deny_list = Self::default().deny_list;
+                        changed = true;
+                    }
+                });
+
+                ui.separator();
+                ui.heading("Content");
+                changed |= ui
+                    .checkbox(
+                        &mut self.synthesize,
+                        "Make up new monsters and items in the later acts",
+                    )
+                    .changed();
+                #[cfg(not(target_arch = "wasm32"))]
+                ui.horizontal(|ui| {
+                    let id = ui.id().with("content_path");
+                    let mut path = ui.data().get_temp::<String>(id).unwrap_or_else(|| {
+                        self.content
+                            .as_ref()
+                            .map_or_else(String::new, |path| path.display().to_string())
+                    });
+                    ui.add(
+                        TextEdit::singleline(&mut path)
+                            .hint_text("Built-in content")
+                            .desired_width(200.0),
+                    )
+                    .on_hover_text("A ron content pack, with races, classes, monsters and items");
+
+                    let load = ui.add_enabled(!path.trim().is_empty(), egui::Button::new("Load"));
+                    let reset =
+                        ui.add_enabled(self.content.is_some(), egui::Button::new("Built-in"));
+                    if load.clicked() || reset.clicked() {
+                        let content = load.clicked().then(|| PathBuf::from(path.trim()));
+                        let previous = std::mem::replace(&mut self.content, content);
+                        if reset.clicked() {
+                            config::set_content(config::ContentPack::default());
+                        }
+                        match self.load_content() {
+                            Ok(()) if reset.clicked() => {
+                                toasts.info("Switched back to the built-in content");
+                                changed = true;
+                            }
+                            Ok(()) => {
+                                toasts.info("Loaded the content, every character uses it now");
+                                changed = true;
+                            }
+                            Err(err) => {
+                                toasts.error(err);
+                                self.content = previous;
+                            }
+                        }
+                        if reset.clicked() {
+                            path.clear()
+                        }
+                    }
+                    ui.data().insert_temp(id, path);
+                });
+
+                ui.separator();
+                ui.heading("Display");
+                ui.horizontal(|ui| {
+                    ui.label("Theme");
+                    for theme in Theme::ALL {
+                        changed |= ui
+                            .selectable_value(&mut self.theme, theme, theme.as_str())
+                            .changed();
+                    }
+                });
+                changed |= ui
+                    .checkbox(&mut self.task_glyphs, "Show a glyph before each task")
+                    .changed();
+                changed |= ui
+                    .checkbox(
+                        &mut self.paper_doll,
+                        "Lay out the equipment on a paper doll",
+                    )
+                    .changed();
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    changed |= ui
+                        .checkbox(&mut self.mini, "Mini mode")
+                        .on_hover_text("Only show the task, in a small window (F3)")
+                        .changed();
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Redraw");
+                    egui::ComboBox::from_id_source("repaint")
+                        .selected_text(self.repaint.as_str())
+                        .show_ui(ui, |ui| {
+                            for repaint in Repaint::ALL {
+                                changed |= ui
+                                    .selectable_value(&mut self.repaint, repaint, repaint.as_str())
+                                    .changed();
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Text size");
+                    changed |= ui
+                        .add(
+                            egui::Slider::new(&mut self.text_scale, Self::TEXT_RANGE)
+                                .step_by(0.05)
+                                .custom_formatter(|scale, _| format!("{:.0}%", scale * 100.0)),
+                        )
+                        .on_hover_text("Only the text grows, unlike zooming with ctrl +")
+                        .changed();
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Zoom");
+                    changed |= ui
+                        .add(
+                            egui::Slider::new(&mut self.ui_scale, Self::ZOOM_RANGE)
+                                .step_by(f64::from(Self::ZOOM_STEP))
+                                .custom_formatter(|scale, _| format!("{:.0}%", scale * 100.0)),
+                        )
+                        .on_hover_text("Everything grows, like zooming with ctrl +")
+                        .changed();
+                });
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    changed |= ui
+                        .checkbox(&mut self.vsync, "Wait for vsync")
+                        .on_hover_text("Takes effect the next time the game is started")
+                        .changed();
+                }
+                changed |= ui
+                    .checkbox(&mut self.notifications, "Show notifications")
+                    .on_hover_text("Problems are shown either way")
+                    .changed();
+
+                ui.separator();
+                ui.heading("Speed");
+                ui.horizontal(|ui| {
+                    ui.label("New characters start at");
+                    let max = config::Tuning::DEFAULT.max_time_scale;
+                    changed |= ui
+                        .add(
+                            egui::Slider::new(&mut self.default_time_scale, 1.0..=max)
+                                .logarithmic(true)
+                                .suffix("×"),
+                        )
+                        .changed();
+                });
+
+                ui.separator();
+                ui.heading("Saving");
+                let mut every_task = matches!(self.autosave, Autosave::EveryTask);
+                ui.horizontal(|ui| {
+                    changed |= ui
+                        .radio_value(&mut every_task, true, "After every task")
+                        .changed();
+                    changed |= ui.radio_value(&mut every_task, false, "Every").changed();
+                    let mut secs = match self.autosave {
+                        Autosave::Interval(secs) => secs,
+                        Autosave::EveryTask => Autosave::DEFAULT_INTERVAL,
+                    };
+                    changed |= ui
+                        .add_enabled(
+                            !every_task,
+                            egui::DragValue::new(&mut secs)
+                                .clamp_range(5..=3600)
+                                .suffix(" seconds"),
+                        )
+                        .changed();
+                    self.autosave = if every_task {
+                        Autosave::EveryTask
+                    } else {
+                        Autosave::Interval(secs)
+                    };
+                });
+
+                ui.separator();
+                ui.heading("Background");
+                ui.horizontal(|ui| {
+                    let mut enabled = self.background_speed.is_some();
+                    changed |= ui.checkbox(&mut enabled, "Speed up while hidden").changed();
+                    let mut speed = self.background_speed.unwrap_or(5.0);
+                    changed |= ui
+                        .add_enabled(
+                            enabled,
+                            egui::Slider::new(&mut speed, 2.0..=20.0).suffix("×"),
+                        )
+                        .changed();
+                    self.background_speed = enabled.then_some(speed);
+                });
+
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    ui.separator();
+                    ui.heading("Hotkeys");
+                    egui::Grid::new("hotkeys").num_columns(2).show(ui, |ui| {
+                        for (label, keys) in [
+                            ("Pause", &mut self.hotkeys.pause),
+                            ("Show or hide", &mut self.hotkeys.show),
+                        ] {
+                            ui.label(label);
+                            // only applied once typed out, rather than on every key
+                            changed |= ui
+                                .add(TextEdit::singleline(keys).hint_text("Off"))
+                                .on_hover_text("Like ctrl+alt+P, works from any window")
+                                .lost_focus();
+                            ui.end_row();
+                        }
+                    });
+                    if ui.small_button("Reset to default").clicked() {
+                        self.hotkeys = Hotkeys::default();
+                        changed = true;
+                    }
+                }
+
+                ui.separator();
+                ui.heading("Party");
+                changed |= ui
+                    .checkbox(
+                        &mut self.party_ticking,
+                        "Keep every character questing, not just the one being played",
+                    )
+                    .changed();
+
+                ui.separator();
+                ui.heading("Hints");
+                changed |= ui
+                    .checkbox(
+                        &mut self.hints,
+                        "Explain each panel the first time it's shown",
+                    )
+                    .changed();
+                if ui
+                    .add_enabled(
+                        !self.seen_hints.is_empty(),
+                        egui::Button::new("Show them again"),
+                    )
+                    .clicked()
+                {
+                    self.seen_hints.clear();
+                    changed = true;
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    ui.separator();
+                    ui.heading("Transfer");
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button("Export")
+                            .on_hover_text(format!(
+                                "Write the settings to {} in the current directory",
+                                Self::EXPORT_PATH
+                            ))
+                            .clicked()
+                        {
+                            match self.export().and_then(|data| {
+                                std::fs::write(Self::EXPORT_PATH, data)
+                                    .map_err(|err| err.to_string())
+                            }) {
+                                Ok(()) => toasts
+                                    .info(format!("Wrote the settings to {}", Self::EXPORT_PATH)),
+                                Err(err) => toasts
+                                    .error(format!("Cannot write {}: {err}", Self::EXPORT_PATH)),
+                            }
+                        }
+                        if ui
+                            .button("Import")
+                            .on_hover_text(format!(
+                                "Read the settings from {} in the current directory",
+                                Self::EXPORT_PATH
+                            ))
+                            .clicked()
+                        {
+                            match std::fs::read_to_string(Self::EXPORT_PATH)
+                                .map_err(|err| err.to_string())
+                                .and_then(|data| self.import(&data))
+                            {
+                                Ok(settings) => {
+                                    *self = settings;
+                                    changed = true;
+                                    toasts.info(format!(
+                                        "Read the settings from {}",
+                                        Self::EXPORT_PATH
+                                    ))
+                                }
+                                Err(err) => toasts
+                                    .error(format!("Cannot import {}: {err}", Self::EXPORT_PATH)),
+                            }
+                        }
+                    });
+                }
+            });
+
+        if changed {
+            self.apply()
+        }
+        changed
+    }
+}
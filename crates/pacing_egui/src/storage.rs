@@ -0,0 +1,87 @@
+//! Where the saved characters are kept between sessions.
+//!
+//! Natively they go through eframe's storage, a file in the platform's data directory. On the web
+//! they're written straight to the browser's `localStorage`, so a full quota is reported rather
+//! than the save quietly going missing when the tab is closed.
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::JsCast as _;
+
+/// Where the characters are kept, under the same key eframe uses, so older saves are still found
+pub const KEY: &str = concat!(env!("CARGO_PKG_NAME"), "_saves");
+/// Where a save that couldn't be loaded is kept, before anything is saved over it
+pub const BACKUP_KEY: &str = concat!(env!("CARGO_PKG_NAME"), "_saves_backup");
+/// Where the key the saves are sealed with is kept, see [`seal_key`]
+pub const SEAL_KEY: &str = concat!(env!("CARGO_PKG_NAME"), "_seal_key");
+
+/// The key the saves are sealed with, so an edited save can't just be given a fresh checksum.
+/// It's made up the first time it's needed, and kept with the saves from then on
+pub fn seal_key(storage: Option<&dyn eframe::Storage>) -> String {
+    storage
+        .and_then(|storage| storage.get_string(SEAL_KEY))
+        .filter(|key| !key.is_empty())
+        .unwrap_or_else(|| {
+            (0..4)
+                .map(|_| format!("{:016x}", pacing_core::Rand::random_seed()))
+                .collect()
+        })
+}
+
+/// The saved characters, if there are any
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load(storage: Option<&dyn eframe::Storage>) -> Option<String> {
+    storage?.get_string(KEY).filter(|data| !data.is_empty())
+}
+
+pub fn store(storage: &mut dyn eframe::Storage, data: String) -> Result<(), String> {
+    store_at(storage, KEY, data)
+}
+
+/// Keep a copy of a save that couldn't be loaded, see [`BACKUP_KEY`]
+pub fn backup(storage: &mut dyn eframe::Storage, data: String) -> Result<(), String> {
+    store_at(storage, BACKUP_KEY, data)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn store_at(storage: &mut dyn eframe::Storage, key: &str, data: String) -> Result<(), String> {
+    storage.set_string(key, data);
+    Ok(())
+}
+
+/// The saved characters, if there are any
+#[cfg(target_arch = "wasm32")]
+pub fn load(storage: Option<&dyn eframe::Storage>) -> Option<String> {
+    let data = match local_storage() {
+        Some(local) => local.get_item(KEY).ok().flatten(),
+        None => storage?.get_string(KEY),
+    };
+    data.filter(|data| !data.is_empty())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn store_at(storage: &mut dyn eframe::Storage, key: &str, data: String) -> Result<(), String> {
+    // private browsing can turn localStorage off entirely
+    let Some(local) = local_storage() else {
+        storage.set_string(key, data);
+        return Ok(());
+    };
+
+    local.set_item(key, &data).map_err(|err| {
+        let full = err
+            .dyn_ref::<web_sys::DomException>()
+            .map_or(false, |err| err.name() == "QuotaExceededError");
+        if full {
+            format!(
+                "the browser's storage is full (the save is {} KiB)",
+                data.len() / 1024
+            )
+        } else {
+            format!("the browser refused to store it: {err:?}")
+        }
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
@@ -0,0 +1,152 @@
+//! Short-lived notices in the corner of the window, for things that used to fail quietly.
+//!
+//! Everything that was shown is kept in a history, which the debug window lists.
+
+use std::collections::VecDeque;
+
+use egui::{Align2, Area, Color32, Frame, Order, RichText, ScrollArea, Sense};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// How long a toast stays up, in seconds
+    const fn timeout(self) -> f64 {
+        match self {
+            Self::Info => 4.0,
+            Self::Warning => 8.0,
+            Self::Error => 12.0,
+        }
+    }
+
+    const fn color(self) -> Color32 {
+        match self {
+            Self::Info => Color32::LIGHT_BLUE,
+            Self::Warning => Color32::YELLOW,
+            Self::Error => Color32::LIGHT_RED,
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+struct Toast {
+    severity: Severity,
+    text: String,
+    /// When the toast was first shown, it starts timing out from then
+    shown: Option<f64>,
+}
+
+#[derive(Default)]
+pub struct Toasts {
+    active: VecDeque<Toast>,
+    history: Vec<(Severity, String)>,
+    /// Only keep the informational toasts in the history
+    muted: bool,
+}
+
+impl Toasts {
+    /// Only this many are shown at once, the rest wait their turn
+    const MAX_SHOWN: usize = 4;
+    const HISTORY: usize = 100;
+
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(Severity::Info, text.into())
+    }
+
+    pub fn warn(&mut self, text: impl Into<String>) {
+        self.push(Severity::Warning, text.into())
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(Severity::Error, text.into())
+    }
+
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
+    fn push(&mut self, severity: Severity, text: String) {
+        if self.history.len() == Self::HISTORY {
+            self.history.remove(0);
+        }
+        self.history.push((severity, text.clone()));
+        if self.muted && severity == Severity::Info {
+            return;
+        }
+        self.active.push_back(Toast {
+            severity,
+            text,
+            shown: None,
+        });
+    }
+
+    pub fn display(&mut self, ctx: &egui::Context) {
+        let now = ctx.input().time;
+        self.active.retain(|toast| {
+            toast
+                .shown
+                .map_or(true, |shown| now - shown < toast.severity.timeout())
+        });
+        if self.active.is_empty() {
+            return;
+        }
+
+        let mut dismissed = None;
+        Area::new("toasts")
+            .order(Order::Foreground)
+            .anchor(Align2::RIGHT_BOTTOM, [-8.0, -8.0])
+            .show(ctx, |ui| {
+                for (i, toast) in self.active.iter_mut().take(Self::MAX_SHOWN).enumerate() {
+                    toast.shown.get_or_insert(now);
+                    let resp = Frame::popup(ui.style())
+                        .stroke(egui::Stroke::new(1.0, toast.severity.color()))
+                        .show(ui, |ui| {
+                            ui.set_max_width(300.0);
+                            ui.label(RichText::new(&toast.text).color(toast.severity.color()));
+                        })
+                        .response
+                        .interact(Sense::click())
+                        .on_hover_text("Click to dismiss");
+                    if resp.clicked() {
+                        dismissed = Some(i)
+                    }
+                }
+            });
+
+        if let Some(i) = dismissed {
+            self.active.remove(i);
+        }
+        // keep redrawing so they time out even when nothing else is happening
+        ctx.request_repaint_after(std::time::Duration::from_millis(250));
+    }
+
+    /// Everything that was shown this session, most recent last
+    pub fn display_history(&self, ui: &mut egui::Ui) {
+        if self.history.is_empty() {
+            ui.weak("Nothing yet");
+            return;
+        }
+        ScrollArea::vertical()
+            .max_height(200.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for (severity, text) in &self.history {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(RichText::new(severity.as_str()).color(severity.color()));
+                        ui.label(text);
+                    });
+                }
+            });
+    }
+}
@@ -0,0 +1,195 @@
+//! The tray icon with its menu, and the global hotkeys, so the game can be looked after without
+//! focusing its window.
+//!
+//! Both only send events, which [`Tray::poll`] turns into [`Command`]s for the main window to act
+//! on during its next frame.
+
+use global_hotkey::{hotkey::HotKey, GlobalHotKeyEvent, GlobalHotKeyManager};
+use tray_icon::{
+    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    ClickEvent, TrayEvent, TrayIcon, TrayIconBuilder,
+};
+
+use crate::settings::Hotkeys;
+
+const ICON: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/icon.png"));
+
+/// The icon for the window, the same one the tray shows
+pub fn window_icon() -> eframe::IconData {
+    let (rgba, width, height) = decode_icon();
+    eframe::IconData {
+        width,
+        height,
+        rgba,
+    }
+}
+
+fn decode_icon() -> (Vec<u8>, u32, u32) {
+    let img =
+        image::load_from_memory_with_format(ICON, image::ImageFormat::Png).expect("valid icon");
+    let (width, height) = (img.width(), img.height());
+    (img.into_rgba8().into_raw(), width, height)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    TogglePause,
+    ToggleVisible,
+    Quit,
+}
+
+pub struct Tray {
+    icon: TrayIcon,
+    menu: Menu,
+    /// A line for each character, above the rest of the menu
+    status: Vec<MenuItem>,
+    shown: Vec<String>,
+    /// Whether the game was paused and the window visible when the menu was last updated
+    state: Option<(Option<bool>, bool)>,
+    pause: MenuItem,
+    show: MenuItem,
+    quit: MenuItem,
+    manager: Option<GlobalHotKeyManager>,
+    /// The hotkeys that are registered, with what they do
+    bound: Vec<(HotKey, Command)>,
+    hotkeys: Option<Hotkeys>,
+}
+
+impl Tray {
+    pub fn new() -> Result<Self, String> {
+        let (rgba, width, height) = decode_icon();
+        let icon =
+            tray_icon::icon::Icon::from_rgba(rgba, width, height).map_err(|err| err.to_string())?;
+
+        let menu = Menu::new();
+        let pause = MenuItem::new("Pause", true, None);
+        let show = MenuItem::new("Hide", true, None);
+        let quit = MenuItem::new("Quit", true, None);
+        menu.append(&PredefinedMenuItem::separator());
+        menu.append(&pause);
+        menu.append(&show);
+        menu.append(&PredefinedMenuItem::separator());
+        menu.append(&quit);
+
+        let icon = TrayIconBuilder::new()
+            .with_icon(icon)
+            .with_tooltip("Pacing")
+            .with_menu(Box::new(menu.clone()))
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        Ok(Self {
+            icon,
+            menu,
+            status: vec![],
+            shown: vec![],
+            state: None,
+            pause,
+            show,
+            quit,
+            // not every desktop allows them, the menu still works without
+            manager: GlobalHotKeyManager::new().ok(),
+            bound: vec![],
+            hotkeys: None,
+        })
+    }
+
+    /// Register `hotkeys` in place of the ones from before. Each one that can't be registered is
+    /// left out, and reported
+    pub fn bind(&mut self, hotkeys: &Hotkeys) -> Vec<String> {
+        if self.hotkeys.as_ref() == Some(hotkeys) {
+            return vec![];
+        }
+        self.hotkeys = Some(hotkeys.clone());
+
+        let Some(manager) = &self.manager else {
+            return vec![String::from(
+                "Global hotkeys aren't available on this desktop",
+            )];
+        };
+        for (hotkey, _) in self.bound.drain(..) {
+            let _ = manager.unregister(hotkey);
+        }
+
+        let mut errors = vec![];
+        for (keys, command) in [
+            (&hotkeys.pause, Command::TogglePause),
+            (&hotkeys.show, Command::ToggleVisible),
+        ] {
+            if keys.trim().is_empty() {
+                continue;
+            }
+            match keys.parse::<HotKey>() {
+                Ok(hotkey) => match manager.register(hotkey) {
+                    Ok(()) => self.bound.push((hotkey, command)),
+                    Err(err) => errors.push(format!("Cannot use {keys} as a hotkey: {err}")),
+                },
+                Err(err) => errors.push(format!("{keys} isn't a hotkey: {err}")),
+            }
+        }
+        errors
+    }
+
+    /// Show a line for each of `characters` in the menu, and label the other items for the state
+    /// the game is in
+    pub fn update(&mut self, characters: Vec<String>, paused: Option<bool>, visible: bool) {
+        if self.state != Some((paused, visible)) {
+            self.state = Some((paused, visible));
+            self.pause.set_enabled(paused.is_some());
+            self.pause.set_text(if paused == Some(true) {
+                "Resume"
+            } else {
+                "Pause"
+            });
+            self.show.set_text(if visible { "Hide" } else { "Show" });
+        }
+
+        if characters == self.shown {
+            return;
+        }
+        let _ = self
+            .icon
+            .set_tooltip(Some(characters.first().map_or("Pacing", String::as_str)));
+
+        for item in self.status.drain(..) {
+            let _ = self.menu.remove(&item);
+        }
+        for (i, line) in characters.iter().enumerate() {
+            let item = MenuItem::new(line, false, None);
+            self.menu.insert(&item, i);
+            self.status.push(item);
+        }
+        self.shown = characters;
+    }
+
+    /// The next thing the player asked for from the tray or a hotkey
+    pub fn poll(&self) -> Option<Command> {
+        if let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
+            let hotkey = self
+                .bound
+                .iter()
+                .find(|(hotkey, _)| hotkey.id() == event.id);
+            if let Some(&(_, command)) = hotkey {
+                return Some(command);
+            }
+        }
+
+        if let Ok(event) = MenuEvent::receiver().try_recv() {
+            return [
+                (&self.pause, Command::TogglePause),
+                (&self.show, Command::ToggleVisible),
+                (&self.quit, Command::Quit),
+            ]
+            .into_iter()
+            .find_map(|(item, command)| (item.id() == event.id).then_some(command));
+        }
+
+        match TrayEvent::receiver().try_recv() {
+            Ok(TrayEvent {
+                event: ClickEvent::Double,
+                ..
+            }) => Some(Command::ToggleVisible),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Asks GitHub whether there's a newer release, when built with the `update-check` feature.
+//!
+//! Nothing is downloaded or installed, a banner links to the release and shows its notes.
+
+use std::sync::mpsc::{self, Receiver};
+
+use egui::{Color32, RichText, ScrollArea, TopBottomPanel, Window};
+
+use crate::toast::Toasts;
+
+const LATEST: &str = "https://api.github.com/repos/museun/pacing/releases/latest";
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+    pub html_url: String,
+}
+
+impl Release {
+    /// Is this release newer than the running version?
+    fn is_newer(&self) -> bool {
+        version(&self.tag_name) > version(env!("CARGO_PKG_VERSION"))
+    }
+
+    fn title(&self) -> &str {
+        self.name
+            .as_deref()
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or(&self.tag_name)
+    }
+}
+
+/// `v1.2.3` as `[1, 2, 3]`, ignoring anything after the numbers like `-beta`
+fn version(tag: &str) -> Vec<u64> {
+    tag.trim_start_matches('v')
+        .split(['.', '-', '+'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+fn fetch() -> Result<Release, String> {
+    let body = ureq::get(LATEST)
+        .set("User-Agent", concat!("pacing/", env!("CARGO_PKG_VERSION")))
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .map_err(|err| err.to_string())?
+        .into_string()
+        .map_err(|err| err.to_string())?;
+    serde_json::from_str(&body).map_err(|err| err.to_string())
+}
+
+pub struct UpdateCheck {
+    pending: Option<Receiver<Result<Release, String>>>,
+    release: Option<Release>,
+    show_notes: bool,
+    dismissed: bool,
+}
+
+impl UpdateCheck {
+    /// Check for a release in the background
+    pub fn start(ctx: &egui::Context) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(fetch());
+            ctx.request_repaint();
+        });
+
+        Self {
+            pending: Some(rx),
+            release: None,
+            show_notes: false,
+            dismissed: false,
+        }
+    }
+
+    pub fn display(&mut self, toasts: &mut Toasts, ctx: &egui::Context) {
+        if let Some(result) = self.pending.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.pending = None;
+            match result {
+                Ok(release) if release.is_newer() => self.release = Some(release),
+                Ok(..) => {}
+                // being offline isn't worth more than a passing mention
+                Err(err) => toasts.info(format!("Couldn't check for updates: {err}")),
+            }
+        }
+
+        let Some(release) = self.release.as_ref().filter(|_| !self.dismissed) else {
+            return;
+        };
+
+        TopBottomPanel::top("update_banner").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(format!("{} is available", release.title()))
+                        .color(Color32::LIGHT_GREEN),
+                );
+                if ui.small_button("Release notes").clicked() {
+                    self.show_notes = true
+                }
+                ui.hyperlink_to("Download", &release.html_url);
+                if ui.small_button("Dismiss").clicked() {
+                    self.dismissed = true
+                }
+            });
+        });
+
+        Window::new(release.title())
+            .id(egui::Id::new("release_notes"))
+            .open(&mut self.show_notes)
+            .collapsible(false)
+            .default_width(400.0)
+            .show(ctx, |ui| {
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    notes(release.body.as_deref().unwrap_or("No release notes."), ui)
+                });
+                ui.separator();
+                ui.hyperlink_to("Open the release page", &release.html_url);
+            });
+    }
+}
+
+/// Release notes are markdown, this shows the headings and lists and leaves the rest as text
+fn notes(body: &str, ui: &mut egui::Ui) {
+    for line in body.lines().map(str::trim_end) {
+        if let Some(heading) = line.strip_prefix('#') {
+            ui.strong(heading.trim_start_matches('#').trim());
+        } else if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            ui.label(format!("• {item}"));
+        } else if line.is_empty() {
+            ui.add_space(4.0);
+        } else {
+            ui.label(line);
+        }
+    }
+}
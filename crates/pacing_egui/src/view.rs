@@ -1,4 +1,25 @@
-use crate::mechanics::{Player, Simulation, StatsBuilder};
+use std::time::Duration;
+
+use crate::{
+    checkpoint::{Session, SessionSummary},
+    mechanics::{Player, Simulation, StatsBuilder, TitleCard},
+    runner::{Runner, Tick},
+};
+
+/// Seconds since the unix epoch
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now() -> Option<u64> {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|time| time.as_secs())
+}
+
+// SystemTime isn't available on the web
+#[cfg(target_arch = "wasm32")]
+pub fn now() -> Option<u64> {
+    None
+}
 
 #[derive(Default)]
 pub enum View {
@@ -9,20 +30,54 @@ pub enum View {
         active: usize,
         players: Vec<Player>,
     },
+    /// The retired heroes, from the profile
+    HallOfFame {
+        players: Vec<Player>,
+    },
     CharacterCreation {
         player: Player,
         stats_builder: StatsBuilder,
         players: Vec<Player>,
     },
     RunSimulation {
-        simulation: Simulation,
+        game: Runner<Game>,
+        /// The card being shown, and when it was first shown
+        title_card: Option<(TitleCard, f64)>,
+        session: Session,
         active: usize,
-        players: Vec<Player>,
     },
     #[default]
     Empty,
 }
 
+/// Everyone being played, ticked together by a [`Runner`] so they keep going between redraws
+pub struct Game {
+    pub simulation: Simulation,
+    /// Everyone else, who only make progress while the party is ticking in the background
+    pub party: Vec<Simulation>,
+    /// Shown beside the active hero in couch mode, and always playing along with them
+    pub partner: Option<Simulation>,
+    /// Stalls the party and partner were recovered from, waiting to be shown
+    pub diagnostics: Vec<String>,
+}
+
+impl Tick for Game {
+    fn tick(&mut self) {
+        self.simulation.tick();
+        for member in self.partner.iter_mut().chain(&mut self.party) {
+            member.tick();
+            // nothing shows their title cards, so don't let them pile up
+            member.take_title_card();
+            if let Some(diagnostic) = member.take_diagnostic() {
+                self.diagnostics.push(format!(
+                    "{}'s game stalled and was recovered: {diagnostic}",
+                    member.player.name
+                ))
+            }
+        }
+    }
+}
+
 impl View {
     pub const fn character_select(players: Vec<Player>) -> Self {
         Self::CharacterSelect { players }
@@ -32,6 +87,10 @@ impl View {
         Self::CharacterDetail { active, players }
     }
 
+    pub const fn hall_of_fame(players: Vec<Player>) -> Self {
+        Self::HallOfFame { players }
+    }
+
     pub const fn character_creation(
         player: Player,
         stats_builder: StatsBuilder,
@@ -45,25 +104,167 @@ impl View {
     }
 
     pub fn run_simulation(active: usize, mut players: Vec<Player>) -> Self {
-        let player = players.remove(active);
+        // keep questing through the time the app was closed
+        let mut caught_up = |player: Player| {
+            let mut simulation = Simulation::new(player);
+            if let (Some(last_seen), Some(now)) = (simulation.player.last_seen, now()) {
+                let away = Duration::from_secs(now.saturating_sub(last_seen));
+                simulation.catch_up(away);
+            }
+            simulation
+        };
+
+        let simulation = caught_up(players.remove(active));
+        let party = players.into_iter().map(caught_up).collect();
 
         Self::RunSimulation {
+            session: Session::start(&simulation.player),
             active,
-            players,
-            simulation: Simulation::new(player),
+            game: Runner::spawn(Game {
+                simulation,
+                party,
+                partner: None,
+                diagnostics: vec![],
+            }),
+            title_card: None,
         }
     }
 
-    pub fn players(&self) -> Option<(&[Player], Option<&Player>)> {
+    /// Couch mode: play `active` and `partner` side by side
+    pub fn run_together(active: usize, partner: usize, players: Vec<Player>) -> Self {
+        let mut view = Self::run_simulation(active, players);
+        if let Self::RunSimulation { game, .. } = &mut view {
+            let mut game = game.lock();
+            // the active hero was taken out from in front of them
+            let index = if partner > active {
+                partner - 1
+            } else {
+                partner
+            };
+            game.partner = Some(game.party.remove(index));
+        }
+        view
+    }
+
+    /// Ends the running session, if there is one, writing its summary to the player's journal
+    pub fn end_session(&mut self) -> Option<SessionSummary> {
         match self {
-            Self::CharacterSelect { players }
-            | Self::CharacterCreation { players, .. }
-            | Self::CharacterDetail { players, .. } => Some((players, None)),
-            Self::RunSimulation {
-                players,
+            Self::RunSimulation { game, session, .. } => {
+                Some(session.end(&mut game.lock().simulation.player))
+            }
+            _ => None,
+        }
+    }
+
+    /// Has anyone completed a task since they were last saved?
+    pub fn dirty(&self) -> bool {
+        match self {
+            Self::RunSimulation { game, .. } => {
+                let game = game.lock();
+                game.simulation.dirty()
+                    || game
+                        .party
+                        .iter()
+                        .chain(&game.partner)
+                        .any(Simulation::dirty)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn mark_saved(&mut self) {
+        if let Self::RunSimulation { game, .. } = self {
+            let mut game = game.lock();
+            let Game {
+                simulation,
+                party,
+                partner,
+                ..
+            } = &mut *game;
+            simulation.mark_saved();
+            party
+                .iter_mut()
+                .chain(partner)
+                .for_each(Simulation::mark_saved);
+        }
+    }
+
+    /// Whether the hero being played is paused, or `None` if nobody is being played
+    pub fn is_paused(&self) -> Option<bool> {
+        match self {
+            Self::RunSimulation { game, .. } => Some(game.lock().simulation.is_paused()),
+            _ => None,
+        }
+    }
+
+    /// Pause or resume the hero being played, along with their couch partner
+    pub fn set_paused(&mut self, paused: bool) {
+        if let Self::RunSimulation { game, .. } = self {
+            let mut game = game.lock();
+            let Game {
                 simulation,
+                partner,
                 ..
-            } => Some((players, Some(&simulation.player))),
+            } = &mut *game;
+            for simulation in std::iter::once(simulation).chain(partner) {
+                if paused {
+                    simulation.pause()
+                } else {
+                    simulation.resume()
+                }
+            }
+        }
+    }
+
+    /// Calls `f` with everyone not being played, along with the hero being played if there is one
+    pub fn with_players_mut<R>(
+        &mut self,
+        f: impl FnOnce(Vec<&mut Player>, Option<&mut Player>) -> R,
+    ) -> Option<R> {
+        match self {
+            Self::CharacterSelect { players }
+            | Self::CharacterCreation { players, .. }
+            | Self::CharacterDetail { players, .. }
+            | Self::HallOfFame { players } => Some(f(players.iter_mut().collect(), None)),
+            Self::RunSimulation { game, .. } => {
+                let mut game = game.lock();
+                let Game {
+                    simulation,
+                    party,
+                    partner,
+                    ..
+                } = &mut *game;
+                Some(f(
+                    party
+                        .iter_mut()
+                        .chain(partner)
+                        .map(|member| &mut member.player)
+                        .collect(),
+                    Some(&mut simulation.player),
+                ))
+            }
+            Self::Empty => None,
+        }
+    }
+
+    /// Calls `f` with everyone not being played, along with the hero being played if there is one
+    pub fn with_players<R>(&self, f: impl FnOnce(Vec<&Player>, Option<&Player>) -> R) -> Option<R> {
+        match self {
+            Self::CharacterSelect { players }
+            | Self::CharacterCreation { players, .. }
+            | Self::CharacterDetail { players, .. }
+            | Self::HallOfFame { players } => Some(f(players.iter().collect(), None)),
+            Self::RunSimulation { game, .. } => {
+                let game = game.lock();
+                Some(f(
+                    game.party
+                        .iter()
+                        .chain(&game.partner)
+                        .map(|member| &member.player)
+                        .collect(),
+                    Some(&game.simulation.player),
+                ))
+            }
             Self::Empty => None,
         }
     }
@@ -0,0 +1,108 @@
+#ifndef PACING_H
+#define PACING_H
+
+/* generated by cbindgen, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum PacingStatus {
+  PACING_STATUS_OK,
+  PACING_STATUS_NULL_ARGUMENT,
+  PACING_STATUS_INVALID_PATH,
+  PACING_STATUS_IO,
+  PACING_STATUS_SERIALIZE,
+  /**
+   * Too many injected tasks are already waiting
+   */
+  PACING_STATUS_QUEUE_FULL,
+  /**
+   * A number was infinite or not a number
+   */
+  PACING_STATUS_INVALID_ARGUMENT,
+} PacingStatus;
+
+/**
+ * An opaque handle to a running simulation
+ */
+typedef struct PacingSimulation PacingSimulation;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Create a new random character. Returns null if the character can't be created
+ */
+PacingSimulation *pacing_create(uint64_t seed);
+
+/**
+ * Load a character saved with [`pacing_save`]. Returns null if it can't be loaded
+ *
+ * # Safety
+ * `path` must be null or a valid nul-terminated string
+ */
+PacingSimulation *pacing_load(const char *path, uint64_t seed);
+
+/**
+ * Release a simulation. Passing null does nothing
+ *
+ * # Safety
+ * `simulation` must be null or a pointer from [`pacing_create`] or [`pacing_load`] that hasn't
+ * been destroyed yet
+ */
+void pacing_destroy(PacingSimulation *simulation);
+
+/**
+ * Advance the simulation by `dt` seconds of game time, which must be finite
+ *
+ * # Safety
+ * `simulation` must be null or a live pointer from [`pacing_create`] or [`pacing_load`]
+ */
+PacingStatus pacing_tick(PacingSimulation *simulation, float dt);
+
+/**
+ * A JSON snapshot of the character, which must be released with [`pacing_string_free`].
+ * Returns null on failure
+ *
+ * # Safety
+ * `simulation` must be null or a live pointer from [`pacing_create`] or [`pacing_load`]
+ */
+char *pacing_snapshot(const PacingSimulation *simulation);
+
+/**
+ * Save the character to `path`, so it can be loaded with [`pacing_load`]
+ *
+ * # Safety
+ * `simulation` must be null or a live pointer from [`pacing_create`] or [`pacing_load`], and
+ * `path` must be null or a valid nul-terminated string
+ */
+PacingStatus pacing_save(const PacingSimulation *simulation, const char *path);
+
+/**
+ * Queue up an event from outside the game, like "Reading a donation message from X", to play out
+ * after the current task. `source` says where it came from, like "chat", and can be null
+ *
+ * # Safety
+ * `simulation` must be null or a live pointer from [`pacing_create`] or [`pacing_load`], and
+ * `description` and `source` must be null or valid nul-terminated strings
+ */
+PacingStatus pacing_inject(PacingSimulation *simulation,
+                           const char *description,
+                           const char *source);
+
+/**
+ * Release a string returned from this library. Passing null does nothing
+ *
+ * # Safety
+ * `string` must be null or a pointer returned from this library that hasn't been freed yet
+ */
+void pacing_string_free(char *string);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* PACING_H */
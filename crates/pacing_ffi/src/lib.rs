@@ -0,0 +1,199 @@
+//! C bindings for the simulation, see `include/pacing.h`.
+//!
+//! Every simulation returned from here must be released with [`pacing_destroy`], and every string
+//! with [`pacing_string_free`]. Strings are JSON documents from [`pacing_core::embed`].
+
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    path::Path,
+};
+
+use pacing_core::{
+    config, embed,
+    lingo::generate_name,
+    mechanics::{ExternalEvent, Player, Priority, Simulation, StatsBuilder},
+    Rand, SliceExt,
+};
+
+/// An opaque handle to a running simulation
+pub struct PacingSimulation {
+    simulation: Simulation,
+}
+
+impl PacingSimulation {
+    fn into_raw(simulation: Simulation) -> *mut Self {
+        Box::into_raw(Box::new(Self { simulation }))
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PacingStatus {
+    Ok,
+    NullArgument,
+    InvalidPath,
+    Io,
+    Serialize,
+    /// Too many injected tasks are already waiting
+    QueueFull,
+    /// A number was infinite or not a number
+    InvalidArgument,
+}
+
+/// Create a new random character. Returns null if the character can't be created
+#[no_mangle]
+pub extern "C" fn pacing_create(seed: u64) -> *mut PacingSimulation {
+    let rng = Rand::seed(seed);
+    let content = config::content();
+    let player = Player::new(
+        generate_name(None, &rng),
+        content.races.choice(&rng).clone(),
+        content.classes.choice(&rng).clone(),
+        StatsBuilder::default().roll(&rng),
+    );
+    PacingSimulation::into_raw(Simulation::with_seed(player, seed))
+}
+
+/// Load a character saved with [`pacing_save`]. Returns null if it can't be loaded
+///
+/// # Safety
+/// `path` must be null or a valid nul-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn pacing_load(path: *const c_char, seed: u64) -> *mut PacingSimulation {
+    let Some(path) = to_path(path) else {
+        return std::ptr::null_mut();
+    };
+
+    match std::fs::read_to_string(path)
+        .ok()
+        .and_then(|data| embed::load(&data).ok())
+    {
+        Some(player) => PacingSimulation::into_raw(Simulation::with_seed(player, seed)),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Release a simulation. Passing null does nothing
+///
+/// # Safety
+/// `simulation` must be null or a pointer from [`pacing_create`] or [`pacing_load`] that hasn't
+/// been destroyed yet
+#[no_mangle]
+pub unsafe extern "C" fn pacing_destroy(simulation: *mut PacingSimulation) {
+    if !simulation.is_null() {
+        drop(Box::from_raw(simulation))
+    }
+}
+
+/// Advance the simulation by `dt` seconds of game time, which must be finite
+///
+/// # Safety
+/// `simulation` must be null or a live pointer from [`pacing_create`] or [`pacing_load`]
+#[no_mangle]
+pub unsafe extern "C" fn pacing_tick(simulation: *mut PacingSimulation, dt: f32) -> PacingStatus {
+    let Some(PacingSimulation { simulation }) = simulation.as_mut() else {
+        return PacingStatus::NullArgument;
+    };
+    if !dt.is_finite() {
+        return PacingStatus::InvalidArgument;
+    }
+    simulation.advance_by(dt);
+    PacingStatus::Ok
+}
+
+/// A JSON snapshot of the character, which must be released with [`pacing_string_free`].
+/// Returns null on failure
+///
+/// # Safety
+/// `simulation` must be null or a live pointer from [`pacing_create`] or [`pacing_load`]
+#[no_mangle]
+pub unsafe extern "C" fn pacing_snapshot(simulation: *const PacingSimulation) -> *mut c_char {
+    let Some(PacingSimulation { simulation }) = simulation.as_ref() else {
+        return std::ptr::null_mut();
+    };
+
+    serde_json::to_string(&embed::Snapshot::of(&simulation.player))
+        .ok()
+        .and_then(|json| CString::new(json).ok())
+        .map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// Save the character to `path`, so it can be loaded with [`pacing_load`]
+///
+/// # Safety
+/// `simulation` must be null or a live pointer from [`pacing_create`] or [`pacing_load`], and
+/// `path` must be null or a valid nul-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn pacing_save(
+    simulation: *const PacingSimulation,
+    path: *const c_char,
+) -> PacingStatus {
+    let Some(PacingSimulation { simulation }) = simulation.as_ref() else {
+        return PacingStatus::NullArgument;
+    };
+    if path.is_null() {
+        return PacingStatus::NullArgument;
+    }
+    let Some(path) = to_path(path) else {
+        return PacingStatus::InvalidPath;
+    };
+
+    let Ok(data) = embed::save(&simulation.player) else {
+        return PacingStatus::Serialize;
+    };
+
+    // write to a temporary file first so a failed save doesn't clobber the old one
+    let tmp = path.with_extension("tmp");
+    match std::fs::write(&tmp, data).and_then(|_| std::fs::rename(&tmp, path)) {
+        Ok(..) => PacingStatus::Ok,
+        Err(..) => PacingStatus::Io,
+    }
+}
+
+/// Queue up an event from outside the game, like "Reading a donation message from X", to play out
+/// after the current task. `source` says where it came from, like "chat", and can be null
+///
+/// # Safety
+/// `simulation` must be null or a live pointer from [`pacing_create`] or [`pacing_load`], and
+/// `description` and `source` must be null or valid nul-terminated strings
+#[no_mangle]
+pub unsafe extern "C" fn pacing_inject(
+    simulation: *mut PacingSimulation,
+    description: *const c_char,
+    source: *const c_char,
+) -> PacingStatus {
+    let Some(PacingSimulation { simulation }) = simulation.as_mut() else {
+        return PacingStatus::NullArgument;
+    };
+    if description.is_null() {
+        return PacingStatus::NullArgument;
+    }
+
+    let mut event = ExternalEvent::new(CStr::from_ptr(description).to_string_lossy());
+    if !source.is_null() {
+        event = event.source(CStr::from_ptr(source).to_string_lossy());
+    }
+    match simulation.inject_task(event.into_task(), Priority::Last) {
+        Ok(..) => PacingStatus::Ok,
+        Err(..) => PacingStatus::QueueFull,
+    }
+}
+
+/// Release a string returned from this library. Passing null does nothing
+///
+/// # Safety
+/// `string` must be null or a pointer returned from this library that hasn't been freed yet
+#[no_mangle]
+pub unsafe extern "C" fn pacing_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string))
+    }
+}
+
+unsafe fn to_path<'a>(path: *const c_char) -> Option<&'a Path> {
+    if path.is_null() {
+        return None;
+    }
+    CStr::from_ptr(path).to_str().ok().map(Path::new)
+}
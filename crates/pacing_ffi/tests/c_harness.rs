@@ -0,0 +1,32 @@
+#![cfg(unix)]
+
+use std::{path::PathBuf, process::Command};
+
+#[test]
+fn c_harness() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let tmp = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+
+    // the test binary is in target/<profile>/deps, the static library is in target/<profile>
+    let exe = std::env::current_exe().unwrap();
+    let lib = exe.ancestors().nth(2).unwrap().join("libpacing_ffi.a");
+    assert!(lib.exists(), "cannot find {}", lib.display());
+
+    let harness = tmp.join("pacing_harness");
+    let status = Command::new(std::env::var("CC").unwrap_or_else(|_| String::from("cc")))
+        .arg(root.join("tests/harness.c"))
+        .arg("-I")
+        .arg(root.join("include"))
+        .arg(&lib)
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&harness)
+        .status()
+        .expect("run the c compiler");
+    assert!(status.success(), "cannot compile the harness");
+
+    let status = Command::new(&harness)
+        .arg(tmp.join("pacing_harness.json"))
+        .status()
+        .unwrap();
+    assert!(status.success(), "the harness failed");
+}
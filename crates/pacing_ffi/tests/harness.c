@@ -0,0 +1,47 @@
+#include <assert.h>
+#include <math.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "pacing.h"
+
+int main(int argc, char **argv) {
+  assert(argc == 2);
+  const char *path = argv[1];
+
+  PacingSimulation *simulation = pacing_create(42);
+  assert(simulation != NULL);
+  assert(pacing_tick(simulation, 600.0f) == PACING_STATUS_OK);
+  assert(pacing_inject(simulation, "Reading a donation message from Bob", "donations") ==
+         PACING_STATUS_OK);
+  assert(pacing_inject(simulation, "Waving to the chat", NULL) == PACING_STATUS_OK);
+  assert(pacing_tick(simulation, 60.0f) == PACING_STATUS_OK);
+
+  char *snapshot = pacing_snapshot(simulation);
+  assert(snapshot != NULL);
+  assert(strstr(snapshot, "\"level\"") != NULL);
+  pacing_string_free(snapshot);
+
+  assert(pacing_save(simulation, path) == PACING_STATUS_OK);
+  pacing_destroy(simulation);
+
+  PacingSimulation *loaded = pacing_load(path, 7);
+  assert(loaded != NULL);
+  assert(pacing_tick(loaded, 1.0f) == PACING_STATUS_OK);
+  // these would never finish
+  assert(pacing_tick(loaded, INFINITY) == PACING_STATUS_INVALID_ARGUMENT);
+  assert(pacing_tick(loaded, NAN) == PACING_STATUS_INVALID_ARGUMENT);
+  pacing_destroy(loaded);
+
+  // null handles are rejected rather than dereferenced
+  assert(pacing_tick(NULL, 1.0f) == PACING_STATUS_NULL_ARGUMENT);
+  assert(pacing_save(NULL, path) == PACING_STATUS_NULL_ARGUMENT);
+  assert(pacing_inject(NULL, "", NULL) == PACING_STATUS_NULL_ARGUMENT);
+  assert(pacing_snapshot(NULL) == NULL);
+  assert(pacing_load(NULL, 0) == NULL);
+  pacing_destroy(NULL);
+  pacing_string_free(NULL);
+
+  puts("ok");
+  return 0;
+}
@@ -0,0 +1,352 @@
+use std::{path::PathBuf, time::Duration};
+
+use pacing_core::{
+    config::{GlyphStyle, Language, Rating},
+    format::SheetStyle,
+};
+
+use crate::{output::Filter, simulate::Format};
+
+const USAGE: &str = "\
+usage: pacing_headless [options]
+
+options:
+    --character PATH        load the character from PATH, and save it there on exit.
+                            saves ending in .json are json, anything else is ron
+    --generate              write a new character to --character (or NAME.ron), then exit
+    --diff OLD NEW          print what changed between two saves of a character, then exit
+    --badge PATH            write an svg badge for the character to PATH, then exit
+    --card-ansi             print a character card for the character, then exit
+    --stats                 print the character's play statistics, then exit
+    --export-jsonl PATH     write the character's journal to PATH as JSON Lines, then exit
+    --export-arena PATH     write the character's arena scores to PATH as JSON Lines, then exit
+    --export STYLE          print a character sheet: text, markdown or bbcode, then exit
+    --simulate N            play N characters from consecutive seeds as fast as possible, then
+                            print statistics on how far they got
+    --compare A B           play the --simulate characters (default 20) under both the tuning,
+                            rules and content in the ron files A and B, then print how the
+                            pacing compares, e.g. (tuning: (level_up_minutes: 15), rules: (),
+                            content: Some(\"pack.ron\"))
+    --hours H               how many hours of game time each --simulate character plays
+                            (default 24)
+    --format FORMAT         how --simulate and --compare print: csv or json
+    --until-level N         stop once the character reaches level N
+    --until-act N           stop once the character reaches act N
+    --max-game-time TIME    stop after TIME of game time has passed
+    --expect-max-hours H    fail unless --until-level or --until-act is reached within H hours
+                            of game time
+    --expect-min-level N    fail unless the character is at least level N when it stops
+    --history PATH          keep the character's full history in an sqlite database at PATH
+    --serve ADDR            answer http requests on ADDR (e.g. 127.0.0.1:8080) with the
+                            character's current state as json, or its badge at /badge.svg
+    -q, --quiet             only print periodic summaries
+    --only CATEGORIES       comma separated list of: tasks, kills, levels, quests, acts, journal,
+                            content (reloads from --watch-content), progress (each quarter
+                            of every task, only printed when listed)
+    --glyphs STYLE          mark tasks with a glyph for their kind: ascii or emoji
+    --summary-every TIME    print a summary every TIME of game time (e.g. 30s, 10m, 1h30m)
+    --no-sleep              run as fast as possible instead of waiting on task durations
+    --fixed-step TIME       advance by TIME of real time every step without waiting, for
+                            reproducible benchmarks
+    --compress              compact and gzip the save, compressed saves are detected on load
+    --seal-key PATH         seal saves with an hmac using the key in PATH, instead of a checksum.
+                            json saves keep their seal beside them, in a .seal file
+    --synthesize            make up new monsters and items in the later acts
+    --seed N                seed the randomness, so a run can be reproduced. a saved
+                            character keeps the seed it was first played with
+    --no-name-filter        don't reject generated names containing offensive words
+    --content PATH          use the races, classes, monsters, items, etc. from a ron file
+    --watch-content         reload the --content file whenever it changes
+    --tuning PATH           use the level times, prices, etc. from a ron file, they're saved
+                            with the character
+    --rating RATING         content rating for a new character: family or classic
+    --language LANGUAGE     grammar for generated text: english, german or french
+    -h, --help              print this message
+
+once a run stops, the last line printed is a json summary of how it went.
+
+exit codes:
+    0    the run finished and met every --expect
+    1    something couldn't be loaded, saved or served
+    2    the arguments were invalid, or the --tuning file doesn't make sense
+    3    an --expect wasn't met, or the run stopped before its --until-level or --until-act
+    4    interrupted before the --expect options could be checked
+";
+
+/// What a run should achieve, so scripts can check the pacing of a content pack
+#[derive(Default)]
+pub struct Expect {
+    pub max_hours: Option<f64>,
+    pub min_level: Option<usize>,
+}
+
+impl Expect {
+    pub const fn is_empty(&self) -> bool {
+        self.max_hours.is_none() && self.min_level.is_none()
+    }
+}
+
+#[derive(Default)]
+pub struct Args {
+    pub character: Option<PathBuf>,
+    pub generate: bool,
+    pub diff: Option<(PathBuf, PathBuf)>,
+    pub badge: Option<PathBuf>,
+    pub card_ansi: bool,
+    pub stats: bool,
+    pub export_jsonl: Option<PathBuf>,
+    pub export_arena: Option<PathBuf>,
+    pub export: Option<SheetStyle>,
+    pub simulate: Option<usize>,
+    pub compare: Option<(PathBuf, PathBuf)>,
+    pub hours: Option<f64>,
+    pub format: Format,
+    pub until_level: Option<usize>,
+    pub until_act: Option<i32>,
+    pub max_game_time: Option<Duration>,
+    pub expect: Expect,
+    pub filter: Filter,
+    pub glyphs: GlyphStyle,
+    pub summary_every: Option<Duration>,
+    #[cfg(feature = "sqlite")]
+    pub history: Option<PathBuf>,
+    pub serve: Option<String>,
+    pub no_sleep: bool,
+    pub fixed_step: Option<Duration>,
+    pub compress: bool,
+    pub seal_key: Option<PathBuf>,
+    pub synthesize: bool,
+    pub seed: Option<u64>,
+    pub no_name_filter: bool,
+    pub rating: Rating,
+    pub content: Option<PathBuf>,
+    #[cfg(feature = "hot-reload")]
+    pub watch_content: bool,
+    pub tuning: Option<PathBuf>,
+    pub language: Language,
+}
+
+impl Args {
+    pub fn parse() -> Result<Self, String> {
+        let mut this = Self::default();
+        let mut quiet = false;
+
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{arg} requires a value"));
+
+            match arg.as_str() {
+                "-q" | "--quiet" => quiet = true,
+                "--no-sleep" => this.no_sleep = true,
+                "--compress" => this.compress = true,
+                "--generate" => this.generate = true,
+                "--seal-key" => this.seal_key = Some(PathBuf::from(value()?)),
+                "--synthesize" => this.synthesize = true,
+                "--no-name-filter" => this.no_name_filter = true,
+                "--character" => this.character = Some(PathBuf::from(value()?)),
+                "--badge" => this.badge = Some(PathBuf::from(value()?)),
+                "--card-ansi" => this.card_ansi = true,
+                "--stats" => this.stats = true,
+                "--export-jsonl" => this.export_jsonl = Some(PathBuf::from(value()?)),
+                "--export-arena" => this.export_arena = Some(PathBuf::from(value()?)),
+                "--export" => {
+                    this.export = Some(match &*value()? {
+                        "text" => SheetStyle::PlainText,
+                        "markdown" => SheetStyle::Markdown,
+                        "bbcode" => SheetStyle::BbCode,
+                        style => return Err(format!("unknown sheet style: {style}")),
+                    })
+                }
+                "--diff" => {
+                    let old = PathBuf::from(value()?);
+                    this.diff = Some((old, PathBuf::from(value()?)))
+                }
+                "--compare" => {
+                    let a = PathBuf::from(value()?);
+                    this.compare = Some((a, PathBuf::from(value()?)))
+                }
+                "--seed" => {
+                    this.seed = Some(
+                        value()?
+                            .parse()
+                            .map_err(|_| String::from("--seed requires a number"))?,
+                    )
+                }
+                "--simulate" => {
+                    this.simulate = Some(
+                        value()?
+                            .parse()
+                            .map_err(|_| String::from("--simulate requires a number"))?,
+                    )
+                }
+                "--hours" => {
+                    this.hours = Some(
+                        value()?
+                            .parse()
+                            .ok()
+                            .filter(|hours: &f64| *hours > 0.0)
+                            .ok_or_else(|| String::from("--hours requires a positive number"))?,
+                    )
+                }
+                "--format" => {
+                    this.format = match &*value()? {
+                        "csv" => Format::Csv,
+                        "json" => Format::Json,
+                        format => return Err(format!("unknown format: {format}")),
+                    }
+                }
+                "--until-level" => {
+                    this.until_level = Some(
+                        value()?
+                            .parse()
+                            .map_err(|_| String::from("--until-level requires a number"))?,
+                    )
+                }
+                "--until-act" => {
+                    this.until_act = Some(
+                        value()?
+                            .parse()
+                            .map_err(|_| String::from("--until-act requires a number"))?,
+                    )
+                }
+                "--max-game-time" => this.max_game_time = Some(parse_duration(&value()?)?),
+                "--expect-max-hours" => {
+                    this.expect.max_hours = Some(
+                        value()?
+                            .parse()
+                            .ok()
+                            .filter(|hours: &f64| *hours > 0.0)
+                            .ok_or_else(|| {
+                                String::from("--expect-max-hours requires a positive number")
+                            })?,
+                    )
+                }
+                "--expect-min-level" => {
+                    this.expect.min_level = Some(
+                        value()?
+                            .parse()
+                            .map_err(|_| String::from("--expect-min-level requires a number"))?,
+                    )
+                }
+                "--only" => {
+                    this.filter = Filter::Only(
+                        value()?
+                            .split(',')
+                            .map(str::parse)
+                            .collect::<Result<_, _>>()?,
+                    )
+                }
+                "--serve" => this.serve = Some(value()?),
+                "--content" => this.content = Some(PathBuf::from(value()?)),
+                "--tuning" => this.tuning = Some(PathBuf::from(value()?)),
+                "--rating" => {
+                    this.rating = match &*value()? {
+                        "family" => Rating::Family,
+                        "classic" => Rating::Classic,
+                        rating => return Err(format!("unknown rating: {rating}")),
+                    }
+                }
+                "--glyphs" => {
+                    this.glyphs = match &*value()? {
+                        "ascii" => GlyphStyle::Ascii,
+                        "emoji" => GlyphStyle::Emoji,
+                        style => return Err(format!("unknown glyph style: {style}")),
+                    }
+                }
+                "--language" => {
+                    this.language = match &*value()? {
+                        "english" => Language::English,
+                        "german" => Language::German,
+                        "french" => Language::French,
+                        language => return Err(format!("unknown language: {language}")),
+                    }
+                }
+                "--fixed-step" => this.fixed_step = Some(parse_duration(&value()?)?),
+                "--summary-every" => this.summary_every = Some(parse_duration(&value()?)?),
+                #[cfg(feature = "hot-reload")]
+                "--watch-content" => this.watch_content = true,
+                #[cfg(not(feature = "hot-reload"))]
+                "--watch-content" => {
+                    return Err(String::from(
+                        "--watch-content requires pacing_headless to be built with the hot-reload feature",
+                    ))
+                }
+                #[cfg(feature = "sqlite")]
+                "--history" => this.history = Some(PathBuf::from(value()?)),
+                #[cfg(not(feature = "sqlite"))]
+                "--history" => {
+                    return Err(String::from(
+                        "--history requires pacing_headless to be built with the sqlite feature",
+                    ))
+                }
+                "-h" | "--help" => {
+                    print!("{USAGE}");
+                    std::process::exit(0)
+                }
+                _ => return Err(format!("unknown argument: {arg}\n\n{USAGE}")),
+            }
+        }
+
+        if this.hours.is_some() && this.simulate.is_none() && this.compare.is_none() {
+            return Err(String::from("--hours requires --simulate or --compare"));
+        }
+
+        if !this.expect.is_empty() && (this.simulate.is_some() || this.compare.is_some()) {
+            return Err(String::from(
+                "--expect-max-hours and --expect-min-level can't be used with --simulate or \
+                 --compare",
+            ));
+        }
+
+        if this.expect.max_hours.is_some() && this.until_level.is_none() && this.until_act.is_none()
+        {
+            return Err(String::from(
+                "--expect-max-hours requires --until-level or --until-act",
+            ));
+        }
+
+        if quiet {
+            this.filter = Filter::Nothing
+        }
+
+        Ok(this)
+    }
+}
+
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let mut total = 0;
+    let mut digits = String::new();
+
+    for ch in input.chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+
+        let scale = match ch {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 60 * 60 * 24,
+            _ => return Err(format!("invalid duration: {input}")),
+        };
+
+        let n: u64 = std::mem::take(&mut digits)
+            .parse()
+            .map_err(|_| format!("invalid duration: {input}"))?;
+        total += n * scale;
+    }
+
+    // a bare number is in seconds
+    if !digits.is_empty() {
+        total += digits
+            .parse::<u64>()
+            .map_err(|_| format!("invalid duration: {input}"))?;
+    }
+
+    if total == 0 {
+        return Err(format!("invalid duration: {input}"));
+    }
+
+    Ok(Duration::from_secs(total))
+}
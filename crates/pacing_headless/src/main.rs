@@ -0,0 +1,473 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use pacing_core::{
+    badge, card,
+    checkpoint::{Checkpoint, Session},
+    config::{self, ContentPack, Tuning},
+    export,
+    format::CharacterSheet,
+    lingo::{act_name, generate_name, set_language, set_name_filter},
+    mechanics::{Player, Simulation, StatsBuilder, TickPolicy},
+    rules::RuleSet,
+    Rand, SliceExt,
+};
+
+mod args;
+use args::Args;
+
+mod output;
+use output::Output;
+
+mod save;
+
+mod serve;
+use serve::Server;
+
+mod simulate;
+
+/// What the process exits with, documented in the usage
+#[derive(Copy, Clone)]
+enum Exit {
+    Success = 0,
+    Failure = 1,
+    Usage = 2,
+    Unmet = 3,
+    Interrupted = 4,
+}
+
+impl Exit {
+    fn exit(self) -> ! {
+        std::process::exit(self as i32)
+    }
+}
+
+enum Stop {
+    Interrupted,
+    Level(usize),
+    Act(i32),
+    GameTime(Duration),
+    /// `--expect-max-hours` ran out before the run reached its target
+    Deadline(f64),
+}
+
+impl Stop {
+    const fn kind(&self) -> &'static str {
+        match self {
+            Self::Interrupted => "interrupted",
+            Self::Level(..) => "level",
+            Self::Act(..) => "act",
+            Self::GameTime(..) => "game_time",
+            Self::Deadline(..) => "deadline",
+        }
+    }
+}
+
+impl std::fmt::Display for Stop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Interrupted => f.write_str("interrupted"),
+            Self::Level(level) => write!(f, "reached level {level}"),
+            Self::Act(act) => write!(f, "reached {}", act_name(*act)),
+            Self::GameTime(time) => write!(f, "ran for {}s of game time", time.as_secs()),
+            Self::Deadline(hours) => write!(f, "ran out of time after {hours} hours"),
+        }
+    }
+}
+
+/// The last line printed, for scripts
+#[derive(serde::Serialize)]
+struct Outcome<'a> {
+    stop: &'static str,
+    seed: u64,
+    name: &'a str,
+    level: usize,
+    act: i32,
+    hours: f64,
+    /// The `--expect` options that weren't met
+    unmet: Vec<String>,
+    exit: i32,
+}
+
+fn main() {
+    let args = match Args::parse() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}");
+            Exit::Usage.exit()
+        }
+    };
+
+    let key = match args.seal_key.as_ref().map(std::fs::read).transpose() {
+        Ok(key) => key,
+        Err(err) => {
+            eprintln!("cannot read the seal key: {err}");
+            Exit::Failure.exit()
+        }
+    };
+    let key = key.as_deref();
+
+    if let Some((old, new)) = &args.diff {
+        match save::load(old, key).and_then(|old| Ok((old, save::load(new, key)?))) {
+            Ok((old, new)) => print!("{}", Checkpoint::of(&new).diff(&Checkpoint::of(&old))),
+            Err(err) => {
+                eprintln!("{err}");
+                Exit::Failure.exit()
+            }
+        }
+        return;
+    }
+
+    if args.badge.is_some()
+        || args.card_ansi
+        || args.stats
+        || args.export_jsonl.is_some()
+        || args.export_arena.is_some()
+        || args.export.is_some()
+    {
+        let result = match &args.character {
+            Some(path) => save::load(path, key),
+            None => Err(String::from(
+                "--badge, --card-ansi, --stats and the --export flags require --character",
+            )),
+        }
+        .and_then(|player| {
+            if args.card_ansi {
+                print!("{}", card::ansi(&player));
+            }
+            if args.stats {
+                for (label, value) in player.statistics.table(player.elapsed) {
+                    println!("{label:<20}{value}");
+                }
+            }
+            if let Some(style) = args.export {
+                print!("{}", CharacterSheet::new(&player, style));
+            }
+            if let Some(path) = &args.export_jsonl {
+                std::fs::File::create(path)
+                    .and_then(|file| export::jsonl(&player, std::io::BufWriter::new(file)))
+                    .map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+            }
+            if let Some(path) = &args.export_arena {
+                std::fs::File::create(path)
+                    .and_then(|file| export::arena_scores(&player, std::io::BufWriter::new(file)))
+                    .map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+            }
+            match &args.badge {
+                Some(badge) => std::fs::write(badge, badge::render(&player))
+                    .map_err(|err| format!("cannot write {}: {err}", badge.display())),
+                None => Ok(()),
+            }
+        });
+        if let Err(err) = result {
+            eprintln!("{err}");
+            Exit::Failure.exit()
+        }
+        return;
+    }
+
+    if let Some(path) = &args.content {
+        match std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|data| ron::from_str::<ContentPack>(&data).map_err(|err| err.to_string()))
+        {
+            Ok(pack) => config::set_content(pack),
+            Err(err) => {
+                eprintln!("cannot load content from {}: {err}", path.display());
+                Exit::Failure.exit()
+            }
+        }
+    }
+
+    // a loaded character keeps its own tuning unless it's replaced
+    let tuning = args.tuning.as_ref().map(|path| {
+        match std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|data| ron::from_str::<Tuning>(&data).map_err(|err| err.to_string()))
+        {
+            Ok(tuning) => {
+                if let Err(err) = tuning.validate() {
+                    eprintln!("invalid tuning in {}: {err}", path.display());
+                    Exit::Usage.exit()
+                }
+                tuning
+            }
+            Err(err) => {
+                eprintln!("cannot load tuning from {}: {err}", path.display());
+                Exit::Failure.exit()
+            }
+        }
+    });
+
+    let seed = args.seed.unwrap_or_else(Rand::random_seed);
+    let rng = Rand::seed(seed);
+    set_name_filter(!args.no_name_filter);
+    set_language(args.language);
+
+    if args.simulate.is_some() || args.compare.is_some() {
+        let batch = simulate::Batch {
+            runs: args.simulate.unwrap_or(20),
+            game_time: Duration::from_secs_f64(args.hours.unwrap_or(24.0) * 60.0 * 60.0),
+            seed,
+            rating: args.rating,
+            synthesize: args.synthesize,
+            tuning: tuning.clone().unwrap_or_default(),
+            rules: RuleSet::default(),
+            content: None,
+        };
+        let report = match &args.compare {
+            Some((a, b)) => {
+                let load = |path: &std::path::Path| match simulate::Variant::load(path) {
+                    Ok(variant) => variant,
+                    Err(err) => {
+                        eprintln!("cannot load a variant from {}: {err}", path.display());
+                        Exit::Failure.exit()
+                    }
+                };
+                simulate::Comparison::run(&batch, &load(a), &load(b)).render(args.format)
+            }
+            None => batch.run().render(args.format),
+        };
+        match report {
+            Ok(report) => print!("{report}"),
+            Err(err) => {
+                eprintln!("cannot write the statistics: {err}");
+                Exit::Failure.exit()
+            }
+        }
+        return;
+    }
+
+    let roll = || {
+        let content = config::content();
+        let mut player = Player::new(
+            generate_name(None, &rng),
+            content.races.choice(&rng).clone(),
+            content.classes.choice(&rng).clone(),
+            StatsBuilder::default().roll(&rng),
+        );
+        player.rating = args.rating;
+        player
+    };
+
+    if args.generate {
+        let player = roll();
+        let path = args
+            .character
+            .clone()
+            .unwrap_or_else(|| save::default_path(&player));
+        if path.exists() {
+            eprintln!("{} already exists", path.display());
+            Exit::Failure.exit()
+        }
+        if let Err(err) = save::save(&path, &player, args.compress, key) {
+            eprintln!("{err}");
+            Exit::Failure.exit()
+        }
+        println!("{}", output::summary(&player));
+        println!(
+            "generated {name} at {path}, run with: pacing_headless --character {path}",
+            name = player.name,
+            path = path.display()
+        );
+        return;
+    }
+
+    let player = match &args.character {
+        Some(path) if path.exists() => match save::load(path, key) {
+            Ok(player) => player,
+            Err(err) => {
+                eprintln!("{err}");
+                Exit::Failure.exit()
+            }
+        },
+        _ => roll(),
+    };
+
+    let interrupted = Arc::new(AtomicBool::new(false));
+    ctrlc::set_handler({
+        let interrupted = interrupted.clone();
+        move || interrupted.store(true, Ordering::SeqCst)
+    })
+    .expect("install signal handler");
+
+    let mut simulation = Simulation::with_seed(player, seed);
+    simulation.set_synthesis(args.synthesize);
+    if let Some(tuning) = tuning {
+        simulation.set_tuning(tuning);
+    }
+    let policy = match args.fixed_step {
+        Some(step) => TickPolicy::FixedStep(step),
+        None if args.no_sleep => TickPolicy::Turbo,
+        None => TickPolicy::RealTime,
+    };
+    simulation.set_tick_policy(policy);
+    output::follow_progress(&args.filter, &mut simulation);
+    let mut output = Output::new(args.filter, args.glyphs, args.summary_every);
+
+    let mut server = args.serve.as_deref().map(|addr| match Server::start(addr) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("cannot serve on {addr}: {err}");
+            Exit::Failure.exit()
+        }
+    });
+
+    #[cfg(feature = "sqlite")]
+    let mut history =
+        args.history.as_ref().map(
+            |path| match pacing_core::history::SqliteHistory::open(path) {
+                Ok(store) => (
+                    pacing_core::history::Recorder::new(&simulation.player),
+                    store,
+                ),
+                Err(err) => {
+                    eprintln!("cannot open {}: {err}", path.display());
+                    Exit::Failure.exit()
+                }
+            },
+        );
+
+    #[cfg(feature = "hot-reload")]
+    let mut watcher = args
+        .content
+        .as_ref()
+        .filter(|_| args.watch_content)
+        .map(|path| {
+            let mut watcher = pacing_core::reload::ContentWatcher::new(path);
+            // the pack was just loaded, so this only remembers when the file last changed
+            let _ = watcher.poll();
+            watcher
+        });
+
+    let start = simulation.player.elapsed;
+    let session = Session::start(&simulation.player);
+
+    let stop = loop {
+        if let TickPolicy::RealTime = policy {
+            // sleep in small steps so an interrupt doesn't wait for a long task
+            let remaining = simulation.player.task_bar.remaining().max(0.0);
+            std::thread::sleep(Duration::from_secs_f32(
+                (remaining / simulation.speed()).min(0.25),
+            ));
+        }
+        simulation.tick();
+
+        output.observe(&simulation.player);
+        if let Some(server) = &mut server {
+            server.publish(&simulation.player);
+        }
+        if let Some(diagnostic) = simulation.take_diagnostic() {
+            eprintln!("simulation stalled and was recovered: {diagnostic}")
+        }
+
+        #[cfg(feature = "hot-reload")]
+        if let Some(result) = watcher.as_mut().and_then(|watcher| watcher.poll()) {
+            output.reloaded(&simulation.player, result);
+        }
+
+        #[cfg(feature = "sqlite")]
+        if let Some((recorder, store)) = &mut history {
+            if let Err(err) = recorder.observe(&simulation.player, store) {
+                eprintln!("cannot record history: {err}");
+            }
+        }
+
+        if interrupted.load(Ordering::SeqCst) {
+            break Stop::Interrupted;
+        }
+
+        if let Some(level) = args.until_level {
+            if simulation.player.level >= level {
+                break Stop::Level(level);
+            }
+        }
+
+        if let Some(act) = args.until_act {
+            if simulation.player.quest_book.act() >= act {
+                break Stop::Act(act);
+            }
+        }
+
+        if let Some(time) = args.max_game_time {
+            if simulation.player.elapsed - start >= time.as_secs_f32() {
+                break Stop::GameTime(time);
+            }
+        }
+
+        if let Some(hours) = args.expect.max_hours {
+            if f64::from(simulation.player.elapsed - start) >= hours * 60.0 * 60.0 {
+                break Stop::Deadline(hours);
+            }
+        }
+    };
+
+    println!("stopped: {stop} (seed: {})", simulation.seed());
+    println!("{}", output::summary(&simulation.player));
+    print!("{}", session.end(&mut simulation.player));
+
+    let path = args
+        .character
+        .unwrap_or_else(|| save::default_path(&simulation.player));
+
+    if let Err(err) = save::save(&path, &simulation.player, args.compress, key) {
+        eprintln!("{err}");
+        Exit::Failure.exit()
+    }
+
+    println!(
+        "saved {name} to {path}, resume with: pacing_headless --character {path}",
+        name = simulation.player.name,
+        path = path.display()
+    );
+
+    let player = &simulation.player;
+    let mut unmet = vec![];
+    match stop {
+        Stop::Deadline(hours) => unmet.push(format!("expected to stop within {hours} hours")),
+        Stop::Interrupted => {}
+        // another --until or --max-game-time stopped the run first
+        _ => {
+            if let Some(level) = args.until_level.filter(|&level| player.level < level) {
+                unmet.push(format!("stopped before level {level}, at {}", player.level));
+            }
+            let act = player.quest_book.act();
+            if let Some(until) = args.until_act.filter(|&until| act < until) {
+                unmet.push(format!(
+                    "stopped before {}, in {}",
+                    act_name(until),
+                    act_name(act)
+                ));
+            }
+        }
+    }
+    if let Some(level) = args.expect.min_level.filter(|&level| player.level < level) {
+        unmet.push(format!("expected level {level}, reached {}", player.level));
+    }
+    let exit = match stop {
+        Stop::Interrupted if !args.expect.is_empty() => Exit::Interrupted,
+        _ if !unmet.is_empty() => Exit::Unmet,
+        _ => Exit::Success,
+    };
+
+    let outcome = Outcome {
+        stop: stop.kind(),
+        seed: simulation.seed(),
+        name: &player.name,
+        level: player.level,
+        act: player.quest_book.act(),
+        hours: f64::from(player.elapsed - start) / 60.0 / 60.0,
+        unmet,
+        exit: exit as i32,
+    };
+    match serde_json::to_string(&outcome) {
+        Ok(json) => println!("{json}"),
+        Err(err) => eprintln!("cannot serialize the summary: {err}"),
+    }
+    exit.exit()
+}
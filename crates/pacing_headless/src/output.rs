@@ -0,0 +1,259 @@
+use std::{str::FromStr, time::Duration};
+
+use pacing_core::{
+    config::GlyphStyle,
+    format::Time,
+    integrity,
+    lingo::act_name,
+    mechanics::{Player, Simulation, TaskKind},
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Category {
+    Tasks,
+    Kills,
+    Levels,
+    Quests,
+    Acts,
+    Journal,
+    /// Reloads of the content pack being watched
+    Content,
+    /// Each quarter of every task, only printed when asked for by name
+    Progress,
+}
+
+impl FromStr for Category {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim() {
+            "tasks" | "task" => Self::Tasks,
+            "kills" | "kill" => Self::Kills,
+            "levels" | "level" => Self::Levels,
+            "quests" | "quest" => Self::Quests,
+            "acts" | "act" => Self::Acts,
+            "journal" => Self::Journal,
+            "content" => Self::Content,
+            "progress" => Self::Progress,
+            s => return Err(format!("unknown category: {s}")),
+        })
+    }
+}
+
+#[derive(Default)]
+pub enum Filter {
+    #[default]
+    All,
+    Only(Vec<Category>),
+    Nothing,
+}
+
+impl Filter {
+    fn allows(&self, category: Category) -> bool {
+        match self {
+            Self::All => category != Category::Progress,
+            Self::Only(list) => list.contains(&category),
+            Self::Nothing => false,
+        }
+    }
+}
+
+/// What the player looked like the last time we checked
+#[derive(Default)]
+struct Seen {
+    task: Option<String>,
+    level: usize,
+    act: i32,
+    quests: usize,
+    journal: usize,
+}
+
+/// Print a line as the current task reaches each quarter, if `filter` asks for them
+pub fn follow_progress(filter: &Filter, simulation: &mut Simulation) {
+    if filter.allows(Category::Progress) {
+        simulation.subscribe(|progress| {
+            println!(
+                "[{}] {}% {}",
+                Time::clock(progress.elapsed),
+                progress.percent,
+                progress.task
+            )
+        });
+    }
+}
+
+pub struct Output {
+    filter: Filter,
+    glyphs: GlyphStyle,
+    summary_every: Option<f32>,
+    next_summary: f32,
+    seen: Seen,
+}
+
+impl Output {
+    pub fn new(filter: Filter, glyphs: GlyphStyle, summary_every: Option<Duration>) -> Self {
+        let summary_every = summary_every.map(|d| d.as_secs_f32());
+        Self {
+            filter,
+            glyphs,
+            summary_every,
+            next_summary: summary_every.unwrap_or_default(),
+            seen: Seen::default(),
+        }
+    }
+
+    pub fn observe(&mut self, player: &Player) {
+        for (category, message) in self.changes(player) {
+            Self::emit(&self.filter, player, category, &message);
+        }
+
+        if let Some(every) = self.summary_every {
+            if player.elapsed >= self.next_summary {
+                self.next_summary = player.elapsed + every;
+                println!("{}", summary(player));
+            }
+        }
+    }
+
+    /// Everything that happened since the last look at `player`, before it's filtered
+    fn changes(&mut self, player: &Player) -> Vec<(Category, String)> {
+        let Seen {
+            task,
+            level,
+            act,
+            quests,
+            journal,
+        } = &mut self.seen;
+        let mut changes = vec![];
+
+        if let Some(current) = &player.task {
+            if task.as_deref() != Some(&*current.description) {
+                let category = match current.kind {
+                    TaskKind::Kill { .. } | TaskKind::Gauntlet { .. } => Category::Kills,
+                    _ => Category::Tasks,
+                };
+                changes.push((category, current.decorated(self.glyphs).into_owned()));
+                task.replace(current.description.to_string());
+            }
+        }
+
+        if player.level != *level {
+            *level = player.level;
+            changes.push((Category::Levels, format!("Reached level {level}")));
+        }
+
+        if player.quest_book.act() != *act {
+            *act = player.quest_book.act();
+            changes.push((Category::Acts, format!("Began {}", act_name(*act))));
+        }
+
+        // the book is trimmed on save, so only the total keeps counting up
+        let count = player.quest_book.total();
+        if count != *quests {
+            *quests = count;
+            if let Some(quest) = player.quest_book.current_quest() {
+                changes.push((Category::Quests, format!("New quest: {quest}")));
+            }
+        }
+
+        for entry in player.journal.since(*journal) {
+            changes.push((Category::Journal, entry.event.to_string()));
+        }
+        *journal = player.journal.recorded();
+
+        changes
+    }
+
+    /// Report a reload of the content pack being watched, or why it couldn't be reloaded
+    #[cfg(feature = "hot-reload")]
+    pub fn reloaded(&self, player: &Player, result: Result<(), String>) {
+        let message = match result {
+            Ok(()) => String::from("Reloaded the content"),
+            Err(err) => err,
+        };
+        Self::emit(&self.filter, player, Category::Content, &message);
+    }
+
+    fn emit(filter: &Filter, player: &Player, category: Category, message: &str) {
+        if filter.allows(category) {
+            println!("[{}] {message}", Time::clock(player.elapsed));
+        }
+    }
+}
+
+pub fn summary(player: &Player) -> String {
+    let summary = format!(
+        "[{elapsed}] {name} the {race} {class}: level {level}, {act}, power {power}, {gold} gold, {quests} quests, {items} items",
+        elapsed = Time::clock(player.elapsed),
+        name = player.name,
+        race = player.race.name,
+        class = player.class.name,
+        level = player.level,
+        act = act_name(player.quest_book.act()),
+        power = player.power_level(),
+        gold = player.inventory.gold(),
+        quests = player.quest_book.completed_quests().len(),
+        items = player.inventory.len(),
+    );
+    if player.tampered {
+        return format!("{summary} ({})", integrity::TAMPERED.to_lowercase());
+    }
+    summary
+}
+
+#[test]
+fn filter() {
+    let categories = ["tasks", " quest ", "progress"].map(|s| s.parse::<Category>().unwrap());
+    assert_eq!(
+        categories,
+        [Category::Tasks, Category::Quests, Category::Progress]
+    );
+    assert!("everything".parse::<Category>().is_err());
+
+    assert!(Filter::All.allows(Category::Kills));
+    // progress is too noisy to print unless it's asked for
+    assert!(!Filter::All.allows(Category::Progress));
+
+    let only = Filter::Only(categories.to_vec());
+    assert!(only.allows(Category::Quests) && only.allows(Category::Progress));
+    assert!(!only.allows(Category::Kills));
+
+    assert!(!Filter::Nothing.allows(Category::Tasks));
+}
+
+#[test]
+fn new_quests() {
+    use pacing_core::{config, mechanics::StatsBuilder, Rand};
+
+    let quests = |output: &mut Output, player: &Player| {
+        output
+            .changes(player)
+            .into_iter()
+            .filter(|(category, _)| *category == Category::Quests)
+            .map(|(_, message)| message)
+            .collect::<Vec<_>>()
+    };
+
+    let mut player = Player::new(
+        "Gorbash",
+        config::RACES[0].clone(),
+        config::CLASSES[0].clone(),
+        StatsBuilder::default().roll(&Rand::seed(42)),
+    );
+    let mut output = Output::new(Filter::All, GlyphStyle::Off, None);
+
+    // a full book stays the same size as quests are added
+    for n in 0..100 {
+        player
+            .quest_book
+            .add_quest(&format!("Quest {n}"), None, 0.0);
+    }
+    assert_eq!(quests(&mut output, &player), ["New quest: Quest 99"]);
+    player.quest_book.add_quest("Quest 100", None, 0.0);
+    assert_eq!(quests(&mut output, &player), ["New quest: Quest 100"]);
+    assert!(quests(&mut output, &player).is_empty());
+
+    // and a trimmed book isn't a new quest
+    player.quest_book.truncate(1);
+    assert!(quests(&mut output, &player).is_empty());
+}
@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+
+use pacing_core::{compress, integrity, mechanics::Player};
+
+pub fn default_path(player: &Player) -> PathBuf {
+    let name = player.name.trim().to_lowercase().replace(' ', "_");
+    PathBuf::from(format!("{name}.ron"))
+}
+
+/// Saves ending in `.json` are JSON, anything else is RON
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+/// JSON has no comments to put a seal in, so it's kept beside the save, e.g. `hero.json.seal`
+fn seal_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".seal");
+    PathBuf::from(path)
+}
+
+/// Load the player from `path`, marking them as tampered with if the save's seal doesn't match
+pub fn load(path: &Path, key: Option<&[u8]>) -> Result<Player, String> {
+    let data = std::fs::read(path)
+        .and_then(|data| Ok(compress::decompress(&data)?.into_owned()))
+        .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+    let data = std::str::from_utf8(&data)
+        .map_err(|err| format!("cannot load {}: {err}", path.display()))?;
+    let (data, verification) = if is_json(path) {
+        let verification = match std::fs::read_to_string(seal_path(path)) {
+            Ok(signature) => integrity::check(data, &signature, key),
+            Err(_) => integrity::Verification::Unsealed,
+        };
+        (data, verification)
+    } else {
+        integrity::verify(data, key)
+    };
+    let mut player: Player = if is_json(path) {
+        serde_json::from_str(data).map_err(|err| err.to_string())
+    } else {
+        ron::from_str(data).map_err(|err| err.to_string())
+    }
+    .map_err(|err| format!("cannot load {}: {err}", path.display()))?;
+    player.tampered |= verification.is_tampered();
+    Ok(player)
+}
+
+/// Save the player to `path`. Compressed saves are written from a compacted copy, and can still be loaded with [`load`]
+pub fn save(
+    path: &Path,
+    player: &Player,
+    compressed: bool,
+    key: Option<&[u8]>,
+) -> Result<(), String> {
+    let compacted;
+    let player = if compressed {
+        let mut copy = player.clone();
+        copy.compact();
+        compacted = copy;
+        &compacted
+    } else {
+        player
+    };
+
+    let data = match (is_json(path), compressed) {
+        (true, true) => serde_json::to_string(player).map_err(|err| err.to_string()),
+        (true, false) => serde_json::to_string_pretty(player).map_err(|err| err.to_string()),
+        (false, true) => ron::ser::to_string(player).map_err(|err| err.to_string()),
+        (false, false) => ron::ser::to_string_pretty(player, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string()),
+    }
+    .map_err(|err| format!("cannot serialize {}: {err}", player.name))?;
+
+    let data = if is_json(path) {
+        let seal = seal_path(path);
+        std::fs::write(&seal, integrity::signature(&data, key) + "\n")
+            .map_err(|err| format!("cannot write {}: {err}", seal.display()))?;
+        data.into_bytes()
+    } else {
+        integrity::seal(&data, key).into_bytes()
+    };
+    let data = if compressed {
+        compress::compress(&data)
+            .map_err(|err| format!("cannot compress {}: {err}", player.name))?
+    } else {
+        data
+    };
+
+    // write to a temporary file first so an interrupted save doesn't clobber the old one
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, data)
+        .and_then(|_| std::fs::rename(&tmp, path))
+        .map_err(|err| format!("cannot write {}: {err}", path.display()))
+}
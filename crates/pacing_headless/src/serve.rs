@@ -0,0 +1,173 @@
+//! A tiny HTTP server for watching a run from a browser or a dashboard script.
+//!
+//! `GET /` or `GET /status` answers with the character's current state as JSON, and
+//! `GET /badge.svg` with the character's badge. The simulation loop publishes fresh copies every so
+//! often, so requests never wait on the simulation.
+
+use std::{
+    io::{self, BufRead as _, BufReader, Write as _},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use pacing_core::{
+    badge,
+    mechanics::{Bar, Player, Quest},
+};
+
+#[derive(serde::Serialize)]
+struct Item<'a> {
+    name: &'a str,
+    quantity: usize,
+}
+
+#[derive(serde::Serialize)]
+struct Status<'a> {
+    name: &'a str,
+    race: &'a str,
+    class: &'a str,
+    level: usize,
+    power_level: usize,
+    act: i32,
+    elapsed: f32,
+    task: Option<&'a str>,
+    task_bar: Bar,
+    exp_bar: Bar,
+    gold: isize,
+    bank: isize,
+    inventory: Vec<Item<'a>>,
+    quests: Vec<&'a Quest>,
+}
+
+impl<'a> Status<'a> {
+    fn of(player: &'a Player) -> Self {
+        Self {
+            name: &player.name,
+            race: &player.race.name,
+            class: &player.class.name,
+            level: player.level,
+            power_level: player.power_level(),
+            act: player.quest_book.act(),
+            elapsed: player.elapsed,
+            task: player.task.as_ref().map(|task| &*task.description),
+            task_bar: player.task_bar,
+            exp_bar: player.exp_bar,
+            gold: player.inventory.gold(),
+            bank: player.bank.balance(),
+            inventory: player
+                .inventory
+                .items()
+                .map(|(name, quantity)| Item {
+                    name,
+                    quantity: *quantity,
+                })
+                .collect(),
+            quests: player.quest_book.quests().collect(),
+        }
+    }
+}
+
+/// What requests are answered with, as of the last publish
+struct Published {
+    status: String,
+    badge: String,
+}
+
+pub struct Server {
+    published: Arc<Mutex<Published>>,
+    at: Option<Instant>,
+}
+
+impl Server {
+    /// Publish at most this often, so fast runs don't spend their time serializing
+    const INTERVAL: Duration = Duration::from_millis(250);
+
+    /// Listen on `addr`, answering requests on a background thread
+    pub fn start(addr: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let published = Arc::new(Mutex::new(Published {
+            status: String::from("{}"),
+            badge: String::new(),
+        }));
+
+        std::thread::spawn({
+            let published = published.clone();
+            move || {
+                // each on its own thread, so a slow client doesn't hold up the rest
+                for stream in listener.incoming().flatten() {
+                    let published = published.clone();
+                    std::thread::spawn(move || {
+                        if let Err(err) = respond(stream, &published) {
+                            eprintln!("cannot answer a status request: {err}")
+                        }
+                    });
+                }
+            }
+        });
+
+        Ok(Self {
+            published,
+            at: None,
+        })
+    }
+
+    pub fn publish(&mut self, player: &Player) {
+        if self.at.is_some_and(|at| at.elapsed() < Self::INTERVAL) {
+            return;
+        }
+        self.at = Some(Instant::now());
+
+        let badge = badge::render(player);
+        let mut published = self.published.lock().unwrap();
+        published.badge = badge;
+        match serde_json::to_string(&Status::of(player)) {
+            Ok(json) => published.status = json,
+            Err(err) => eprintln!("cannot serialize the status: {err}"),
+        }
+    }
+}
+
+fn respond(mut stream: TcpStream, published: &Mutex<Published>) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    let mut request = String::new();
+    BufReader::new(&stream).read_line(&mut request)?;
+
+    let mut parts = request.split_whitespace();
+    let (code, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/" | "/status")) => (
+            "200 OK",
+            "application/json",
+            published.lock().unwrap().status.clone(),
+        ),
+        (Some("GET"), Some("/badge.svg")) => (
+            "200 OK",
+            "image/svg+xml",
+            published.lock().unwrap().badge.clone(),
+        ),
+        (Some("GET"), _) => (
+            "404 Not Found",
+            "application/json",
+            String::from(r#"{"error":"not found"}"#),
+        ),
+        _ => (
+            "405 Method Not Allowed",
+            "application/json",
+            String::from(r#"{"error":"only GET is supported"}"#),
+        ),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {code}\r\n\
+         Content-Type: {content_type}\r\n\
+         Content-Length: {length}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Connection: close\r\n\
+         \r\n\
+         {body}",
+        length = body.len(),
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,381 @@
+//! Monte Carlo runs for balancing: roll a batch of characters from consecutive seeds, play each one
+//! for the same amount of game time as fast as possible, and report how far they got.
+//!
+//! Each character is rolled and simulated exactly like `--seed N --no-sleep` would, so an outlier
+//! can be replayed by its seed.
+//!
+//! [`Comparison`] plays the same seeds under two [`Variant`]s, to see what a tuning change or a
+//! different content pack does to the pacing.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use pacing_core::{
+    config::{self, ContentPack, Rating, Tuning},
+    lingo::generate_name,
+    mechanics::{Player, Simulation, StatsBuilder, TickPolicy},
+    rules::RuleSet,
+    Rand, SliceExt,
+};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Csv,
+    Json,
+}
+
+pub struct Batch {
+    pub runs: usize,
+    pub game_time: Duration,
+    pub seed: u64,
+    pub rating: Rating,
+    pub synthesize: bool,
+    pub tuning: Tuning,
+    pub rules: RuleSet,
+    /// Played instead of the global content, if set
+    pub content: Option<Arc<ContentPack>>,
+}
+
+/// One side of a comparison
+#[derive(Clone, Debug, Default)]
+pub struct Variant {
+    pub tuning: Tuning,
+    pub rules: RuleSet,
+    /// `None` plays the same content as the other side
+    pub content: Option<Arc<ContentPack>>,
+}
+
+/// How a [`Variant`] is written down, e.g.
+/// `(tuning: (level_up_minutes: 15), rules: (), content: Some("harsh.ron"))`
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+struct VariantFile {
+    tuning: Tuning,
+    rules: RuleSet,
+    /// A content pack, relative to the variant's file
+    content: Option<PathBuf>,
+}
+
+impl Variant {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file = std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|data| ron::from_str::<VariantFile>(&data).map_err(|err| err.to_string()))?;
+        file.tuning
+            .validate()
+            .map_err(|err| format!("invalid tuning: {err}"))?;
+        let content = match file.content {
+            Some(pack) => {
+                let pack = path.parent().unwrap_or(Path::new("")).join(pack);
+                let content = std::fs::read_to_string(&pack)
+                    .map_err(|err| err.to_string())
+                    .and_then(|data| {
+                        ron::from_str::<ContentPack>(&data).map_err(|err| err.to_string())
+                    })
+                    .map_err(|err| format!("cannot load content from {}: {err}", pack.display()))?;
+                Some(content.shared())
+            }
+            None => None,
+        };
+        Ok(Self {
+            tuning: file.tuning,
+            rules: file.rules,
+            content,
+        })
+    }
+}
+
+/// When a character got where, in game time
+#[derive(Default)]
+struct Trace {
+    /// When each level was reached, starting with level 1
+    levels: Vec<f32>,
+    /// When each act started, starting with the prologue
+    acts: Vec<f32>,
+    /// The gold earned so far at each of the [`Comparison::CHECKPOINTS`]
+    gold: Vec<isize>,
+}
+
+/// How one character ended up
+#[derive(Debug, serde::Serialize)]
+pub struct Run {
+    pub seed: u64,
+    pub race: String,
+    pub class: String,
+    pub level: usize,
+    pub gold_gained: isize,
+    pub acts_completed: usize,
+    pub power_level: usize,
+}
+
+/// The spread of one statistic across every run
+#[derive(Debug, Default, serde::Serialize)]
+pub struct Spread {
+    pub min: f64,
+    pub mean: f64,
+    pub median: f64,
+    pub max: f64,
+}
+
+impl Spread {
+    fn of(values: impl Iterator<Item = f64>) -> Self {
+        let mut values = values.collect::<Vec<_>>();
+        if values.is_empty() {
+            return Self::default();
+        }
+        values.sort_by(f64::total_cmp);
+
+        let mid = values.len() / 2;
+        let median = if values.len() % 2 == 0 {
+            (values[mid - 1] + values[mid]) / 2.0
+        } else {
+            values[mid]
+        };
+        Self {
+            min: values[0],
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+            median,
+            max: values[values.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct Report {
+    pub runs: usize,
+    pub hours: f64,
+    pub level: Spread,
+    pub gold_gained: Spread,
+    pub acts_completed: Spread,
+    pub power_level: Spread,
+    pub characters: Vec<Run>,
+}
+
+impl Batch {
+    /// Play every character, and sum up how far they got
+    pub fn run(&self) -> Report {
+        let (characters, _): (Vec<_>, Vec<_>) = self.play_all(Self::threads()).into_iter().unzip();
+        let spread = |f: fn(&Run) -> f64| Spread::of(characters.iter().map(f));
+
+        Report {
+            runs: self.runs,
+            hours: self.game_time.as_secs_f64() / 3600.0,
+            level: spread(|run| run.level as f64),
+            gold_gained: spread(|run| run.gold_gained as f64),
+            acts_completed: spread(|run| run.acts_completed as f64),
+            power_level: spread(|run| run.power_level as f64),
+            characters,
+        }
+    }
+
+    fn threads() -> usize {
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    }
+
+    /// Play every character, spread across up to `threads`, in the order of their seeds
+    fn play_all(&self, threads: usize) -> Vec<(Run, Trace)> {
+        let workers = threads.clamp(1, self.runs.max(1));
+        let next = AtomicUsize::new(0);
+        let runs = Mutex::new(Vec::with_capacity(self.runs));
+
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= self.runs {
+                        break;
+                    }
+                    let run = self.play(self.seed.wrapping_add(i as u64));
+                    runs.lock().unwrap().push(run);
+                });
+            }
+        });
+
+        let mut runs = runs.into_inner().unwrap();
+        runs.sort_by_key(|(run, _)| run.seed);
+        runs
+    }
+
+    fn play(&self, seed: u64) -> (Run, Trace) {
+        let rng = Rand::seed(seed);
+        let content = self.content.clone().unwrap_or_else(config::content);
+        let mut player = config::with_content(&content, || {
+            Player::new(
+                generate_name(None, &rng),
+                content.races.choice(&rng).clone(),
+                content.classes.choice(&rng).clone(),
+                StatsBuilder::default().roll(&rng),
+            )
+        });
+        player.rating = self.rating;
+        let gold = player.inventory.gold();
+
+        let mut simulation = Simulation::with_seed(player, seed);
+        if let Some(content) = &self.content {
+            simulation.set_content(Arc::clone(content));
+        }
+        simulation.set_synthesis(self.synthesize);
+        simulation.set_tuning(self.tuning.clone());
+        simulation.set_rules(self.rules.clone());
+        simulation.set_tick_policy(TickPolicy::Turbo);
+
+        let mut trace = Trace::default();
+        let checkpoint = self.game_time.as_secs_f32() / Comparison::CHECKPOINTS as f32;
+        while simulation.player.elapsed < self.game_time.as_secs_f32() {
+            simulation.tick();
+
+            let player = &simulation.player;
+            while trace.levels.len() < player.level {
+                trace.levels.push(player.elapsed)
+            }
+            while trace.acts.len() as i32 <= player.quest_book.act() {
+                trace.acts.push(player.elapsed)
+            }
+            while player.elapsed >= checkpoint * (trace.gold.len() + 1) as f32 {
+                trace.gold.push(player.statistics.gold_earned)
+            }
+        }
+
+        let player = &simulation.player;
+        let run = Run {
+            seed,
+            race: player.race.name.to_string(),
+            class: player.class.name.to_string(),
+            level: player.level,
+            gold_gained: player.inventory.gold() - gold,
+            acts_completed: player.quest_book.act().max(0) as usize,
+            power_level: player.power_level(),
+        };
+        (run, trace)
+    }
+}
+
+/// How the same characters fared under two variants
+#[derive(Debug, serde::Serialize)]
+pub struct Comparison {
+    pub runs: usize,
+    pub hours: f64,
+    pub rows: Vec<Row>,
+}
+
+/// The median of one measurement under each variant
+#[derive(Debug, serde::Serialize)]
+pub struct Row {
+    pub metric: String,
+    pub a: f64,
+    pub b: f64,
+}
+
+impl Comparison {
+    /// The gold curve is sampled this many times over the run
+    pub const CHECKPOINTS: usize = 6;
+    const LEVELS: [usize; 6] = [5, 10, 15, 20, 30, 40];
+
+    /// Play `batch` under both variants, on the same seeds
+    pub fn run(batch: &Batch, a: &Variant, b: &Variant) -> Self {
+        let with = |variant: &Variant| Batch {
+            tuning: variant.tuning.clone(),
+            rules: variant.rules.clone(),
+            content: variant.content.clone().or_else(|| batch.content.clone()),
+            ..*batch
+        };
+        let traces = |variant: &Variant, threads: usize| -> Vec<Trace> {
+            let runs = with(variant).play_all(threads);
+            runs.into_iter().map(|(_, trace)| trace).collect()
+        };
+        // both batches play at once, so they share the cores between them
+        let threads = Batch::threads();
+        let (a, b) = std::thread::scope(|scope| {
+            let a = scope.spawn(|| traces(a, threads.div_ceil(2)));
+            let b = traces(b, threads / 2);
+            (a.join().unwrap(), b)
+        });
+
+        let hours = batch.game_time.as_secs_f64() / 3600.0;
+        let mut rows = vec![];
+        let mut row = |metric: String, f: &dyn Fn(&Trace) -> Option<f64>| {
+            // only measured when most characters got that far under both
+            if let (Some(a), Some(b)) = (median(&a, f), median(&b, f)) {
+                rows.push(Row { metric, a, b })
+            }
+        };
+
+        for level in Self::LEVELS {
+            row(format!("hours to level {level}"), &|trace| {
+                Some(f64::from(*trace.levels.get(level - 1)?) / 3600.0)
+            });
+        }
+        for act in 1..=9 {
+            row(format!("hours in act {act}"), &|trace| {
+                let length = trace.acts.get(act + 1)? - trace.acts.get(act)?;
+                Some(f64::from(length) / 3600.0)
+            });
+        }
+        for checkpoint in 0..Self::CHECKPOINTS {
+            let at = hours * (checkpoint + 1) as f64 / Self::CHECKPOINTS as f64;
+            row(format!("gold earned by hour {at:.1}"), &|trace| {
+                trace.gold.get(checkpoint).map(|&gold| gold as f64)
+            });
+        }
+
+        Self {
+            runs: batch.runs,
+            hours,
+            rows,
+        }
+    }
+
+    pub fn render(&self, format: Format) -> Result<String, String> {
+        match format {
+            Format::Json => serde_json::to_string_pretty(self).map_err(|err| err.to_string()),
+            Format::Csv => {
+                let mut out = String::from("metric,a,b,change\n");
+                for row in &self.rows {
+                    let change = match row.a {
+                        0.0 => String::new(),
+                        a => format!("{:+.1}%", (row.b - a) / a * 100.0),
+                    };
+                    out.push_str(&format!(
+                        "{},{:.2},{:.2},{change}\n",
+                        row.metric, row.a, row.b
+                    ));
+                }
+                Ok(out)
+            }
+        }
+    }
+}
+
+/// The median across the characters that have the measurement, if most of them do
+fn median(traces: &[Trace], f: &dyn Fn(&Trace) -> Option<f64>) -> Option<f64> {
+    let values = traces.iter().filter_map(f).collect::<Vec<_>>();
+    (values.len() * 2 > traces.len()).then(|| Spread::of(values.into_iter()).median)
+}
+
+impl Report {
+    pub fn render(&self, format: Format) -> Result<String, String> {
+        match format {
+            Format::Json => serde_json::to_string_pretty(self).map_err(|err| err.to_string()),
+            Format::Csv => {
+                let mut out = String::from("statistic,min,mean,median,max\n");
+                for (name, spread) in [
+                    ("level", &self.level),
+                    ("gold_gained", &self.gold_gained),
+                    ("acts_completed", &self.acts_completed),
+                    ("power_level", &self.power_level),
+                ] {
+                    out.push_str(&format!(
+                        "{name},{},{:.2},{},{}\n",
+                        spread.min, spread.mean, spread.median, spread.max
+                    ));
+                }
+                Ok(out)
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! The exit codes documented in the usage, which scripts checking the pacing rely on
+
+use std::{
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+/// Run the binary with a new character saved to `name` in the temp dir
+fn headless(name: &str) -> Command {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = std::fs::remove_file(&path);
+
+    let mut command = Command::new(env!("CARGO_BIN_EXE_pacing_headless"));
+    command
+        .arg("--character")
+        .arg(path)
+        .args(["--seed", "7", "--quiet"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    command
+}
+
+fn code(command: &mut Command) -> i32 {
+    command.status().unwrap().code().expect("exited normally")
+}
+
+#[test]
+fn success() {
+    let mut command = headless("success.ron");
+    command.args([
+        "--no-sleep",
+        "--until-level",
+        "2",
+        "--expect-min-level",
+        "2",
+    ]);
+    assert_eq!(code(&mut command), 0);
+}
+
+#[test]
+fn failure() {
+    let mut command = headless("failure.ron");
+    command.args(["--tuning", "no such tuning.ron"]);
+    assert_eq!(code(&mut command), 1);
+}
+
+#[test]
+fn usage() {
+    assert_eq!(code(headless("usage.ron").arg("--no-such-flag")), 2);
+    assert_eq!(
+        code(headless("usage.ron").args(["--expect-max-hours", "1"])),
+        2,
+        "--expect-max-hours without a target"
+    );
+
+    let mut command = Command::new(env!("CARGO_BIN_EXE_pacing_headless"));
+    command
+        .args(["--simulate", "2", "--expect-min-level", "2"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    assert_eq!(code(&mut command), 2, "--expect with --simulate");
+}
+
+#[test]
+fn unmet() {
+    let mut command = headless("unmet.ron");
+    command.args([
+        "--no-sleep",
+        "--until-level",
+        "2",
+        "--expect-min-level",
+        "99",
+    ]);
+    assert_eq!(code(&mut command), 3);
+
+    // stopping short of the target fails even without an --expect
+    let mut command = headless("short.ron");
+    command.args(["--no-sleep", "--until-level", "99", "--max-game-time", "1m"]);
+    assert_eq!(code(&mut command), 3);
+}
+
+#[cfg(unix)]
+#[test]
+fn interrupted() {
+    let mut child = headless("interrupted.ron")
+        .args(["--until-level", "99", "--expect-min-level", "99"])
+        .spawn()
+        .unwrap();
+    // give it time to install its signal handler
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    let status = Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(child.wait().unwrap().code(), Some(4));
+}
@@ -0,0 +1,234 @@
+use cursive::{
+    view::{Nameable as _, Resizable as _, Scrollable as _},
+    views::{Button, Dialog, DummyView, EditView, LinearLayout, Panel, SelectView, TextView},
+    Cursive, CursiveRunnable,
+};
+
+use pacing_core::{
+    config::{self, Rating, Stat},
+    lingo::{self, generate_name},
+    mechanics::{Player, RollMode, Stats, StatsBuilder},
+    Rand, SliceExt,
+};
+
+struct Creation {
+    player: Player,
+    stats_builder: StatsBuilder,
+    rng: Rand,
+    sold: bool,
+}
+
+/// Roll up a character, like the egui frontend's creation screen. This is `None` if the player quit
+pub fn create(cursive: &mut CursiveRunnable) -> Option<Player> {
+    let rng = Rand::new();
+    let mut stats_builder = StatsBuilder::default();
+    let content = config::content();
+    let player = Player::new(
+        generate_name(None, &rng),
+        content.races.choice(&rng).clone(),
+        content.classes.choice(&rng).clone(),
+        stats_builder.roll(&rng),
+    );
+
+    cursive.add_layer(
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(
+                    LinearLayout::horizontal()
+                        .child(
+                            EditView::new()
+                                .content(&player.name)
+                                .on_edit(|cursive, name, _| {
+                                    with_creation(cursive, |creation| {
+                                        creation.player.name = name.to_string()
+                                    })
+                                })
+                                .with_name("name")
+                                .fixed_width(24),
+                        )
+                        .child(Button::new("Random", |cursive| {
+                            let name = with_creation(cursive, |creation| {
+                                creation.player.name = generate_name(None, &creation.rng);
+                                creation.player.name.clone()
+                            });
+                            cursive
+                                .call_on_name("name", |view: &mut EditView| view.set_content(name));
+                        }))
+                        .child(DummyView)
+                        .child(TextView::new("Themes "))
+                        .child(
+                            EditView::new()
+                                .on_edit(|cursive, themes, _| {
+                                    with_creation(cursive, |creation| {
+                                        creation.player.themes = lingo::themes(themes)
+                                    })
+                                })
+                                .fixed_width(24),
+                        )
+                        .child(DummyView)
+                        .child(
+                            SelectView::new()
+                                .popup()
+                                .with_all(Rating::ALL.map(|rating| (rating.as_str(), rating)))
+                                .selected(
+                                    Rating::ALL
+                                        .iter()
+                                        .position(|&r| r == player.rating)
+                                        .unwrap_or(0),
+                                )
+                                .on_submit(|cursive, rating: &Rating| {
+                                    with_creation(cursive, |creation| {
+                                        creation.player.rating = *rating
+                                    })
+                                }),
+                        ),
+                )
+                .child(DummyView)
+                .child(
+                    LinearLayout::horizontal()
+                        .child(Panel::new(races(&player)).title("Race"))
+                        .child(Panel::new(classes(&player)).title("Class"))
+                        .child(
+                            Panel::new(
+                                LinearLayout::vertical()
+                                    .child(
+                                        TextView::new(stat_sheet(&player.stats)).with_name("stats"),
+                                    )
+                                    .child(DummyView)
+                                    .child(
+                                        LinearLayout::horizontal()
+                                            .child(Button::new("Roll", |cursive| {
+                                                reroll(cursive, |creation| {
+                                                    creation.stats_builder.roll(&creation.rng)
+                                                })
+                                            }))
+                                            .child(Button::new("Unroll", |cursive| {
+                                                reroll(cursive, |creation| {
+                                                    creation.stats_builder.unroll()
+                                                })
+                                            })),
+                                    )
+                                    .child(
+                                        SelectView::new()
+                                            .popup()
+                                            .with_all(
+                                                RollMode::ALL.map(|mode| (mode.to_string(), mode)),
+                                            )
+                                            .on_submit(|cursive, &mode: &RollMode| {
+                                                reroll(cursive, |creation| {
+                                                    creation.stats_builder.mode = mode;
+                                                    creation.stats_builder.roll(&creation.rng)
+                                                })
+                                            }),
+                                    ),
+                            )
+                            .title("Stats"),
+                        ),
+                ),
+        )
+        .title("Create a character")
+        .button("Sold!", |cursive| {
+            with_creation(cursive, |creation| creation.sold = true)
+        })
+        .button("Quit", Cursive::quit),
+    );
+
+    cursive.set_user_data(Creation {
+        player,
+        stats_builder,
+        rng,
+        sold: false,
+    });
+
+    crate::step_until(cursive, |creation: &Creation| creation.sold);
+
+    cursive
+        .take_user_data::<Creation>()
+        .filter(|creation| creation.sold)
+        .map(|mut creation| {
+            creation.player.outfit();
+            creation.player
+        })
+}
+
+fn with_creation<R>(cursive: &mut Cursive, f: impl FnOnce(&mut Creation) -> R) -> R {
+    cursive
+        .with_user_data(f)
+        .expect("the creation screen should have its state")
+}
+
+fn races(player: &Player) -> impl cursive::View {
+    let content = config::content();
+    let selected = content
+        .races
+        .iter()
+        .position(|race| race.name == player.race.name)
+        .unwrap_or(0);
+
+    SelectView::new()
+        .with_all(
+            content
+                .races
+                .iter()
+                .enumerate()
+                .map(|(i, race)| (race.name.to_string(), i)),
+        )
+        .selected(selected)
+        .on_select(|cursive, &i| {
+            with_creation(cursive, |creation| {
+                creation.player.race = config::content().races[i].clone()
+            })
+        })
+        .scrollable()
+}
+
+fn classes(player: &Player) -> impl cursive::View {
+    let content = config::content();
+    let selected = content
+        .classes
+        .iter()
+        .position(|class| class.name == player.class.name)
+        .unwrap_or(0);
+
+    SelectView::new()
+        .with_all(
+            content
+                .classes
+                .iter()
+                .enumerate()
+                .map(|(i, class)| (class.name.to_string(), i)),
+        )
+        .selected(selected)
+        .on_select(|cursive, &i| {
+            with_creation(cursive, |creation| {
+                creation.player.class = config::content().classes[i].clone()
+            })
+        })
+        .scrollable()
+}
+
+fn reroll(cursive: &mut Cursive, roll: impl FnOnce(&mut Creation) -> Stats) {
+    let sheet = with_creation(cursive, |creation| {
+        creation.player.stats = roll(creation);
+        creation.player.roll_mode = creation.stats_builder.rolled_with();
+        stat_sheet(&creation.player.stats)
+    });
+    cursive.call_on_name("stats", |view: &mut TextView| view.set_content(sheet));
+}
+
+fn stat_sheet(stats: &Stats) -> String {
+    let mut total = 0;
+    let mut lines = vec![];
+    for &(stat, value) in stats.iter() {
+        if let Stat::HpMax = stat {
+            lines.push(String::new());
+        }
+        lines.push(format!("{:<7}{value:>3}", stat.as_str()));
+        if !matches!(stat, Stat::HpMax | Stat::MpMax) {
+            total += value;
+        }
+    }
+    lines.push(String::new());
+    lines.push(format!("{:<7}{total:>3}", "Total"));
+    lines.join("\n")
+}
@@ -1,23 +1,32 @@
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
 
 use cursive::{
     align::HAlign,
     event::Event,
     theme::{Color, Palette, PaletteColor, Theme},
     view::Nameable,
-    views::{DummyView, LinearLayout, ListView, OnEventView, Panel, ProgressBar, TextView},
-    Cursive, View,
+    views::{Dialog, DummyView, LinearLayout, ListView, OnEventView, Panel, ProgressBar, TextView},
+    Cursive, CursiveRunnable, View,
 };
 
 use log::RecordBuilder;
 use pacing_core::{
-    config::{CLASSES, RACES},
-    format::Roman,
-    lingo::generate_name,
-    mechanics::{Bar, Player, Simulation, StatsBuilder},
-    Rand, SliceExt,
+    balance,
+    checkpoint::Session,
+    config::GlyphStyle,
+    format::{Roman, Time},
+    lingo::act_name,
+    mechanics::{Bar, Simulation},
 };
 
+mod creation;
+mod save;
+mod select;
+use select::Selection;
+
 fn default_palette() -> Palette {
     use PaletteColor::*;
     [
@@ -44,9 +53,27 @@ fn default_palette() -> Palette {
 #[derive(Clone)]
 struct App {
     simulation: Arc<Mutex<Simulation>>,
+    session: Session,
 }
 
 impl App {
+    const SUMMARY: &'static str = "session_summary";
+
+    // the first 'q' shows what happened this session, the second one (or the button) quits
+    fn quit(&self, cursive: &mut Cursive) {
+        if cursive.find_name::<Dialog>(Self::SUMMARY).is_some() {
+            return cursive.quit();
+        }
+
+        let summary = self.session.end(&mut self.get().simulation.player);
+        cursive.add_layer(
+            Dialog::text(summary.to_string())
+                .title("Farewell")
+                .button("Quit", Cursive::quit)
+                .with_name(Self::SUMMARY),
+        );
+    }
+
     fn get(&self) -> AppRef<'_> {
         AppRef {
             simulation: self.simulation.lock().unwrap(),
@@ -59,13 +86,27 @@ struct AppRef<'a> {
 }
 
 impl AppRef<'_> {
-    fn make_progress_bar(bar: &Bar) -> ProgressBar {
+    fn make_progress_bar(&self, bar: &Bar) -> ProgressBar {
+        use cursive::theme::BaseColor::Red;
+        const BLINK: f32 = 1.0;
+
+        // blink for a moment after filling up, in real time so it's the same at any time scale
+        let blinking = bar
+            .since_completed(self.simulation.player.elapsed)
+            .map(|since| since / self.simulation.speed())
+            .filter(|&since| since < BLINK)
+            .map_or(false, |since| (since * 8.0) as u32 % 2 == 0);
+
         let mut pb = ProgressBar::new()
             .min(0 as usize)
             .with_label(|_, _| String::new())
-            .with_color(Color::Dark(cursive::theme::BaseColor::Red))
-            .max(bar.max as _);
-        pb.set_value(bar.pos as _);
+            .with_color(if blinking {
+                Color::Light(Red)
+            } else {
+                Color::Dark(Red)
+            })
+            .max(if blinking { 1 } else { bar.max as _ });
+        pb.set_value(if blinking { 1 } else { bar.pos as _ });
         pb
     }
 }
@@ -80,6 +121,8 @@ impl AppRef<'_> {
                     .child(self.right_view()),
             )
             .child(self.bottom_view())
+            .child(self.history_view())
+            .child(self.journal_view())
     }
 
     fn left_panel(&self) -> impl View {
@@ -98,44 +141,120 @@ impl AppRef<'_> {
         LinearLayout::vertical()
             .child(self.plot_development())
             .child(DummyView)
+            .child(self.quest_board())
+            .child(DummyView)
             .child(self.quest_list())
     }
 
     fn bottom_view(&self) -> impl View {
         let mut ll = LinearLayout::vertical();
+        if let Some(dungeon) = &self.simulation.player.dungeon {
+            ll.add_child(
+                LinearLayout::horizontal()
+                    .child(TextView::new(format!("{} ", dungeon.name)))
+                    .child(self.make_progress_bar(&dungeon.bar)),
+            )
+        }
+        if let Some(arena) = &self.simulation.player.arena {
+            let time_left = arena.time_left(self.simulation.player.elapsed).max(0.0);
+            ll.add_child(
+                LinearLayout::horizontal()
+                    .child(TextView::new(format!(
+                        "{} ({} left) ",
+                        arena.name,
+                        Time::compact(time_left)
+                    )))
+                    .child(self.make_progress_bar(&arena.bar)),
+            )
+        }
+        if let Some(expedition) = &self.simulation.player.expedition {
+            ll.add_child(
+                LinearLayout::horizontal()
+                    .child(TextView::new("Treasure hunt "))
+                    .child(self.make_progress_bar(&expedition.bar)),
+            )
+        }
         if let Some(task) = &self.simulation.player.task {
-            ll.add_child(TextView::new(&*task.description))
+            ll.add_child(TextView::new(task.decorated(GlyphStyle::Ascii)))
         }
         ll.child(self.progress_bar())
     }
 
+    fn history_view(&self) -> impl View {
+        const MAX_SHOWN: usize = 5;
+
+        let history = self.simulation.history();
+        let skip = history.len().saturating_sub(MAX_SHOWN);
+        Panel::new(
+            history
+                .skip(skip)
+                .fold(LinearLayout::vertical(), |ll, task| {
+                    ll.child(TextView::new(task))
+                }),
+        )
+        .title("History")
+    }
+
+    fn journal_view(&self) -> impl View {
+        const MAX_SHOWN: usize = 5;
+
+        let lines = self
+            .simulation
+            .event_log()
+            .with_journal(&self.simulation.player.journal);
+        let skip = lines.len().saturating_sub(MAX_SHOWN);
+        Panel::new(lines.into_iter().skip(skip).fold(
+            LinearLayout::vertical(),
+            |ll, (elapsed, line)| {
+                ll.child(TextView::new(format!("{} {line}", Time::clock(elapsed))))
+            },
+        ))
+        .title("Journal")
+    }
+
     fn equipment_list(&self) -> impl View {
         let mut lv = ListView::new();
 
-        for (item, stat) in self.simulation.player.equipment.iter() {
-            lv.add_child(item.as_str(), TextView::new(stat).h_align(HAlign::Right))
+        let equipment = &self.simulation.player.equipment;
+        for (item, _) in equipment.iter() {
+            lv.add_child(
+                item.as_str(),
+                TextView::new(equipment.display(item).unwrap_or_default()).h_align(HAlign::Right),
+            )
         }
 
         Panel::new(lv).title("Equipment")
     }
 
     fn inventory_list(&self) -> impl View {
-        let mut lv = ListView::new().child("Item", TextView::new("Qty")).child(
-            "Gold",
-            TextView::new(self.simulation.player.inventory.gold().to_string())
-                .h_align(HAlign::Right),
-        );
+        let mut lv = ListView::new()
+            .child("Item", TextView::new("Qty"))
+            .child(
+                "Gold",
+                TextView::new(self.simulation.player.inventory.gold().to_string())
+                    .h_align(HAlign::Right),
+            )
+            .child(
+                "Bank",
+                TextView::new(self.simulation.player.bank.balance().to_string())
+                    .h_align(HAlign::Right),
+            );
 
         for (item, qty) in self.simulation.player.inventory.items() {
             lv.add_child(item, TextView::new(qty.to_string()).h_align(HAlign::Right))
         }
 
+        let outlook = self.simulation.player.market.outlook().unwrap_or_default();
         Panel::new(
-            LinearLayout::vertical().child(lv).child(DummyView).child(
-                LinearLayout::vertical()
-                    .child(TextView::new("Encumbrance"))
-                    .child(self.encumbrance_bar()),
-            ),
+            LinearLayout::vertical()
+                .child(lv)
+                .child(TextView::new(outlook))
+                .child(DummyView)
+                .child(
+                    LinearLayout::vertical()
+                        .child(TextView::new("Encumbrance"))
+                        .child(self.encumbrance_bar()),
+                ),
         )
         .title("Inventory")
     }
@@ -165,10 +284,41 @@ impl AppRef<'_> {
                 )
                 .child(DummyView)
                 .child(self.plot_bar())
+                .child(eta(balance::upcoming(&self.simulation).next_act()))
+                .child(self.ascension())
         })
         .title("Plot development")
     }
 
+    fn ascension(&self) -> impl View {
+        let player = &self.simulation.player;
+        let mut lines = vec![];
+        if let Some(last) = self.simulation.rules().final_act {
+            lines.push(format!("Ascends after {}", act_name(last)));
+        }
+        if player.legacy.ascensions > 0 {
+            lines.push(player.legacy.to_string());
+        }
+        TextView::new(lines.join("\n"))
+    }
+
+    fn quest_board(&self) -> impl View {
+        let player = &self.simulation.player;
+        Panel::new(player.board.bounties().fold(ListView::new(), |lv, bounty| {
+            let mark = if bounty.claimed { "x" } else { " " };
+            lv.child(
+                &format!("[{mark}] {}", bounty.objective),
+                TextView::new(format!(
+                    "{}/{}",
+                    bounty.progress(player),
+                    bounty.objective.goal()
+                ))
+                .h_align(HAlign::Right),
+            )
+        }))
+        .title("Quest board")
+    }
+
     fn quest_list(&self) -> impl View {
         Panel::new({
             let mut lv = self
@@ -179,13 +329,27 @@ impl AppRef<'_> {
                 .fold(ListView::new(), |lv, q| {
                     lv.child(&format!("[x] {q}"), DummyView)
                 });
-            if let Some(current) = self.simulation.player.quest_book.current_quest() {
-                lv.add_child(&format!("[ ] {current}"), DummyView)
+            let mut details = vec![];
+            let quest_book = &self.simulation.player.quest_book;
+            let elapsed = self.simulation.player.elapsed;
+            if let Some(current) = quest_book.current_quest() {
+                lv.add_child(&format!("[ ] {current}"), DummyView);
+
+                // there's no hovering in a terminal, so the current quest's details are always shown
+                if let Some(giver) = &current.giver {
+                    details.push(format!("for {giver}"));
+                }
+                if let Some(age) = current.age(elapsed) {
+                    details.push(format!("{} old", Time::compact(age)));
+                }
+                if let Some(remaining) = quest_book.remaining(elapsed) {
+                    details.push(format!("~{} to go", Time::compact(remaining)));
+                }
             }
 
             LinearLayout::vertical()
                 .child(lv)
-                .child(DummyView)
+                .child(TextView::new(details.join(", ")))
                 .child(self.quest_bar())
         })
         .title("Quests")
@@ -198,7 +362,8 @@ impl AppRef<'_> {
                 .child(DummyView)
                 .child(self.stat_sheet())
                 .child(DummyView)
-                .child(self.experience_bar()),
+                .child(self.experience_bar())
+                .child(eta(balance::upcoming(&self.simulation).next_level())),
         )
         .title("Character sheet")
     }
@@ -219,23 +384,23 @@ impl AppRef<'_> {
     }
 
     fn progress_bar(&self) -> impl View {
-        Self::make_progress_bar(&self.simulation.player.task_bar)
+        self.make_progress_bar(&self.simulation.player.task_bar)
     }
 
     fn experience_bar(&self) -> impl View {
-        Self::make_progress_bar(&self.simulation.player.exp_bar)
+        self.make_progress_bar(&self.simulation.player.exp_bar)
     }
 
     fn encumbrance_bar(&self) -> impl View {
-        Self::make_progress_bar(&self.simulation.player.inventory.encumbrance)
+        self.make_progress_bar(&self.simulation.player.inventory.encumbrance)
     }
 
     fn quest_bar(&self) -> impl View {
-        Self::make_progress_bar(&self.simulation.player.quest_book.quest)
+        self.make_progress_bar(&self.simulation.player.quest_book.quest)
     }
 
     fn plot_bar(&self) -> impl View {
-        Self::make_progress_bar(&self.simulation.player.quest_book.plot)
+        self.make_progress_bar(&self.simulation.player.quest_book.plot)
     }
 
     fn trait_sheet(&self) -> impl View {
@@ -246,9 +411,33 @@ impl AppRef<'_> {
             ("Level", &*self.simulation.player.level.to_string()),
             ("Class", &*self.simulation.player.class.name),
             ("Race", &*self.simulation.player.race.name),
+            ("Location", &*self.simulation.player.world.location()),
         ] {
             ch.add_child(trait_, TextView::new(value).h_align(HAlign::Right))
         }
+        if let Some(title) = self.simulation.player.bestiary.title() {
+            ch.add_child("Title", TextView::new(title).h_align(HAlign::Right))
+        }
+        if let Some(ironman) = &self.simulation.player.ironman {
+            ch.add_child(
+                "Ironman",
+                TextView::new(ironman.to_string()).h_align(HAlign::Right),
+            )
+        }
+
+        if let Some(rival) = &self.simulation.player.rival {
+            ch.add_child(
+                "Rival",
+                TextView::new(format!("{} ({})", rival.name, rival.level)).h_align(HAlign::Right),
+            )
+        }
+
+        if let Some(nemesis) = &self.simulation.player.nemesis {
+            ch.add_child(
+                "Nemesis",
+                TextView::new(format!("{nemesis} ({})", nemesis.level)).h_align(HAlign::Right),
+            )
+        }
         ch
     }
 
@@ -265,21 +454,32 @@ impl AppRef<'_> {
     }
 }
 
-fn main() {
-    let rng = Rand::new();
-
-    let player = Player::new(
-        generate_name(None, &rng),
-        RACES.choice(&rng).clone(),
-        CLASSES.choice(&rng).clone(),
-        StatsBuilder::default().roll(&rng),
-    );
-    let mut app = App {
-        simulation: Arc::new(Mutex::new(Simulation::new(player))),
-    };
+/// Run the layer on top until `done` says so, or the user quits, then remove it.
+///
+/// This steps by hand so the game can take over on the same runner afterwards
+fn step_until<T: 'static>(cursive: &mut CursiveRunnable, done: impl Fn(&T) -> bool) {
+    let mut runner = cursive.runner();
+    runner.refresh();
+    while runner.is_running()
+        && !runner
+            .with_user_data(|data: &mut T| done(data))
+            .unwrap_or(false)
+    {
+        runner.step();
+    }
+    runner.pop_layer();
+}
 
-    app.get().simulation.time_scale = 10.0;
+fn eta(after: Option<Duration>) -> TextView {
+    TextView::new(after.map_or_else(
+        || String::from("not long now"),
+        |after| format!("~{} to go", Time::compact(after.as_secs_f32())),
+    ))
+}
 
+fn main() {
+    // so warnings show up in the debug console
+    cursive::logger::init();
     let mut cursive = cursive::default();
 
     cursive.set_theme(Theme {
@@ -288,6 +488,34 @@ fn main() {
         palette: default_palette(),
     });
 
+    let (players, errors) = save::load_all();
+    for error in errors {
+        log::warn!("{error}")
+    }
+
+    let selection = if players.is_empty() {
+        Some(Selection::Create)
+    } else {
+        select::select(&mut cursive, players)
+    };
+    let played = match selection {
+        Some(Selection::Play(path, player)) => Some((path, player)),
+        Some(Selection::Create) => {
+            creation::create(&mut cursive).map(|player| (save::new_path(&player), player))
+        }
+        None => None,
+    };
+    let (path, player) = match played {
+        Some(played) => played,
+        None => return,
+    };
+    let app = App {
+        session: Session::start(&player),
+        simulation: Arc::new(Mutex::new(Simulation::new(player))),
+    };
+
+    app.get().simulation.time_scale = 10.0;
+
     cursive.add_fullscreen_layer(
         OnEventView::new(app.get().display().with_name("main_view")).on_event(Event::Refresh, {
             let app = app.clone();
@@ -298,15 +526,79 @@ fn main() {
     );
 
     cursive.add_global_callback('1', Cursive::toggle_debug_console);
-    cursive.add_global_callback('q', |s| s.quit());
+    cursive.add_global_callback('p', {
+        let app = app.clone();
+        move |_| {
+            let mut app = app.get();
+            if app.simulation.is_paused() {
+                app.simulation.resume()
+            } else {
+                app.simulation.pause()
+            }
+        }
+    });
+    cursive.add_global_callback('q', {
+        let app = app.clone();
+        move |cursive| app.quit(cursive)
+    });
     cursive.set_autorefresh(true);
 
     let mut cursive = cursive.into_runner();
     cursive.refresh();
 
+    const BANNER_DURATION: Duration = Duration::from_secs(3);
+    let mut banner = None;
+
+    const AUTOSAVE: Duration = Duration::from_secs(60);
+    let mut saved = Instant::now();
+
     while cursive.is_running() {
-        app.get().simulation.tick(&rng);
+        app.get().simulation.tick();
+
+        if saved.elapsed() >= AUTOSAVE {
+            if let Err(err) = save::save(&path, &app.get().simulation.player) {
+                log::warn!("{err}")
+            }
+            saved = Instant::now();
+        }
+
+        if let Some(diagnostic) = app.get().simulation.take_diagnostic() {
+            log::warn!("simulation stalled and was recovered: {diagnostic}")
+        }
+
+        let title_card = app.get().simulation.take_title_card();
+        // leave the layers alone while the session summary is on top
+        if cursive.find_name::<Dialog>(App::SUMMARY).is_some() {
+            cursive.step();
+            continue;
+        }
+
+        if let Some(card) = title_card {
+            if banner.is_some() {
+                cursive.pop_layer();
+            }
+            cursive.add_layer(Panel::new(
+                TextView::new(card.to_string()).h_align(HAlign::Center),
+            ));
+            banner = Some(Instant::now());
+        }
+
+        if banner.map_or(false, |shown: Instant| shown.elapsed() >= BANNER_DURATION) {
+            cursive.pop_layer();
+            banner = None;
+        }
 
         cursive.step();
     }
+
+    // restore the terminal first, so errors are visible
+    drop(cursive);
+    let saved = save::save(&path, &app.get().simulation.player);
+    match saved {
+        Ok(()) => println!("saved to {}", path.display()),
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1)
+        }
+    }
 }
@@ -0,0 +1,91 @@
+//! Characters are saved one per file in the data directory, in the same format as the egui saves
+
+use std::path::{Path, PathBuf};
+
+use pacing_core::{mechanics::Player, profile::Profile, save::SaveFile};
+
+const EXTENSION: &str = "save";
+
+pub fn dir() -> PathBuf {
+    pacing_core::save::data_dir()
+}
+
+/// A file for a new character, named after them but kept to letters, digits and underscores so
+/// it stays in the data directory. Characters that share a name get a number on the end instead
+/// of each other's file
+pub fn new_path(player: &Player) -> PathBuf {
+    let name = player
+        .name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            c if c.is_alphanumeric() => c.to_ascii_lowercase(),
+            _ => '_',
+        })
+        .collect::<String>();
+    let name = match name.trim_matches('_') {
+        "" => "hero",
+        name => name,
+    };
+
+    (1..)
+        .map(|n| match n {
+            1 => dir().join(format!("{name}.{EXTENSION}")),
+            n => dir().join(format!("{name}_{n}.{EXTENSION}")),
+        })
+        .find(|path| !path.exists())
+        .expect("there should be a free file name")
+}
+
+/// Every saved character and the file it's kept in, most recently played first, along with the
+/// saves that couldn't be loaded. A character stays in the same file even if they're renamed
+pub fn load_all() -> (Vec<(PathBuf, Player)>, Vec<String>) {
+    let (mut players, mut errors) = (vec![], vec![]);
+    let entries = match std::fs::read_dir(dir()) {
+        Ok(entries) => entries,
+        Err(_) => return (players, errors),
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()).filter(|path| {
+        path.extension()
+            .map_or(false, |ext| ext.eq_ignore_ascii_case(EXTENSION))
+    }) {
+        match std::fs::read(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|data| SaveFile::<()>::decode(&data, None).map_err(|err| err.to_string()))
+        {
+            Ok(save) => players.extend(
+                save.players
+                    .into_iter()
+                    .take(1)
+                    .map(|player| (path.clone(), player)),
+            ),
+            Err(err) => errors.push(format!("cannot load {}: {err}", path.display())),
+        }
+    }
+
+    players.sort_by_key(|(_, player)| std::cmp::Reverse(player.last_seen));
+    (players, errors)
+}
+
+/// Write `player` to `path`. The live player is left alone, only the copy that's written is
+/// compacted
+pub fn save(path: &Path, player: &Player) -> Result<(), String> {
+    let mut player = player.clone();
+    player.compact();
+    player.last_seen = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|time| time.as_secs());
+
+    let data = SaveFile::new(vec![player], Profile::default(), ())
+        .encode(None)
+        .map_err(|err| format!("cannot save {}: {err}", path.display()))?;
+
+    // write to a temporary file first so an interrupted save doesn't clobber the old one
+    let tmp = path.with_extension("tmp");
+    std::fs::create_dir_all(dir())
+        .and_then(|_| std::fs::write(&tmp, data))
+        .and_then(|_| std::fs::rename(&tmp, path))
+        .map_err(|err| format!("cannot write {}: {err}", path.display()))
+}
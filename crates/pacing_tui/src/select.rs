@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use cursive::{
+    view::Scrollable as _,
+    views::{Dialog, SelectView},
+    Cursive, CursiveRunnable,
+};
+
+use pacing_core::{lingo::act_name, mechanics::Player};
+
+pub enum Selection {
+    /// A saved character, and the file they're saved in
+    Play(PathBuf, Player),
+    Create,
+}
+
+#[derive(Default)]
+struct Choice(Option<Option<usize>>);
+
+/// Pick one of the saved characters, like the egui frontend's select screen. This is `None` if the
+/// player quit
+pub fn select(
+    cursive: &mut CursiveRunnable,
+    mut players: Vec<(PathBuf, Player)>,
+) -> Option<Selection> {
+    let list = SelectView::new()
+        .with_all(players.iter().enumerate().map(|(i, (_, player))| {
+            let label = format!(
+                "{name}, level {level} {race} {class}, {act}, power {power}",
+                name = player.name,
+                level = player.level,
+                race = player.race.name,
+                class = player.class.name,
+                act = act_name(player.quest_book.act()),
+                power = player.power_level(),
+            );
+            (label, i)
+        }))
+        .on_submit(|cursive, &i: &usize| {
+            cursive.with_user_data(|choice: &mut Choice| choice.0 = Some(Some(i)));
+        });
+
+    cursive.add_layer(
+        Dialog::around(list.scrollable())
+            .title("Choose a character")
+            .button("New character", |cursive| {
+                cursive.with_user_data(|choice: &mut Choice| choice.0 = Some(None));
+            })
+            .button("Quit", Cursive::quit),
+    );
+
+    cursive.set_user_data(Choice::default());
+    crate::step_until(cursive, |choice: &Choice| choice.0.is_some());
+
+    match cursive.take_user_data::<Choice>()?.0? {
+        Some(i) => {
+            let (path, player) = players.swap_remove(i);
+            Some(Selection::Play(path, player))
+        }
+        None => Some(Selection::Create),
+    }
+}
@@ -0,0 +1,58 @@
+//! A JavaScript wrapper around the simulation, for embedding a hero in any web page.
+//!
+//! Snapshots, saves and events are JSON strings from [`pacing_core::embed`], so they can be
+//! handed to `JSON.parse` or stored as-is.
+
+use pacing_core::{
+    embed,
+    mechanics::{ExternalEvent, Priority, Simulation},
+    Rand,
+};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct Hero {
+    simulation: Simulation,
+}
+
+#[wasm_bindgen]
+impl Hero {
+    /// Roll a new random hero
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: u32) -> Self {
+        let rng = Rand::seed(seed as _);
+        Self {
+            simulation: Simulation::with_seed(embed::roll(&rng), seed as _),
+        }
+    }
+
+    /// Restore a hero from a previous [`Hero::save`]
+    pub fn load(state: &str, seed: u32) -> Result<Hero, JsError> {
+        Ok(Self {
+            simulation: Simulation::with_seed(embed::load(state)?, seed as _),
+        })
+    }
+
+    /// Advance by `dt` seconds of game time, returning a JSON array of the events that happened
+    pub fn advance(&mut self, dt: f32) -> Result<String, JsError> {
+        let events = embed::advance(&mut self.simulation, dt);
+        Ok(serde_json::to_string(&events)?)
+    }
+
+    /// Queue up an event from outside the game, like "Reading a donation message from X", to
+    /// play out after the current task. Returns false if too many are already waiting
+    pub fn inject(&mut self, description: &str, source: &str) -> bool {
+        let task = ExternalEvent::new(description).source(source).into_task();
+        self.simulation.inject_task(task, Priority::Last).is_ok()
+    }
+
+    /// A JSON object describing the hero, for drawing it
+    pub fn snapshot(&self) -> Result<String, JsError> {
+        let snapshot = embed::Snapshot::of(&self.simulation.player);
+        Ok(serde_json::to_string(&snapshot)?)
+    }
+
+    pub fn save(&self) -> Result<String, JsError> {
+        Ok(embed::save(&self.simulation.player)?)
+    }
+}